@@ -11,7 +11,7 @@ pub fn build() -> App<'static, 'static> {
                 .overrides_with("almost-all")
                 .long("all")
                 .multiple(true)
-                .help("Do not ignore entries starting with ."),
+                .help("Do not ignore entries starting with ., nor the hidden ones on Windows"),
         )
         .arg(
             Arg::with_name("almost-all")
@@ -27,10 +27,25 @@ pub fn build() -> App<'static, 'static> {
                 .possible_value("always")
                 .possible_value("auto")
                 .possible_value("never")
+                .possible_value("yes")
+                .possible_value("force")
+                .possible_value("tty")
+                .possible_value("if-tty")
+                .possible_value("no")
                 .default_value("auto")
                 .multiple(true)
                 .number_of_values(1)
-                .help("When to use terminal colours"),
+                .help("When to use terminal colours (yes and force mean always, tty and if-tty mean auto, no means never)"),
+        )
+        .arg(
+            Arg::with_name("color-theme")
+                .long("color-theme")
+                .visible_alias("theme")
+                .validator(validate_color_theme)
+                .default_value("default")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Which color theme to use: default, nord, gruvbox, solarized-dark or the path of a theme file"),
         )
         .arg(
             Arg::with_name("icon")
@@ -53,12 +68,39 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("Whether to use fancy or unicode icons"),
         )
+        .arg(
+            Arg::with_name("nerd-font-version")
+                .long("nerd-font-version")
+                .possible_value("2")
+                .possible_value("3")
+                .default_value("3")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Which version of the Nerd Fonts the fancy icons are taken from"),
+        )
+        .arg(
+            Arg::with_name("icon-override")
+                .long("icon-override")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("name=icon")
+                .validator(validate_icon_override)
+                .help("Override the icon of a file name, or of an extension with .ext=icon, eg: .rs=\\ue7a8"),
+        )
         .arg(
             Arg::with_name("indicators")
                 .short("F")
                 .long("classify")
+                .possible_value("always")
+                .possible_value("auto")
+                .possible_value("never")
+                .min_values(0)
+                .require_equals(true)
                 .multiple(true)
-                .help("Append indicator (one of */=>@|) at the end of the file names"),
+                .value_name("when")
+                .help(
+                    "Append indicator (one of */=>@|) at the end of the file names, always or only on a tty with --classify=auto",
+                ),
         )
         .arg(
             Arg::with_name("long")
@@ -67,6 +109,55 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Display extended file metadata as a table"),
         )
+        .arg(
+            Arg::with_name("hyperlink")
+                .long("hyperlink")
+                .possible_value("always")
+                .possible_value("auto")
+                .possible_value("never")
+                .default_value("never")
+                .multiple(true)
+                .number_of_values(1)
+                .help("When to print the file names as hyperlinks to the files"),
+        )
+        .arg(
+            Arg::with_name("paging")
+                .long("paging")
+                .possible_value("auto")
+                .possible_value("always")
+                .possible_value("never")
+                .default_value("never")
+                .multiple(true)
+                .number_of_values(1)
+                .help("When to write the output through $PAGER, or less -R"),
+        )
+        .arg(
+            Arg::with_name("tty")
+                .long("tty")
+                .possible_value("auto")
+                .possible_value("force")
+                .default_value("auto")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Keep the layout, colors and icons of a terminal when the output is redirected with --tty=force"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .possible_value("text")
+                .possible_value("csv")
+                .possible_value("json")
+                .default_value("text")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Output format: text, csv with one record per entry and a column per block, or json"),
+        )
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .multiple(true)
+                .help("Write the entries as a JSON document, shorthand for --output json"),
+        )
         .arg(
             Arg::with_name("ignore-config")
                 .long("ignore-config")
@@ -79,6 +170,106 @@ pub fn build() -> App<'static, 'static> {
                 .value_name("config-file")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("profile")
+                .long("profile")
+                .conflicts_with("ignore-config")
+                .help("Apply the options of a profile of the configuration file")
+                .value_name("name")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("date-groups")
+                .long("date-groups")
+                .multiple(true)
+                .help("List the entries under the headers of their age group (Today, This Week, This Month, Older) when sorting by time in the oneline or long layout"),
+        )
+        .arg(
+            Arg::with_name("respect-gitattributes")
+                .long("respect-gitattributes")
+                .multiple(true)
+                .help("Do not display the files with the export-ignore attribute in .gitattributes, which git archive leaves out"),
+        )
+        .arg(
+            Arg::with_name("set-title")
+                .long("set-title")
+                .multiple(true)
+                .help("Set the title of the terminal to the listed paths, when the output is a terminal"),
+        )
+        .arg(
+            Arg::with_name("grid-gap")
+                .long("grid-gap")
+                .multiple(true)
+                .number_of_values(1)
+                .takes_value(true)
+                .value_name("num")
+                .validator(validate_grid_gap)
+                .help("Number of spaces between the columns of the grid layout [default: 2]"),
+        )
+        .arg(
+            Arg::with_name("quote-name")
+                .short("Q")
+                .long("quote-name")
+                .multiple(true)
+                .help("Wrap the names in double quotes, escaping them as C strings"),
+        )
+        .arg(
+            Arg::with_name("quoting-style")
+                .long("quoting-style")
+                .possible_value("literal")
+                .possible_value("escape")
+                .possible_value("c")
+                .possible_value("locale")
+                .multiple(true)
+                .number_of_values(1)
+                .help("How to quote and escape the names, escaping the invalid UTF-8 bytes as \\xNN but with literal"),
+        )
+        .arg(
+            Arg::with_name("external-formatter")
+                .long("external-formatter")
+                .multiple(true)
+                .number_of_values(1)
+                .takes_value(true)
+                .value_name("cmd")
+                .help("Write the entries as JSON to the standard input of cmd and print its output instead"),
+        )
+        .arg(
+            Arg::with_name("truncate")
+                .long("truncate")
+                .multiple(true)
+                .number_of_values(1)
+                .takes_value(true)
+                .value_name("num")
+                .validator(validate_truncate)
+                .help("Truncate the names wider than num cells in the middle, keeping their extension"),
+        )
+        .arg(
+            Arg::with_name("min-columns")
+                .long("min-columns")
+                .multiple(true)
+                .number_of_values(1)
+                .takes_value(true)
+                .value_name("num")
+                .validator(validate_min_columns)
+                .help("Lay out the grid in at least num columns, when the entries fit in the width"),
+        )
+        .arg(
+            Arg::with_name("max-entries")
+                .long("max-entries")
+                .multiple(true)
+                .number_of_values(1)
+                .takes_value(true)
+                .value_name("num")
+                .validator(validate_max_entries)
+                .help("List at most num entries per directory, after sorting, followed by the number of the other ones"),
+        )
+        .arg(
+            Arg::with_name("across")
+                .short("x")
+                .long("across")
+                .multiple(true)
+                .help("Fill the grid row by row instead of column by column"),
+        )
         .arg(
             Arg::with_name("oneline")
                 .short("1")
@@ -86,6 +277,12 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Display one entry per line"),
         )
+        .arg(
+            Arg::with_name("pack")
+                .long("pack")
+                .multiple(true)
+                .help("Pack as many entries per line as fit in the width, separated by commas, or one per line when the width is unknown"),
+        )
         .arg(
             Arg::with_name("recursive")
                 .short("R")
@@ -107,13 +304,84 @@ pub fn build() -> App<'static, 'static> {
                 .conflicts_with("recursive")
                 .help("Recurse into directories and present the result as a tree"),
         )
+        .arg(
+            Arg::with_name("no-hidden-recurse")
+                .long("no-hidden-recurse")
+                .multiple(true)
+                .help("List hidden directories without recursing into them"),
+        )
+        .arg(
+            Arg::with_name("magic")
+                .long("magic")
+                .multiple(true)
+                .help("Pick the icons of the files without an extension by reading their first bytes"),
+        )
+        .arg(
+            Arg::with_name("summary")
+                .long("summary")
+                .multiple(true)
+                .help("Print how many entries were hidden or ignored after the listing"),
+        )
+        .arg(
+            Arg::with_name("show-control-chars")
+                .long("show-control-chars")
+                .multiple(true)
+                .help("Print the control characters of the names as they are, instead of `?` on a tty"),
+        )
+        .arg(
+            Arg::with_name("one-file-system")
+                .long("one-file-system")
+                .multiple(true)
+                .help("Do not recurse into the directories mounted from another file system"),
+        )
+        .arg(
+            Arg::with_name("ipc-status")
+                .long("ipc-status")
+                .multiple(true)
+                .help("Append whether a process is listening to sockets, or [?] to FIFOs"),
+        )
+        .arg(
+            Arg::with_name("since")
+                .long("since")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("time")
+                .help("Only list the entries modified at or after a time, either a duration like '2 hours ago' or a date like '2023-01-01'"),
+        )
+        .arg(
+            Arg::with_name("until")
+                .long("until")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("time")
+                .help("Only list the entries modified at or before a time, either a duration like '2 hours ago' or a date like '2023-01-01'"),
+        )
+        .arg(
+            Arg::with_name("stream")
+                .long("stream")
+                .multiple(true)
+                .help("Print the entries one per line as soon as they are read, without sorting nor aligning them, for huge directories"),
+        )
         .arg(
             Arg::with_name("depth")
                 .long("depth")
+                .visible_alias("max-depth")
                 .multiple(true)
+                .number_of_values(1)
                 .takes_value(true)
                 .value_name("num")
-                .help("Stop recursing into directories after reaching specified depth"),
+                .help("Stop recursing into directories after reaching specified depth (1 lists the immediate children only, 0 the given entries themselves like -d)"),
+        )
+        .arg(
+            Arg::with_name("recurse-order")
+                .long("recurse-order")
+                .possible_value("pre")
+                .possible_value("post")
+                .multiple(true)
+                .number_of_values(1)
+                .help("List a directory before its subdirectories, or after them, when recursing [default: pre]"),
         )
         .arg(
             Arg::with_name("directory-only")
@@ -149,7 +417,9 @@ pub fn build() -> App<'static, 'static> {
                 .default_value("date")
                 .multiple(true)
                 .number_of_values(1)
-                .help("How to display date [possible values: date, relative, +date-time-format]"),
+                .help(
+                    "How to display date [possible values: date, relative, relative-smart, +date-time-format]",
+                ),
         )
         .arg(
             Arg::with_name("timesort")
@@ -199,14 +469,62 @@ pub fn build() -> App<'static, 'static> {
             Arg::with_name("sort")
                 .long("sort")
                 .multiple(true)
-                .possible_values(&["size", "time", "version", "extension"])
+                .number_of_values(1)
                 .takes_value(true)
+                .allow_hyphen_values(true)
+                .validator(validate_sort_argument)
                 .value_name("WORD")
                 .overrides_with("timesort")
                 .overrides_with("sizesort")
                 .overrides_with("extensionsort")
                 .overrides_with("versionsort")
-                .help("sort by WORD instead of name")
+                .help("sort by WORD instead of name, or by a comma separated list of WORDs each prefixed by - (descending) or + (ascending) [possible values: name, size, time, version, extension, owner, group, inode, random, recent, none]")
+        )
+        .arg(
+            Arg::with_name("compound-extensions")
+                .long("compound-extensions")
+                .multiple(true)
+                .help("Treat the known compound extensions, like tar.gz, as a single extension when sorting and for the icons"),
+        )
+        .arg(
+            Arg::with_name("normalize")
+                .long("normalize")
+                .multiple(true)
+                .help("Compose the names in the NFC form of Unicode before sorting and printing them"),
+        )
+        .arg(
+            Arg::with_name("case-sensitive-extensions")
+                .long("case-sensitive-extensions")
+                .multiple(true)
+                .help("Sort and group apart the extensions which only differ in case, like .JPG and .jpg, when sorting by extension"),
+        )
+        .arg(
+            Arg::with_name("natural-locale")
+                .long("natural-locale")
+                .multiple(true)
+                .help("Sort names with the Unicode collation algorithm, so accented letters sort near their base letters"),
+        )
+        .arg(
+            Arg::with_name("seed")
+                .long("seed")
+                .multiple(true)
+                .number_of_values(1)
+                .takes_value(true)
+                .value_name("num")
+                .validator(validate_seed)
+                .help("Seed of the shuffle of --sort random, to list the entries in the same order on every run"),
+        )
+        .arg(
+            Arg::with_name("thousands-separator")
+                .long("thousands-separator")
+                .multiple(true)
+                .help("Group the digits of the sizes in bytes and of the counts by thousands, with the separator of the locale"),
+        )
+        .arg(
+            Arg::with_name("group-separators")
+                .long("group-separators")
+                .multiple(true)
+                .help("Print a blank line between groups of entries (same extension when sorting by extension, directories and files when grouping directories)"),
         )
         .arg(
             Arg::with_name("reverse")
@@ -226,6 +544,40 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("Sort the directories then the files"),
         )
+        .arg(
+            Arg::with_name("sort-dirs")
+                .long("sort-dirs")
+                .possible_values(&[
+                    "name", "size", "time", "version", "extension", "owner", "group", "inode",
+                    "random", "recent",
+                ])
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("WORD")
+                .help("Sort the directories by WORD when they are grouped, the files being sorted by --sort"),
+        )
+        .arg(
+            Arg::with_name("group-directories-first")
+                .long("group-directories-first")
+                .multiple(true)
+                .help("Sort the directories before the files, like --group-dirs first"),
+        )
+        .arg(
+            Arg::with_name("group-directories-last")
+                .long("group-directories-last")
+                .multiple(true)
+                .help("Sort the directories after the files, like --group-dirs last"),
+        )
+        .arg(
+            Arg::with_name("dotfiles")
+                .long("dotfiles")
+                .possible_value("mixed")
+                .possible_value("first")
+                .possible_value("last")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Sort the files whose name starts with a dot first, last or mixed with the other files [default: mixed]"),
+        )
         .arg(
             Arg::with_name("blocks")
                 .long("blocks")
@@ -237,10 +589,18 @@ pub fn build() -> App<'static, 'static> {
                     "user",
                     "group",
                     "size",
+                    "size_value",
+                    "size_detail",
                     "date",
+                    "accessed",
                     "name",
                     "inode",
                     "links",
+                    "children",
+                    "link_target_info",
+                    "caps",
+                    "fstype",
+                    "flags",
                 ])
                 .help("Specify the blocks that will be displayed and in what order"),
         )
@@ -249,12 +609,56 @@ pub fn build() -> App<'static, 'static> {
             .long("classic")
             .help("Enable classic mode (display output similar to ls)"),
         )
+        .arg(
+            Arg::with_name("stat")
+                .long("stat")
+                .multiple(true)
+                .help("Print the details of the given files, one attribute per line, instead of listing them"),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .long("verbose")
+                .multiple(true)
+                .help("Print diagnostic notes on stderr, like the options overridden by the classic mode and the resolved settings"),
+        )
         .arg(
             Arg::with_name("no-symlink")
                 .long("no-symlink")
                 .multiple(true)
                 .help("Do not display symlink target"),
         )
+        .arg(
+            Arg::with_name("symlink-display")
+                .long("symlink-display")
+                .possible_value("full")
+                .possible_value("target")
+                .possible_value("name")
+                .default_value("full")
+                .multiple(true)
+                .number_of_values(1)
+                .help("How to display symbolic links: link and target, target only or link name only"),
+        )
+        .arg(
+            Arg::with_name("symlink-size")
+                .long("symlink-size")
+                .possible_value("link")
+                .possible_value("target")
+                .possible_value("none")
+                .default_value("link")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Which size to display for symbolic links: of the link, of its target or none"),
+        )
+        .arg(
+            Arg::with_name("symlink-target")
+                .long("symlink-target")
+                .possible_value("relative")
+                .possible_value("absolute")
+                .default_value("relative")
+                .multiple(true)
+                .number_of_values(1)
+                .help("How to display the target of symbolic links: as stored in the link or as its absolute path"),
+        )
         .arg(
             Arg::with_name("ignore-glob")
                 .short("I")
@@ -263,7 +667,13 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .value_name("pattern")
                 .default_value("")
-                .help("Do not display files/directories with names matching the glob pattern(s). More than one can be specified by repeating the argument"),
+                .help("Do not display files/directories with names matching the glob pattern(s). More than one can be specified by repeating the argument, and they are added to the ones of the configuration file"),
+        )
+        .arg(
+            Arg::with_name("no-ignore")
+                .long("no-ignore")
+                .multiple(true)
+                .help("Do not ignore any file, neither with the glob pattern(s) of the arguments nor of the configuration file"),
         )
         .arg(
             Arg::with_name("inode")
@@ -279,15 +689,138 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("When showing file information for a symbolic link, show information for the file the link references rather than for the link itself"),
         )
+        .arg(
+            Arg::with_name("list-blocks")
+                .long("list-blocks")
+                .help("List the available blocks and exit"),
+        )
+        .arg(
+            Arg::with_name("list-sorts")
+                .long("list-sorts")
+                .help("List the available sort columns and exit"),
+        )
 }
 
 fn validate_date_argument(arg: String) -> Result<(), String> {
     if arg.starts_with('+') {
         validate_time_format(&arg)
-    } else if &arg == "date" || &arg == "relative" {
+    } else if &arg == "date" || &arg == "relative" || &arg == "relative-smart" {
         Result::Ok(())
     } else {
-        Result::Err("possible values: date, relative, +date-time-format".to_owned())
+        Result::Err("possible values: date, relative, relative-smart, +date-time-format".to_owned())
+    }
+}
+
+fn validate_sort_argument(arg: String) -> Result<(), String> {
+    for key in arg.split(',') {
+        let name = key.trim_start_matches(&['-', '+'][..]);
+        match name {
+            "name" | "size" | "time" | "version" | "extension" | "owner" | "group" | "inode"
+            | "random" | "recent" | "none" => (),
+            _ => {
+                return Err(format!(
+                    "invalid sort key '{}', possible values: name, size, time, version, extension, owner, group, inode, random, recent, none",
+                    key
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_icon_override(arg: String) -> Result<(), String> {
+    parse_icon_override(&arg).map(|_| ())
+}
+
+/// Parse an icon override, written `name=icon` or `.extension=icon`, into the lower-case name or
+/// extension with its leading dot, and the icon. The icon may be given as `\uXXXX` or `\u{XXXX}`
+/// escapes, as shells do not unescape them in quotes.
+pub fn parse_icon_override(arg: &str) -> Result<(String, String), String> {
+    let (key, value) = match arg.find('=') {
+        Some(index) if index > 0 && index + 1 < arg.len() => (&arg[..index], &arg[index + 1..]),
+        _ => {
+            return Err(format!(
+                "invalid icon override '{}', expected name=icon or .extension=icon",
+                arg
+            ))
+        }
+    };
+
+    let mut icon = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("\\u") {
+        icon.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+        let (digits, len) = if rest.as_bytes().first() == Some(&b'{') {
+            let braced = &rest[1..];
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => (braced, rest.len()),
+            }
+        } else {
+            let end = rest
+                .char_indices()
+                .nth(4)
+                .map_or(rest.len(), |(index, _)| index);
+            (&rest[..end], end)
+        };
+        match u32::from_str_radix(digits, 16)
+            .ok()
+            .and_then(std::char::from_u32)
+        {
+            Some(c) => icon.push(c),
+            None => return Err(format!("invalid codepoint '\\u{}' in '{}'", digits, arg)),
+        }
+        rest = &rest[len..];
+    }
+    icon.push_str(rest);
+
+    Ok((key.to_lowercase(), icon))
+}
+
+fn validate_color_theme(arg: String) -> Result<(), String> {
+    match arg.as_str() {
+        "default" | "nord" | "gruvbox" | "solarized-dark" => Ok(()),
+        path if std::path::Path::new(path).is_file() => Ok(()),
+        _ => Err(format!(
+            "'{}' is neither a built-in theme, one of default, nord, gruvbox and solarized-dark, nor a theme file",
+            arg
+        )),
+    }
+}
+
+fn validate_truncate(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(width) if width > 0 => Ok(()),
+        _ => Err("the truncate width must be a positive number".to_owned()),
+    }
+}
+
+fn validate_min_columns(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(columns) if columns > 0 => Ok(()),
+        _ => Err("the minimum number of columns must be a positive number".to_owned()),
+    }
+}
+
+fn validate_max_entries(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(entries) if entries > 0 => Ok(()),
+        _ => Err("the maximum number of entries must be a positive number".to_owned()),
+    }
+}
+
+fn validate_seed(arg: String) -> Result<(), String> {
+    match arg.parse::<u64>() {
+        Ok(_) => Ok(()),
+        _ => Err("the seed must be a non-negative integer".to_owned()),
+    }
+}
+
+fn validate_grid_gap(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err("the grid gap must be a non-negative integer".to_owned()),
     }
 }
 
@@ -1,8 +1,72 @@
+use crate::flags::block_colors::parse_colour;
+use crate::flags::ThemeOption;
 use ansi_term::{ANSIString, Colour, Style};
 use lscolors::{Indicator, LsColors};
 use std::collections::HashMap;
+use std::fs;
 use std::path::Path;
 
+/// The keys of the colors of a theme file, in the order of [ThemeColors].
+pub const THEME_KEYS: [&str; 16] = [
+    "user",
+    "group",
+    "read",
+    "write",
+    "exec",
+    "muted",
+    "file",
+    "executable",
+    "dir",
+    "link",
+    "broken",
+    "recent",
+    "old",
+    "small",
+    "medium",
+    "large",
+];
+
+/// The colors of a theme, in the order of [THEME_KEYS], each replacing the ones of the default
+/// theme when it is set.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct ThemeColors([Option<Colour>; 16]);
+
+impl ThemeColors {
+    /// Read a theme file, a YAML mapping of the keys of [THEME_KEYS] to colors, which are either
+    /// the name of one of the 8 basic colors, a number from 0 to 255 or an hexadecimal RGB value.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Can not open theme file {}: {}", path.display(), err))?;
+        let values: HashMap<String, String> = serde_yaml::from_str(&content)
+            .map_err(|err| format!("Theme file {} format error, {}", path.display(), err))?;
+
+        let mut colors = Self::default();
+        for (key, value) in &values {
+            let index = THEME_KEYS
+                .iter()
+                .position(|known| known == key)
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown key '{}' in the theme file {}, expected one of {}",
+                        key,
+                        path.display(),
+                        THEME_KEYS.join(", ")
+                    )
+                })?;
+            let colour = parse_colour(value).map_err(|err| {
+                format!(
+                    "{}, for the key '{}' in the theme file {}",
+                    err,
+                    key,
+                    path.display()
+                )
+            })?;
+            colors.0[index] = Some(colour);
+        }
+        Ok(colors)
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Hash, Debug, Eq, PartialEq, Clone)]
 pub enum Elem {
@@ -21,6 +85,12 @@ pub enum Elem {
     CharDevice,
     Socket,
     Special,
+    /// A file carrying capabilities, which are only detected on Linux.
+    Capability,
+    /// A directory writable by others, with or without the sticky bit.
+    WorldWritableDir {
+        sticky: bool,
+    },
 
     /// Permissions
     Read,
@@ -40,6 +110,8 @@ pub enum Elem {
 
     /// File Size
     NonFile,
+    /// The size of an empty file, told apart from the small sizes.
+    FileEmpty,
     FileLarge,
     FileMedium,
     FileSmall,
@@ -60,6 +132,10 @@ impl Elem {
     pub fn has_suid(&self) -> bool {
         matches!(self, Elem::Dir { uid: true } | Elem::File { uid: true, .. })
     }
+
+    pub fn is_world_writable(&self) -> bool {
+        matches!(self, Elem::WorldWritableDir { .. })
+    }
 }
 
 pub type ColoredString<'a> = ANSIString<'a>;
@@ -70,6 +146,8 @@ pub enum Theme {
     NoColor,
     Default,
     NoLscolors,
+    /// A built-in theme, replacing the colors of LS_COLORS.
+    Preset(ThemeOption),
 }
 
 pub struct Colors {
@@ -83,16 +161,40 @@ impl Colors {
             Theme::NoColor => None,
             Theme::Default => Some(Self::get_light_theme_colour_map()),
             Theme::NoLscolors => Some(Self::get_light_theme_colour_map()),
+            Theme::Preset(preset) => Some(Self::get_preset_colour_map(preset)),
         };
         let lscolors = match theme {
             Theme::NoColor => None,
             Theme::Default => Some(LsColors::from_env().unwrap_or_default()),
             Theme::NoLscolors => None,
+            Theme::Preset(_) => None,
         };
 
         Self { colors, lscolors }
     }
 
+    /// Replace the colors of a rendered string, which may be made of several colored parts, by a
+    /// single color, unless the colors are disabled.
+    pub fn recolor<'a>(&self, rendered: String, colour: Colour) -> ColoredString<'a> {
+        if self.colors.is_none() {
+            return ColoredString::from(rendered);
+        }
+
+        // Drop the escape sequences setting the colors, eg: `ESC [ 1 ; 34 m`
+        let mut uncolored = String::with_capacity(rendered.len());
+        let mut rest = rendered.as_str();
+        while let Some(start) = rest.find("\u{1b}[") {
+            uncolored.push_str(&rest[..start]);
+            rest = match rest[start..].find('m') {
+                Some(end) => &rest[start + end + 1..],
+                None => "",
+            };
+        }
+        uncolored.push_str(rest);
+
+        colour.paint(uncolored)
+    }
+
     pub fn colorize<'a>(&self, input: String, elem: &Elem) -> ColoredString<'a> {
         self.style(elem).paint(input)
     }
@@ -139,6 +241,8 @@ impl Colors {
             let style_fg = Style::default().fg(colors[elem]);
             if elem.has_suid() {
                 style_fg.on(Colour::Fixed(124)) // Red3
+            } else if elem.is_world_writable() {
+                style_fg.on(Colour::Fixed(28)) // Green4
             } else {
                 style_fg
             }
@@ -167,6 +271,11 @@ impl Colors {
             Elem::BlockDevice => Some("bd"),
             Elem::CharDevice => Some("cd"),
             Elem::BrokenSymLink => Some("or"),
+            Elem::Capability => Some("ca"),
+            Elem::WorldWritableDir { sticky } => match sticky {
+                true => Some("tw"),
+                false => Some("ow"),
+            },
             Elem::INode { valid } => match valid {
                 true => Some("so"),
                 false => Some("no"),
@@ -231,13 +340,16 @@ impl Colors {
         ); // Green3
         m.insert(Elem::Dir { uid: true }, Colour::Fixed(33)); // DodgerBlue1
         m.insert(Elem::Dir { uid: false }, Colour::Fixed(33)); // DodgerBlue1
-        m.insert(Elem::Pipe, Colour::Fixed(44)); // DarkTurquoise
+        m.insert(Elem::Pipe, Colour::Fixed(178)); // Gold3
         m.insert(Elem::SymLink, Colour::Fixed(44)); // DarkTurquoise
         m.insert(Elem::BrokenSymLink, Colour::Fixed(124)); // Red3
         m.insert(Elem::BlockDevice, Colour::Fixed(44)); // DarkTurquoise
         m.insert(Elem::CharDevice, Colour::Fixed(172)); // Orange3
-        m.insert(Elem::Socket, Colour::Fixed(44)); // DarkTurquoise
+        m.insert(Elem::Socket, Colour::Fixed(170)); // Orchid
         m.insert(Elem::Special, Colour::Fixed(44)); // DarkTurquoise
+        m.insert(Elem::Capability, Colour::Fixed(196)); // Red1
+        m.insert(Elem::WorldWritableDir { sticky: false }, Colour::Fixed(33)); // DodgerBlue1
+        m.insert(Elem::WorldWritableDir { sticky: true }, Colour::Fixed(16)); // Grey0
 
         // Last Time Modified
         m.insert(Elem::HourOld, Colour::Fixed(40)); // Green3
@@ -246,6 +358,7 @@ impl Colors {
 
         // Last Time Modified
         m.insert(Elem::NonFile, Colour::Fixed(245)); // Grey
+        m.insert(Elem::FileEmpty, Colour::Fixed(167)); // IndianRed
         m.insert(Elem::FileSmall, Colour::Fixed(229)); // Wheat1
         m.insert(Elem::FileMedium, Colour::Fixed(216)); // LightSalmon1
         m.insert(Elem::FileLarge, Colour::Fixed(172)); // Orange3
@@ -260,4 +373,172 @@ impl Colors {
         // m.insert(Elem::TreeEdge, Colour::Fixed(44)); // DarkTurquoise
         m
     }
+
+    fn get_preset_colour_map(preset: ThemeOption) -> HashMap<Elem, Colour> {
+        let mut m = Self::get_light_theme_colour_map();
+        let colors = match preset {
+            ThemeOption::Default => return m,
+            ThemeOption::File(colors) => colors,
+            ThemeOption::Nord => Palette {
+                user: Colour::RGB(180, 142, 173),       // nord15
+                group: Colour::RGB(143, 188, 187),      // nord7
+                read: Colour::RGB(163, 190, 140),       // nord14
+                write: Colour::RGB(235, 203, 139),      // nord13
+                exec: Colour::RGB(191, 97, 106),        // nord11
+                muted: Colour::RGB(76, 86, 106),        // nord3
+                file: Colour::RGB(216, 222, 233),       // nord4
+                executable: Colour::RGB(163, 190, 140), // nord14
+                dir: Colour::RGB(129, 161, 193),        // nord9
+                link: Colour::RGB(136, 192, 208),       // nord8
+                broken: Colour::RGB(191, 97, 106),      // nord11
+                recent: Colour::RGB(163, 190, 140),     // nord14
+                old: Colour::RGB(94, 129, 172),         // nord10
+                small: Colour::RGB(235, 203, 139),      // nord13
+                medium: Colour::RGB(208, 135, 112),     // nord12
+                large: Colour::RGB(191, 97, 106),       // nord11
+            }
+            .into(),
+            ThemeOption::Gruvbox => Palette {
+                user: Colour::RGB(254, 128, 25),       // orange
+                group: Colour::RGB(211, 134, 155),     // purple
+                read: Colour::RGB(184, 187, 38),       // green
+                write: Colour::RGB(250, 189, 47),      // yellow
+                exec: Colour::RGB(251, 73, 52),        // red
+                muted: Colour::RGB(146, 131, 116),     // gray
+                file: Colour::RGB(235, 219, 178),      // fg
+                executable: Colour::RGB(184, 187, 38), // green
+                dir: Colour::RGB(131, 165, 152),       // blue
+                link: Colour::RGB(142, 192, 124),      // aqua
+                broken: Colour::RGB(251, 73, 52),      // red
+                recent: Colour::RGB(184, 187, 38),     // green
+                old: Colour::RGB(168, 153, 132),       // fg4
+                small: Colour::RGB(250, 189, 47),      // yellow
+                medium: Colour::RGB(254, 128, 25),     // orange
+                large: Colour::RGB(251, 73, 52),       // red
+            }
+            .into(),
+            ThemeOption::SolarizedDark => Palette {
+                user: Colour::RGB(108, 113, 196),     // violet
+                group: Colour::RGB(211, 54, 130),     // magenta
+                read: Colour::RGB(133, 153, 0),       // green
+                write: Colour::RGB(181, 137, 0),      // yellow
+                exec: Colour::RGB(220, 50, 47),       // red
+                muted: Colour::RGB(88, 110, 117),     // base01
+                file: Colour::RGB(131, 148, 150),     // base0
+                executable: Colour::RGB(133, 153, 0), // green
+                dir: Colour::RGB(38, 139, 210),       // blue
+                link: Colour::RGB(42, 161, 152),      // cyan
+                broken: Colour::RGB(220, 50, 47),     // red
+                recent: Colour::RGB(133, 153, 0),     // green
+                old: Colour::RGB(88, 110, 117),       // base01
+                small: Colour::RGB(181, 137, 0),      // yellow
+                medium: Colour::RGB(203, 75, 22),     // orange
+                large: Colour::RGB(220, 50, 47),      // red
+            }
+            .into(),
+        };
+
+        for (key, colour) in THEME_KEYS.iter().zip(colors.0.iter()) {
+            if let Some(colour) = colour {
+                for elem in Self::theme_elems(key) {
+                    m.insert(elem, *colour);
+                }
+            }
+        }
+        m
+    }
+
+    /// The elements colored by a key of [THEME_KEYS].
+    fn theme_elems(key: &str) -> Vec<Elem> {
+        match key {
+            "user" => vec![Elem::User],
+            "group" => vec![Elem::Group],
+            "read" => vec![Elem::Read],
+            "write" => vec![Elem::Write],
+            "exec" => vec![Elem::Exec],
+            "muted" => vec![
+                Elem::NoAccess,
+                Elem::NonFile,
+                Elem::INode { valid: false },
+                Elem::Links { valid: false },
+            ],
+            "file" => vec![
+                Elem::File {
+                    exec: false,
+                    uid: false,
+                },
+                Elem::File {
+                    exec: false,
+                    uid: true,
+                },
+            ],
+            "executable" => vec![
+                Elem::File {
+                    exec: true,
+                    uid: false,
+                },
+                Elem::File {
+                    exec: true,
+                    uid: true,
+                },
+            ],
+            "dir" => vec![
+                Elem::Dir { uid: false },
+                Elem::Dir { uid: true },
+                Elem::WorldWritableDir { sticky: false },
+                Elem::WorldWritableDir { sticky: true },
+            ],
+            "link" => vec![Elem::SymLink, Elem::DayOld],
+            "broken" => vec![Elem::BrokenSymLink, Elem::FileEmpty],
+            "recent" => vec![Elem::HourOld],
+            "old" => vec![Elem::Older],
+            "small" => vec![Elem::FileSmall],
+            "medium" => vec![Elem::FileMedium],
+            "large" => vec![Elem::FileLarge],
+            _ => vec![],
+        }
+    }
+}
+
+/// The colors of a built-in theme, overriding the ones of the default theme.
+struct Palette {
+    user: Colour,
+    group: Colour,
+    read: Colour,
+    write: Colour,
+    exec: Colour,
+    muted: Colour,
+    file: Colour,
+    executable: Colour,
+    dir: Colour,
+    link: Colour,
+    broken: Colour,
+    recent: Colour,
+    old: Colour,
+    small: Colour,
+    medium: Colour,
+    large: Colour,
+}
+
+impl From<Palette> for ThemeColors {
+    fn from(palette: Palette) -> Self {
+        Self([
+            Some(palette.user),
+            Some(palette.group),
+            Some(palette.read),
+            Some(palette.write),
+            Some(palette.exec),
+            Some(palette.muted),
+            Some(palette.file),
+            Some(palette.executable),
+            Some(palette.dir),
+            Some(palette.link),
+            Some(palette.broken),
+            Some(palette.recent),
+            Some(palette.old),
+            Some(palette.small),
+            Some(palette.medium),
+            Some(palette.large),
+        ])
+    }
 }
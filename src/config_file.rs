@@ -1,13 +1,25 @@
 ///! This module provides methods to handle the program's config files and operations related to
 ///! this.
 use crate::flags::color::ColorOption;
+use crate::flags::date::RelativeUnit;
 use crate::flags::display::Display;
-use crate::flags::icons::{IconOption, IconTheme};
+use crate::flags::hyperlink::HyperlinkOption;
+use crate::flags::icons::{IconFileType, IconLookupStep, IconOption, IconTheme, NerdFontVersion};
+use crate::flags::indicators::Indicators;
 use crate::flags::layout::Layout;
+use crate::flags::output::Output;
+use crate::flags::paging::Paging;
+use crate::flags::quoting_style::QuotingStyle;
+use crate::flags::recursion::RecursionOrder;
 use crate::flags::size::SizeFlag;
-use crate::flags::sorting::{DirGrouping, SortColumn};
+use crate::flags::sorting::{DirGrouping, DotFiles, SortColumn};
+use crate::flags::symlink_display::SymlinkDisplay;
+use crate::flags::symlink_size::SymlinkSize;
+use crate::flags::symlink_target::SymlinkTarget;
+use crate::flags::tty::TtyOption;
 use crate::print_error;
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use serde::Deserialize;
@@ -27,24 +39,91 @@ pub struct Config {
     pub classic: Option<bool>,
     pub blocks: Option<Vec<String>>,
     pub color: Option<Color>,
-    pub date: Option<String>,
+    pub date: Option<Date>,
     pub dereference: Option<bool>,
     pub display: Option<Display>,
     pub icons: Option<Icons>,
     pub ignore_globs: Option<Vec<String>>,
-    pub indicators: Option<bool>,
+    pub indicators: Option<Indicators>,
     pub layout: Option<Layout>,
+    pub layout_non_tty: Option<Layout>,
     pub recursion: Option<Recursion>,
     pub size: Option<SizeFlag>,
     pub sorting: Option<Sorting>,
+    pub sort_priority: Option<Vec<String>>,
     pub no_symlink: Option<bool>,
     pub total_size: Option<bool>,
     pub symlink_arrow: Option<String>,
+    pub symlink_display: Option<SymlinkDisplay>,
+    pub no_hidden_recurse: Option<bool>,
+    pub group_separators: Option<bool>,
+    pub grid_gap: Option<usize>,
+    pub output: Option<Output>,
+    pub ipc_status: Option<bool>,
+    pub across: Option<bool>,
+    pub paging: Option<Paging>,
+    pub profiles: Option<HashMap<String, Config>>,
+    pub hyperlink: Option<HyperlinkOption>,
+    pub tty: Option<TtyOption>,
+    pub block_colors: Option<HashMap<String, String>>,
+    pub truncate: Option<usize>,
+    pub date_groups: Option<bool>,
+    pub respect_gitattributes: Option<bool>,
+    pub set_title: Option<bool>,
+    pub min_columns: Option<usize>,
+    pub max_entries: Option<usize>,
+    pub name_colors: Option<serde_yaml::Mapping>,
+    pub compound_extensions: Option<bool>,
+    pub quote_name: Option<bool>,
+    pub one_file_system: Option<bool>,
+    pub show_control_chars: Option<bool>,
+    pub block_align: Option<HashMap<String, String>>,
+    pub summary: Option<bool>,
+    pub magic: Option<bool>,
+    pub symlink_size: Option<SymlinkSize>,
+    pub symlink_target: Option<SymlinkTarget>,
+    pub quoting_style: Option<QuotingStyle>,
+    pub external_formatter: Option<String>,
+    pub thousands_separator: Option<bool>,
+    pub normalize: Option<bool>,
 }
 
 #[derive(Eq, PartialEq, Debug, Deserialize)]
 pub struct Color {
     pub when: ColorOption,
+    pub theme: Option<String>,
+}
+
+/// The date format, either shared by all the date blocks or given per block.
+#[derive(Eq, PartialEq, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Date {
+    Format(String),
+    PerBlock(DateFormats),
+}
+
+impl From<&str> for Date {
+    fn from(format: &str) -> Self {
+        Self::Format(format.into())
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DateFormats {
+    pub modified: Option<String>,
+    pub accessed: Option<String>,
+    pub relative: Option<RelativeDate>,
+}
+
+/// How to count the relative dates, the default being a single unit rounded to the most
+/// meaningful one.
+#[derive(Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[serde(deny_unknown_fields)]
+pub struct RelativeDate {
+    pub max_unit: Option<RelativeUnit>,
+    pub compound: Option<bool>,
 }
 
 #[derive(Eq, PartialEq, Debug, Deserialize)]
@@ -52,12 +131,18 @@ pub struct Icons {
     pub when: Option<IconOption>,
     pub theme: Option<IconTheme>,
     pub separator: Option<String>,
+    #[serde(rename = "nerd-font-version")]
+    pub nerd_font_version: Option<NerdFontVersion>,
+    pub lookup: Option<Vec<IconLookupStep>>,
+    #[serde(rename = "hide-for")]
+    pub hide_for: Option<Vec<IconFileType>>,
 }
 
 #[derive(Eq, PartialEq, Debug, Deserialize)]
 pub struct Recursion {
     pub enabled: Option<bool>,
     pub depth: Option<usize>,
+    pub order: Option<RecursionOrder>,
 }
 
 #[derive(Eq, PartialEq, Debug, Deserialize)]
@@ -66,6 +151,10 @@ pub struct Sorting {
     pub column: Option<SortColumn>,
     pub reverse: Option<bool>,
     pub dir_grouping: Option<DirGrouping>,
+    pub dotfiles: Option<DotFiles>,
+    pub natural_locale: Option<bool>,
+    pub dir_column: Option<SortColumn>,
+    pub case_sensitive_extensions: Option<bool>,
 }
 
 impl Config {
@@ -82,15 +171,113 @@ impl Config {
             ignore_globs: None,
             indicators: None,
             layout: None,
+            layout_non_tty: None,
             recursion: None,
             size: None,
             sorting: None,
             no_symlink: None,
             total_size: None,
             symlink_arrow: None,
+            symlink_display: None,
+            no_hidden_recurse: None,
+            group_separators: None,
+            grid_gap: None,
+            output: None,
+            ipc_status: None,
+            across: None,
+            paging: None,
+            profiles: None,
+            hyperlink: None,
+            tty: None,
+            block_colors: None,
+            truncate: None,
+            date_groups: None,
+            respect_gitattributes: None,
+            set_title: None,
+            min_columns: None,
+            max_entries: None,
+            name_colors: None,
+            compound_extensions: None,
+            quote_name: None,
+            one_file_system: None,
+            show_control_chars: None,
+            block_align: None,
+            summary: None,
+            magic: None,
+            symlink_size: None,
+            symlink_target: None,
+            quoting_style: None,
+            external_formatter: None,
+            thousands_separator: None,
+            normalize: None,
+            sort_priority: None,
         }
     }
 
+    /// Apply the options of the profile named `name` over the ones of this config.
+    ///
+    /// If the profile is not defined, this returns an error.
+    pub fn with_profile(mut self, name: &str) -> Result<Self, String> {
+        let profile = self
+            .profiles
+            .take()
+            .and_then(|mut profiles| profiles.remove(name))
+            .ok_or_else(|| format!("Profile {} is not defined in the configuration file.", name))?;
+
+        Ok(Self {
+            classic: profile.classic.or(self.classic),
+            blocks: profile.blocks.or(self.blocks),
+            color: profile.color.or(self.color),
+            date: profile.date.or(self.date),
+            dereference: profile.dereference.or(self.dereference),
+            display: profile.display.or(self.display),
+            icons: profile.icons.or(self.icons),
+            ignore_globs: profile.ignore_globs.or(self.ignore_globs),
+            indicators: profile.indicators.or(self.indicators),
+            layout: profile.layout.or(self.layout),
+            layout_non_tty: profile.layout_non_tty.or(self.layout_non_tty),
+            recursion: profile.recursion.or(self.recursion),
+            size: profile.size.or(self.size),
+            sorting: profile.sorting.or(self.sorting),
+            no_symlink: profile.no_symlink.or(self.no_symlink),
+            total_size: profile.total_size.or(self.total_size),
+            symlink_arrow: profile.symlink_arrow.or(self.symlink_arrow),
+            symlink_display: profile.symlink_display.or(self.symlink_display),
+            no_hidden_recurse: profile.no_hidden_recurse.or(self.no_hidden_recurse),
+            group_separators: profile.group_separators.or(self.group_separators),
+            grid_gap: profile.grid_gap.or(self.grid_gap),
+            output: profile.output.or(self.output),
+            ipc_status: profile.ipc_status.or(self.ipc_status),
+            across: profile.across.or(self.across),
+            paging: profile.paging.or(self.paging),
+            hyperlink: profile.hyperlink.or(self.hyperlink),
+            tty: profile.tty.or(self.tty),
+            block_colors: profile.block_colors.or(self.block_colors),
+            truncate: profile.truncate.or(self.truncate),
+            min_columns: profile.min_columns.or(self.min_columns),
+            max_entries: profile.max_entries.or(self.max_entries),
+            name_colors: profile.name_colors.or(self.name_colors),
+            compound_extensions: profile.compound_extensions.or(self.compound_extensions),
+            quote_name: profile.quote_name.or(self.quote_name),
+            one_file_system: profile.one_file_system.or(self.one_file_system),
+            show_control_chars: profile.show_control_chars.or(self.show_control_chars),
+            block_align: profile.block_align.or(self.block_align),
+            summary: profile.summary.or(self.summary),
+            magic: profile.magic.or(self.magic),
+            symlink_size: profile.symlink_size.or(self.symlink_size),
+            symlink_target: profile.symlink_target.or(self.symlink_target),
+            quoting_style: profile.quoting_style.or(self.quoting_style),
+            external_formatter: profile.external_formatter.or(self.external_formatter),
+            thousands_separator: profile.thousands_separator.or(self.thousands_separator),
+            normalize: profile.normalize.or(self.normalize),
+            sort_priority: profile.sort_priority.or(self.sort_priority),
+            date_groups: profile.date_groups.or(self.date_groups),
+            respect_gitattributes: profile.respect_gitattributes.or(self.respect_gitattributes),
+            set_title: profile.set_title.or(self.set_title),
+            profiles: None,
+        })
+    }
+
     /// This constructs a Config struct with a passed file path [String].
     pub fn from_file(file: String) -> Option<Self> {
         match fs::read(&file) {
@@ -170,7 +357,7 @@ classic: false
 # == Blocks ==
 # This specifies the columns and their order when using the long and the tree
 # layout.
-# Possible values: permission, user, group, size, size_value, date, name, inode
+# Possible values: permission, user, group, size, size_value, size_detail, date, accessed, name, inode, children, link_target_info, caps, fstype, flags
 blocks:
   - permission
   - user
@@ -185,14 +372,38 @@ color:
   # When to colorize the output.
   # When "classic" is set, this is set to "never".
   # Possible values: never, auto, always
+  # The synonyms yes and force (always), tty and if-tty (auto) and no (never)
+  # are accepted as well.
   when: auto
+  # Which color theme to use, a built-in one or the path of a theme file. Any
+  # other theme than "default" replaces the colors of LS_COLORS.
+  # A theme file maps some of the keys user, group, read, write, exec, muted,
+  # file, executable, dir, link, broken, recent, old, small, medium and large
+  # to colors, eg: `dir: '#81a1c1'`, the other ones keeping the default colors.
+  # Possible values: default, nord, gruvbox, solarized-dark, or a path
+  theme: default
 
 # == Date ==
-# This specifies the date format for the date column. The freeform format
+# This specifies the date format for the date columns. The freeform format
 # accepts an strftime like string.
 # When "classic" is set, this is set to "date".
-# Possible values: date, relative, +<date_format>
+# "relative-smart" is like "relative", but shows "today" and "yesterday" for the
+# dates of these calendar days.
+# Possible values: date, relative, relative-smart, +<date_format>
 # date: date
+# A different format can be given to each date column instead, the ones left
+# unspecified using the default format:
+# date:
+#   modified: +%F
+#   accessed: +%F %T
+# The relative dates can be counted in units up to "max-unit", eg: "400 days ago"
+# with "day", and in two units with "compound", eg: "1h 5m ago".
+# Possible values of max-unit: second, minute, hour, day, week, month, year
+# date:
+#   modified: relative
+#   relative:
+#     max-unit: day
+#     compound: false
 
 # == Dereference ==
 # Whether to dereference symbolic links.
@@ -216,31 +427,56 @@ icons:
   # The string between the icons and the name.
   # Possible values: any string (eg: " |")
   separator: " "
+  # Which version of the Nerd Fonts the fancy icons are taken from, as the version 3
+  # moved many icons. Set it to 2 if some icons render as boxes with an older font.
+  # Possible values: 2, 3
+  nerd-font-version: 3
+  # The order the icons of the files are looked up in, by their name, their
+  # extension and the category of their extension (eg: any image extension),
+  # before the default icon. The steps left out are skipped.
+  # Possible values: name, extension, category
+  lookup: [name, extension, category]
+  # The file types printed without an icon, even when the icons are shown.
+  # Possible values: file, directory, symlink, pipe, socket, block-device,
+  # char-device, special
+  hide-for: []
 
 # == Ignore Globs ==
-# A list of globs to ignore when listing.
+# A list of globs to ignore when listing. The globs passed with `--ignore-glob`
+# are added to these ones, and `--no-ignore` disables all of them.
 # ignore-globs:
 #   - .git
 
 # == Indicators ==
-# Whether to add indicator characters to certain listed files.
-# Possible values: false, true
+# Whether to add indicator characters to certain listed files. "auto" only adds
+# them when the output is a tty.
+# Possible values: false, true, auto
 indicators: false
 
 # == Layout ==
 # Which layout to use. "oneline" might be a bit confusing here and should be
 # called "one-per-line". It might be changed in the future.
-# Possible values: grid, tree, oneline
+# Possible values: grid, tree, oneline, pack
 layout: grid
+# The layout to use when stdout is not a tty, for instance when the output is
+# piped, in place of the one above. A layout passed as argument is used anyway.
+# Possible values: grid, tree, oneline, pack
+# layout-non-tty: oneline
 
 # == Recursion ==
 recursion:
   # Whether to enable recursion.
   # Possible values: false, true
   enabled: false
-  # How deep the recursion should go. This has to be a positive integer. Leave
-  # it unspecified for (virtually) infinite.
+  # How deep the recursion should go. This has to be a positive integer. The
+  # listed directory itself is not counted, so 1 lists its immediate children
+  # only, and 0 the listed entries themselves. Leave it unspecified for
+  # (virtually) infinite.
   # depth: 3
+  # Whether to list a directory before its subdirectories, or after them, eg:
+  # to remove them in order.
+  # Possible values: pre, post
+  order: pre
 
 # == Size ==
 # Specifies the format of the size column.
@@ -250,7 +486,8 @@ size: default
 # == Sorting ==
 sorting:
   # Specify what to sort by.
-  # Possible values: extension, name, time, size, version
+  # Possible values: extension, name, time, size, version, owner, group, inode, random, recent,
+  # none
   column: name
   # Whether to reverse the sorting.
   # Possible values: false, true
@@ -259,6 +496,31 @@ sorting:
   # When "classic" is set, this is set to "none".
   # Possible values: first, last, none
   dir-grouping: none
+  # The column to sort the directories by, when they are grouped, while the
+  # files are sorted by the column above. Leave it unspecified to sort the
+  # directories like the files.
+  # Possible values: extension, name, time, size, version, owner, group, inode, random, recent
+  # dir-column: name
+  # Where to place the files whose name starts with a dot, whatever their
+  # name, case or extension.
+  # Possible values: mixed, first, last
+  dotfiles: mixed
+  # Whether to sort names with the Unicode collation algorithm, so that accented
+  # letters sort near their base letters. This is slower than the default.
+  # Possible values: false, true
+  natural-locale: false
+  # Whether to sort and group apart the extensions which only differ in case,
+  # like .JPG and .jpg, when sorting by extension.
+  # Possible values: false, true
+  case-sensitive-extensions: false
+
+# == Sort priority ==
+# The names or glob patterns of the entries sorted first, in this order, before
+# the other entries sorted as usual, eg: to list the files of a project in its
+# own order.
+# sort-priority:
+#   - README*
+#   - Cargo.toml
 
 # == No Symlink ==
 # Whether to omit showing symlink targets
@@ -273,6 +535,209 @@ total-size: false
 # == Symlink arrow ==
 # Specifies how the symlink arrow display, chars in both ascii and utf8
 symlink-arrow: ⇒
+
+# == Symlink display ==
+# Specifies how symbolic links are shown in the name column.
+# "full" shows the link name and its target, "target" only shows the target,
+# "name" only shows the link name, along with the metadata of the target.
+# Possible values: full, target, name
+symlink-display: full
+
+# == Symlink size ==
+# Specifies which size is shown for symbolic links in the size column.
+# "link" shows the size of the link itself, "target" shows the size of its
+# target, or a dash if it is broken, and "none" shows a dash.
+# Possible values: link, target, none
+symlink-size: link
+
+# == Symlink target ==
+# Specifies how the target of symbolic links is shown. "relative" shows it as
+# it is stored in the link, and "absolute" shows its canonical absolute path,
+# or the stored target if the link is broken.
+# Possible values: relative, absolute
+symlink-target: relative
+
+# == No hidden recurse ==
+# Whether to list hidden directories without descending into them when
+# recursing (eg: to avoid walking through `.git`).
+# Possible values: false, true
+no-hidden-recurse: false
+
+# == Thousands separator ==
+# Whether to group the digits of the sizes in bytes and of the numbers of links
+# and children by thousands, with the separator of the locale (LC_ALL,
+# LC_NUMERIC or LANG), eg: `12,345`.
+# Possible values: false, true
+thousands-separator: false
+
+# == Normalize ==
+# Whether to compose the names in the NFC form of Unicode before sorting and
+# printing them, so that the names decomposed by the file system, like on
+# macOS, sort and look like the same names typed in.
+# Possible values: false, true
+normalize: false
+
+# == Group separators ==
+# Whether to print a blank line between the groups of entries, when using the
+# oneline or the long layout. A group holds the entries with the same extension
+# when sorting by extension, and the directories or the files when grouping
+# directories.
+# Possible values: false, true
+group-separators: false
+
+# == Grid gap ==
+# The number of spaces between the columns of the grid layout.
+grid-gap: 2
+
+# == Output ==
+# The format to write the listing in. "csv" writes one record per entry, with
+# a header row and one column per block, without colors nor icons. "json"
+# writes a versioned document with stable field names, see the man page.
+# Possible values: text, csv, json
+output: text
+
+# == IPC status ==
+# Whether to annotate sockets with whether a process is listening on them.
+# FIFOs are never opened, so their status is always shown as unknown.
+# Possible values: false, true
+ipc-status: false
+
+# == Across ==
+# Whether to fill the grid row by row, instead of column by column.
+# Possible values: false, true
+across: false
+
+# == Paging ==
+# When to write the output through $PAGER, or "less -R" if it is not set.
+# "auto" pages the output when it is taller than the terminal.
+# Possible values: auto, always, never
+paging: never
+
+# == External formatter ==
+# A command the entries are written to as the JSON document of `--json`, to
+# print its output instead of the listing, eg: `jq -r .entries[].path`.
+# external-formatter: cat
+
+# == Profiles ==
+# Named sets of options, selected with `--profile <name>`, which override the
+# ones above. The sections given in a profile replace the ones above as a whole.
+# profiles:
+#   work:
+#     layout: tree
+#     sorting:
+#       column: time
+
+# == Hyperlink ==
+# Print the file names as hyperlinks to the files, when the terminal supports them.
+# Possible values: always, auto, never
+hyperlink: never
+
+# == Tty ==
+# Whether to lay out and decorate the output as on a terminal when it is redirected,
+# eg: to keep the grid when piped.
+# Possible values: auto, force
+tty: auto
+
+# == Block colors ==
+# The colors of the blocks, which replace the colors of the theme, eg: to always
+# color the sizes in cyan.
+# Possible values: black, red, green, yellow, blue, purple, cyan, white, a number
+# from 0 to 255, or an hexadecimal value like '#00afaf'
+# block-colors:
+#   size: cyan
+
+# == Truncate ==
+# The width, in terminal cells, above which the names are truncated in the middle
+# with an ellipsis, keeping their extension visible.
+# truncate: 30
+
+# == Date groups ==
+# Whether to list the entries under the headers of their age group, "Today",
+# "This Week", "This Month" or "Older", when sorting by time in the oneline
+# or long layout.
+# Possible values: false, true
+date-groups: false
+
+# == Respect gitattributes ==
+# Whether to hide the files which `git archive` leaves out, as they have the
+# export-ignore attribute in the .gitattributes files of the repository.
+# Possible values: false, true
+respect-gitattributes: false
+
+# == Set title ==
+# Whether to set the title of the terminal to the listed paths, when the output
+# is a terminal.
+# Possible values: false, true
+set-title: false
+
+# == Min columns ==
+# The minimum number of columns of the grid layout, used when the entries fit in
+# the width of the terminal, eg: to spread them when piped with `tty: force`.
+# min-columns: 4
+
+# == Max entries ==
+# The maximum number of entries listed per directory, after sorting, the other
+# ones being counted in a `... and M more` footer.
+# max-entries: 100
+
+# == Name colors ==
+# The colors of the file names matching glob patterns, which replace the colors
+# of the theme and of LS_COLORS. The first matching pattern is used.
+# Possible values: the same as the block colors
+# name-colors:
+#   '*.bak': 244
+#   TODO: red
+
+# == Compound extensions ==
+# Whether to treat the known compound extensions, like tar.gz or tar.bz2, as a
+# single extension when sorting by extension and looking up the icons.
+# Possible values: false, true
+compound-extensions: false
+
+# == Quote name ==
+# Whether to wrap the names in double quotes, escaping the quotes, backslashes
+# and control characters as C strings do.
+# Possible values: false, true
+quote-name: false
+
+# == Quoting style ==
+# How to quote and escape the names, replacing `quote-name` and
+# `show-control-chars`. "escape", "c" and "locale" escape the names as C strings,
+# the invalid UTF-8 bytes as `\xNN`, and respectively add no quotes, double
+# quotes, or the quotation marks ‘ and ’.
+# Possible values: auto, literal, escape, c, locale
+# quoting-style: locale
+
+# == One file system ==
+# Whether to skip the directories mounted from another file system when
+# recursing (like `du -x`).
+# Possible values: false, true
+one-file-system: false
+
+# == Show control chars ==
+# Whether to print the control characters of the names as they are. Otherwise
+# they are printed as `?` to a tty, and escaped (eg: `\n`) to a pipe.
+# Possible values: false, true
+show-control-chars: false
+
+# == Block align ==
+# The alignments of the blocks in the long layout, which replace the default
+# ones, eg: to left-align the sizes.
+# Possible values: left, right, center
+# block-align:
+#   size: left
+
+# == Summary ==
+# Whether to print a footer line with the number of entries left out of the
+# listing, eg: `(3 hidden, 2 ignored)`.
+# Possible values: false, true
+summary: false
+
+# == Magic ==
+# Whether to pick the icons of the files without an extension by reading their
+# first bytes, eg: to show an image icon for a PNG file.
+# Possible values: false, true
+magic: false
 "#;
 
 #[cfg(test)]
@@ -287,10 +752,19 @@ mod tests {
     use super::Config;
     use crate::config_file;
     use crate::flags::color::ColorOption;
-    use crate::flags::icons::{IconOption, IconTheme};
+    use crate::flags::hyperlink::HyperlinkOption;
+    use crate::flags::icons::{IconLookupStep, IconOption, IconTheme, NerdFontVersion};
+    use crate::flags::indicators::Indicators;
     use crate::flags::layout::Layout;
+    use crate::flags::output::Output;
+    use crate::flags::paging::Paging;
+    use crate::flags::recursion::RecursionOrder;
     use crate::flags::size::SizeFlag;
-    use crate::flags::sorting::{DirGrouping, SortColumn};
+    use crate::flags::sorting::{DirGrouping, DotFiles, SortColumn};
+    use crate::flags::symlink_display::SymlinkDisplay;
+    use crate::flags::symlink_size::SymlinkSize;
+    use crate::flags::symlink_target::SymlinkTarget;
+    use crate::flags::tty::TtyOption;
 
     #[test]
     fn test_read_default() {
@@ -311,6 +785,7 @@ mod tests {
                 ),
                 color: Some(config_file::Color {
                     when: ColorOption::Auto,
+                    theme: Some("default".into()),
                 }),
                 date: None,
                 dereference: Some(false),
@@ -319,23 +794,69 @@ mod tests {
                     when: Some(IconOption::Auto),
                     theme: Some(IconTheme::Fancy),
                     separator: Some(" ".to_string()),
+                    nerd_font_version: Some(NerdFontVersion::V3),
+                    lookup: Some(vec![
+                        IconLookupStep::Name,
+                        IconLookupStep::Extension,
+                        IconLookupStep::Category,
+                    ]),
+                    hide_for: Some(vec![]),
                 }),
                 ignore_globs: None,
-                indicators: Some(false),
+                indicators: Some(Indicators::Never),
                 layout: Some(Layout::Grid),
+                layout_non_tty: None,
                 recursion: Some(config_file::Recursion {
                     enabled: Some(false),
                     depth: None,
+                    order: Some(RecursionOrder::Pre),
                 }),
                 size: Some(SizeFlag::Default),
                 sorting: Some(config_file::Sorting {
                     column: Some(SortColumn::Name),
                     reverse: Some(false),
                     dir_grouping: Some(DirGrouping::None),
+                    dotfiles: Some(DotFiles::Mixed),
+                    natural_locale: Some(false),
+                    dir_column: None,
+                    case_sensitive_extensions: Some(false),
                 }),
                 no_symlink: Some(false),
                 total_size: Some(false),
                 symlink_arrow: Some("⇒".into()),
+                symlink_display: Some(SymlinkDisplay::Full),
+                no_hidden_recurse: Some(false),
+                group_separators: Some(false),
+                grid_gap: Some(2),
+                output: Some(Output::Text),
+                ipc_status: Some(false),
+                across: Some(false),
+                paging: Some(Paging::Never),
+                profiles: None,
+                hyperlink: Some(HyperlinkOption::Never),
+                tty: Some(TtyOption::Auto),
+                block_colors: None,
+                truncate: None,
+                date_groups: Some(false),
+                respect_gitattributes: Some(false),
+                set_title: Some(false),
+                min_columns: None,
+                max_entries: None,
+                name_colors: None,
+                compound_extensions: Some(false),
+                quote_name: Some(false),
+                one_file_system: Some(false),
+                show_control_chars: Some(false),
+                block_align: None,
+                summary: Some(false),
+                magic: Some(false),
+                symlink_size: Some(SymlinkSize::Link),
+                symlink_target: Some(SymlinkTarget::Relative),
+                quoting_style: None,
+                external_formatter: None,
+                thousands_separator: Some(false),
+                normalize: Some(false),
+                sort_priority: None,
             },
             c
         );
@@ -347,6 +868,47 @@ mod tests {
         assert!(c.classic.unwrap())
     }
 
+    #[test]
+    fn test_read_config_profile() {
+        let yaml = "layout: grid\n\
+                    indicators: true\n\
+                    profiles:\n  \
+                      work:\n    \
+                        layout: tree\n    \
+                        grid-gap: 4\n  \
+                      home:\n    \
+                        layout: oneline\n";
+
+        let c = Config::from_yaml(yaml)
+            .unwrap()
+            .with_profile("work")
+            .unwrap();
+        assert_eq!(Some(Layout::Tree), c.layout);
+        assert_eq!(Some(4), c.grid_gap);
+        assert_eq!(Some(Indicators::Always), c.indicators);
+        assert_eq!(None, c.profiles);
+
+        let c = Config::from_yaml(yaml)
+            .unwrap()
+            .with_profile("home")
+            .unwrap();
+        assert_eq!(Some(Layout::OneLine), c.layout);
+        assert_eq!(None, c.grid_gap);
+    }
+
+    #[test]
+    fn test_read_config_unknown_profile() {
+        let c = Config::from_yaml("profiles:\n  work:\n    layout: tree").unwrap();
+        assert!(c.with_profile("home").is_err());
+    }
+
+    #[test]
+    fn test_read_config_indicators_auto() {
+        let c = Config::from_yaml("indicators: auto").unwrap();
+        assert_eq!(Some(Indicators::Auto), c.indicators);
+        assert!(Config::from_yaml("indicators: sometimes").is_err());
+    }
+
     #[test]
     fn test_read_config_bad_bool() {
         let c = Config::from_yaml("classic: notbool");
@@ -359,6 +921,19 @@ mod tests {
         assert!(c.is_none())
     }
 
+    #[test]
+    fn test_read_date_per_block() {
+        let c = Config::from_yaml("date:\n  modified: relative\n  accessed: +%T").unwrap();
+        assert_eq!(
+            Some(config_file::Date::PerBlock(config_file::DateFormats {
+                modified: Some("relative".into()),
+                accessed: Some("+%T".into()),
+                relative: None,
+            })),
+            c.date
+        );
+    }
+
     #[test]
     fn test_read_bad_display() {
         assert!(Config::from_yaml("display: bad").is_err())
@@ -1,29 +1,49 @@
 use crate::color::{self, Colors};
 use crate::display;
-use crate::flags::{ColorOption, Display, Flags, IconOption, IconTheme, Layout, SortOrder};
+use crate::exit_code::ExitCode;
+use crate::flags::sorting::DotFiles;
+use crate::flags::{
+    Block, ColorOption, DateFlag, DirGrouping, Display, Flags, HyperlinkOption, IconOption,
+    IconTheme, Layout, Output, Paging, RecursionOrder, SetTitle, SortColumn, SortOrder, Stream,
+    SymlinkDisplay, ThemeOption, ThousandsSeparator, TimeRange, Truncate, TtyOption,
+};
 use crate::icon::{self, Icons};
-use crate::meta::Meta;
-use crate::{print_error, print_output, sort};
-use std::path::PathBuf;
+use crate::meta::name::DisplayOption;
+use crate::meta::{ExportIgnore, FileType, Filtered, Meta, Name, Unlisted};
+use crate::{formatter, pager, print_error, print_output, sort, terminal};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Component, PathBuf};
 
 #[cfg(not(target_os = "windows"))]
 use std::io;
 #[cfg(not(target_os = "windows"))]
 use std::os::unix::io::AsRawFd;
 
-#[cfg(target_os = "windows")]
-use terminal_size::terminal_size;
-
 pub struct Core {
     flags: Flags,
     icons: Icons,
     //display: Display,
     colors: Colors,
-    sorters: Vec<(SortOrder, sort::SortFn)>,
+    sorters: Vec<sort::Sorter>,
+    /// Why the entries can not be streamed, if `--stream` is passed.
+    stream_blocker: Option<&'static str>,
+    /// When to page the output, [Paging::Auto] is resolved to [Paging::Never] if stdout is not a
+    /// tty.
+    paging: Paging,
+    /// The generator shuffling the entries before sorting them, with `--sort random`.
+    shuffler: Option<RefCell<StdRng>>,
+    /// Whether only the names of the entries are listed, in the order of the directories, so
+    /// that their metadata does not need to be read.
+    names_only: bool,
 }
 
 impl Core {
-    pub fn new(flags: Flags) -> Self {
+    pub fn new(mut flags: Flags) -> Self {
         // Check through libc if stdout is a tty. Unix specific so not on windows.
         // Determine color output availability (and initialize color output (for Windows 10))
         #[cfg(not(target_os = "windows"))]
@@ -33,25 +53,68 @@ impl Core {
         let console_color_ok = true;
 
         #[cfg(target_os = "windows")]
-        let tty_available = terminal_size().is_some(); // terminal_size allows us to know if the stdout is a tty or not.
+        let tty_available = terminal::size().is_some(); // terminal::size allows us to know if the stdout is a tty or not.
 
         #[cfg(target_os = "windows")]
         let console_color_ok = ansi_term::enable_ansi_support().is_ok();
 
+        let tty_available = tty_available || flags.tty == TtyOption::Force;
+        flags.layout = flags.layout_non_tty.resolve(flags.layout, tty_available);
+
         let mut inner_flags = flags.clone();
 
+        // The machine readable outputs are written without colors nor icons.
+        let machine_readable = flags.output.is_machine_readable();
+
         let color_theme = match (tty_available && console_color_ok, flags.color.when) {
+            _ if machine_readable => color::Theme::NoColor,
             (_, ColorOption::Never) | (false, ColorOption::Auto) => color::Theme::NoColor,
-            _ => color::Theme::Default,
+            _ => match flags.color.theme {
+                ThemeOption::Default => color::Theme::Default,
+                preset => color::Theme::Preset(preset),
+            },
         };
 
         let icon_theme = match (tty_available, flags.icons.when, flags.icons.theme) {
+            _ if machine_readable => icon::Theme::NoIcon,
             (_, IconOption::Never, _) | (false, IconOption::Auto, _) => icon::Theme::NoIcon,
+            (true, IconOption::Auto, theme) => {
+                icon::Theme::auto(theme, |name| std::env::var(name).ok())
+            }
             (_, _, IconTheme::Fancy) => icon::Theme::Fancy,
             (_, _, IconTheme::Unicode) => icon::Theme::Unicode,
         };
 
+        // The entries are only told empty directories when the icons are shown.
+        if icon_theme == icon::Theme::NoIcon {
+            flags.icons.when = IconOption::Never;
+        }
+        // Nor are the capabilities of the files read to color their names.
+        if matches!(color_theme, color::Theme::NoColor) {
+            flags.color.when = ColorOption::Never;
+        }
+
         let icon_separator = flags.icons.separator.0.clone();
+        let nerd_font_version = flags.icons.nerd_font_version;
+        let icon_overrides = flags.icons.overrides.clone();
+        let icon_lookup = flags.icons.lookup.clone();
+        let icon_hide_for = flags.icons.hide_for.clone();
+        let compound_extensions = flags.compound_extensions.0;
+
+        flags.display_indicators = flags.display_indicators.resolve(tty_available);
+        resolve_for_output(&mut flags, tty_available);
+        if flags.stat.0 && !machine_readable {
+            // The details are printed for the given files themselves, not for their content.
+            flags.display = Display::DirectoryOnly;
+            flags.layout = Layout::OneLine;
+            flags.recursion.enabled = false;
+            flags.stream = Stream(false);
+        }
+        if flags.recursion.depth == 0 {
+            // The given entries are listed themselves, without reading the directories.
+            flags.display = Display::DirectoryOnly;
+            flags.recursion.enabled = false;
+        }
 
         if !tty_available {
             // The output is not a tty, this means the command is piped. (ex: lsd -l | less)
@@ -62,36 +125,138 @@ impl Core {
         };
 
         let sorters = sort::assemble_sorters(&flags);
+        let shuffled = sorting_column_is_random(&flags);
+        let shuffler = match flags.sorting.seed.0 {
+            _ if !shuffled => None,
+            Some(seed) => Some(RefCell::new(StdRng::seed_from_u64(seed))),
+            None => Some(RefCell::new(StdRng::from_entropy())),
+        };
+
+        let sorting = &flags.sorting;
+        // The entries are streamed in the order of the directory, as with the `none` column.
+        let sorted = !sorting.keys.0.is_empty() || sorting.column != SortColumn::Name;
+        let stream_blocker = if (sorted && !sorting.is_unsorted())
+            || sorting.order == SortOrder::Reverse
+            || sorting.dir_grouping != DirGrouping::None
+            || sorting.dotfiles != DotFiles::Mixed
+            || !flags.sort_priority.is_empty()
+        {
+            Some("sorting is requested")
+        } else if flags.layout == Layout::Tree || flags.recursion.enabled {
+            Some("recursing is requested")
+        } else if flags.layout == Layout::Grid && tty_available {
+            Some("the grid layout is requested, pass --oneline")
+        } else if flags.layout == Layout::Pack {
+            Some("the pack layout is requested")
+        } else if machine_readable {
+            Some("the csv or json output is requested")
+        } else if flags.external_formatter.0.is_some() {
+            Some("an external formatter is requested")
+        } else if flags.total_size.0 {
+            Some("the total size is requested")
+        } else if flags.max_entries.0.is_some() {
+            Some("the maximum number of entries is requested")
+        } else {
+            None
+        };
+
+        let paging = match flags.paging {
+            Paging::Auto if !tty_available => Paging::Never,
+            paging => paging,
+        };
+
+        // Nothing but the name is rendered, so the entries do not need to be stat'ed.
+        let names_only = flags.sorting.is_unsorted()
+            && flags.sorting.dir_grouping == DirGrouping::None
+            && flags.sorting.dotfiles == DotFiles::Mixed
+            && flags.sort_priority.is_empty()
+            && (flags.layout == Layout::OneLine
+                || (flags.layout == Layout::Grid && !tty_available))
+            && flags.blocks.0 == [Block::Name]
+            && !machine_readable
+            && matches!(color_theme, color::Theme::NoColor)
+            && icon_theme == icon::Theme::NoIcon
+            && !flags.display_indicators.is_enabled()
+            && flags.hyperlink == HyperlinkOption::Never
+            && !flags.ipc_status.0
+            && !flags.recursion.enabled
+            && flags.display != Display::DirectoryOnly
+            && flags.time_range == TimeRange::default()
+            && !flags.total_size.0
+            && flags.max_entries.0.is_none()
+            && flags.external_formatter.0.is_none()
+            && paging == Paging::Never;
+
+        if flags.verbose.0 {
+            for note in resolution_notes(&flags, tty_available, color_theme, icon_theme) {
+                print_error!("note: {}.", note);
+            }
+        }
 
         Self {
             flags,
             //display: Display::new(inner_flags),
             colors: Colors::new(color_theme),
-            icons: Icons::new(icon_theme, icon_separator),
+            icons: Icons::with_nerd_font_version(icon_theme, icon_separator, nerd_font_version)
+                .with_overrides(icon_overrides)
+                .with_compound_extensions(compound_extensions)
+                .with_lookup(icon_lookup)
+                .with_hide_for(icon_hide_for),
             sorters,
+            stream_blocker,
+            paging,
+            shuffler,
+            names_only,
         }
     }
 
     pub fn run(self, paths: Vec<PathBuf>) {
-        let mut meta_list = self.fetch(paths);
+        if self.flags.set_title.0 {
+            print_output!("{}", terminal::title_sequence(&paths));
+        }
 
-        self.sort(&mut meta_list);
-        self.display(&meta_list)
-    }
+        if self.names_only {
+            return self.list_names(paths);
+        }
+
+        if self.flags.stream.0 {
+            match self.stream_blocker {
+                Some(reason) => print_error!("--stream is disabled as {}", reason),
+                None => return self.stream(paths),
+            }
+        }
 
-    fn fetch(&self, paths: Vec<PathBuf>) -> Vec<Meta> {
-        let mut meta_list = Vec::with_capacity(paths.len());
         let depth = match self.flags.layout {
             Layout::Tree { .. } => self.flags.recursion.depth,
             _ if self.flags.recursion.enabled => self.flags.recursion.depth,
             _ => 1,
         };
 
+        let walk = self.flags.layout != Layout::Tree
+            && self.flags.output == Output::Text
+            && self.flags.external_formatter.0.is_none()
+            && !self.flags.stat.0;
+        if walk {
+            return self.walk(paths, depth);
+        }
+
+        let mut meta_list = self.fetch(paths, depth, false);
+
+        self.sort(&mut meta_list);
+        self.display(&meta_list)
+    }
+
+    /// Read the given paths, and the content of the directories among them `depth` levels deep,
+    /// or only their own content if the one of their entries is `deferred` until walked.
+    fn fetch(&self, paths: Vec<PathBuf>, depth: usize, deferred: bool) -> Vec<Meta> {
+        let mut meta_list = Vec::with_capacity(paths.len());
+
         for path in paths {
-            let mut meta = match Meta::from_path(&path, self.flags.dereference.0) {
+            let mut meta = match Meta::from_path_with(&path, &self.flags) {
                 Ok(meta) => meta,
                 Err(err) => {
                     print_error!("{}: {}.", path.display(), err);
+                    ExitCode::MajorIssue.set_if_greater();
                     continue;
                 }
             };
@@ -99,46 +264,595 @@ impl Core {
             let recurse =
                 self.flags.layout == Layout::Tree || self.flags.display != Display::DirectoryOnly;
             if recurse {
-                match meta.recurse_into(depth, &self.flags) {
-                    Ok(content) => {
-                        meta.content = content;
-                        meta_list.push(meta);
-                    }
+                let content = if deferred {
+                    meta.read_content(depth, &self.flags)
+                } else {
+                    meta.recurse_into(depth, &self.flags)
+                };
+                match content {
+                    Ok(content) => meta.content = content,
+                    Err(err) => meta.report_unreadable(&err),
+                };
+            }
+            meta.probe_empty_dir(&self.flags);
+            meta_list.push(meta);
+        }
+        if self.flags.total_size.0 {
+            let mut counted = HashSet::new();
+            for meta in &mut meta_list.iter_mut() {
+                meta.calculate_total_size(&mut counted);
+            }
+        }
+
+        meta_list
+    }
+
+    /// Print the entries in the order of the directories, as soon as they are read.
+    fn stream(&self, paths: Vec<PathBuf>) {
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        let with_headers = paths.len() > 1;
+        let mut filtered = Filtered::default();
+
+        for path in paths {
+            let mut meta = match Meta::from_path_with(&path, &self.flags) {
+                Ok(meta) => meta,
+                Err(err) => {
+                    print_error!("{}: {}.", path.display(), err);
+                    ExitCode::MajorIssue.set_if_greater();
+                    continue;
+                }
+            };
+            meta.probe_empty_dir(&self.flags);
+
+            let is_dir = matches!(meta.file_type, FileType::Directory { .. });
+            let res = if !is_dir || self.flags.display == Display::DirectoryOnly {
+                display::stream(
+                    vec![meta],
+                    &DisplayOption::None,
+                    &self.flags,
+                    &self.colors,
+                    &self.icons,
+                    &mut handle,
+                )
+            } else {
+                let entries = match meta.path.read_dir() {
+                    Ok(entries) => entries,
                     Err(err) => {
-                        print_error!("lsd: {}: {}\n", path.display(), err);
+                        meta.report_unreadable(&err);
                         continue;
                     }
                 };
+                if with_headers {
+                    print_output!("{}", display::display_folder_path(&meta));
+                }
+                let base_path = meta.path.clone();
+
+                let mut dots = vec![];
+                if self.flags.display == Display::All {
+                    let parent = meta.path.join(Component::ParentDir);
+                    if let Ok(mut parent) = Meta::from_path_with(&parent, &self.flags) {
+                        parent.name.name = "..".to_owned();
+                        dots.push(parent);
+                    }
+                    meta.name.name = ".".to_owned();
+                    dots.insert(0, meta);
+                }
+
+                let flags = &self.flags;
+                let export_ignore = if flags.respect_gitattributes.0 {
+                    Some(ExportIgnore::for_dir(&base_path))
+                } else {
+                    None
+                };
+                let metas = entries.filter_map(|entry| {
+                    let path = entry.ok()?.path();
+                    let export_ignored = match &export_ignore {
+                        Some(export_ignore) => export_ignore.is_ignored(path.file_name()?),
+                        None => false,
+                    };
+                    let unlisted = match Meta::unlisted(&path, flags) {
+                        None if export_ignored => Some(Unlisted::Ignored),
+                        unlisted => unlisted,
+                    };
+                    if let Some(unlisted) = unlisted {
+                        filtered.add(unlisted);
+                        return None;
+                    }
+                    match Meta::from_path_with(&path, flags) {
+                        Ok(mut meta) if meta.date.is_within(&flags.time_range) => {
+                            meta.probe_empty_dir(flags);
+                            Some(meta)
+                        }
+                        Ok(_) => None,
+                        Err(err) => {
+                            print_error!("{}: {}.", path.display(), err);
+                            ExitCode::MinorIssue.set_if_greater();
+                            None
+                        }
+                    }
+                });
+
+                display::stream(
+                    dots.into_iter().chain(metas),
+                    &DisplayOption::Relative {
+                        base_path: &base_path,
+                    },
+                    flags,
+                    &self.colors,
+                    &self.icons,
+                    &mut handle,
+                )
+            };
+
+            // Stop with success when stdout is closed, eg: when piped into head.
+            if res.is_err() {
+                std::process::exit(0);
+            }
+        }
+        if let Some(summary) = self.summary(filtered) {
+            print_output!("{}", summary);
+        }
+    }
+
+    /// Print the names of the entries in the order of the directories, as soon as they are read,
+    /// without reading their metadata but for the symlinks whose target is shown.
+    fn list_names(&self, paths: Vec<PathBuf>) {
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        let with_headers = paths.len() > 1;
+        let mut filtered = Filtered::default();
+        let flags = &self.flags;
+        // The same conditions as in the name block, which needs the metadata of the symlinks.
+        let with_targets = flags.symlink_display == SymlinkDisplay::Target
+            || !(flags.no_symlink.0
+                || flags.dereference.0
+                || flags.layout == Layout::Grid
+                || flags.symlink_display != SymlinkDisplay::Full);
+
+        for path in paths {
+            let mut meta = match Meta::from_path_with(&path, flags) {
+                Ok(meta) => meta,
+                Err(err) => {
+                    print_error!("{}: {}.", path.display(), err);
+                    ExitCode::MajorIssue.set_if_greater();
+                    continue;
+                }
+            };
+
+            if !matches!(meta.file_type, FileType::Directory { .. }) {
+                self.stream_or_exit(vec![meta], &DisplayOption::None, &mut handle);
+                continue;
+            }
+            let entries = match meta.path.read_dir() {
+                Ok(entries) => entries,
+                Err(err) => {
+                    meta.report_unreadable(&err);
+                    continue;
+                }
+            };
+            if with_headers {
+                print_output!("{}", display::display_folder_path(&meta));
+            }
+            let base_path = meta.path.clone();
+            let display_option = DisplayOption::Relative {
+                base_path: &base_path,
+            };
+
+            if flags.display == Display::All {
+                let mut dots = vec![];
+                let parent = meta.path.join(Component::ParentDir);
+                if let Ok(mut parent) = Meta::from_path_with(&parent, flags) {
+                    parent.name.name = "..".to_owned();
+                    dots.push(parent);
+                }
+                meta.name.name = ".".to_owned();
+                dots.insert(0, meta);
+                self.stream_or_exit(dots, &display_option, &mut handle);
+            }
+
+            let export_ignore = if flags.respect_gitattributes.0 {
+                Some(ExportIgnore::for_dir(&base_path))
             } else {
-                meta_list.push(meta);
+                None
             };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let export_ignored = match (&export_ignore, path.file_name()) {
+                    (Some(export_ignore), Some(name)) => export_ignore.is_ignored(name),
+                    _ => false,
+                };
+                let unlisted = match Meta::unlisted(&path, flags) {
+                    None if export_ignored => Some(Unlisted::Ignored),
+                    unlisted => unlisted,
+                };
+                if let Some(unlisted) = unlisted {
+                    filtered.add(unlisted);
+                    continue;
+                }
+
+                // The type of the entry is read along with the directory on most file systems.
+                let symlink = entry.file_type().map_or(true, |t| t.is_symlink());
+                if with_targets && symlink {
+                    match Meta::from_path_with(&path, flags) {
+                        Ok(meta) => self.stream_or_exit(vec![meta], &display_option, &mut handle),
+                        Err(err) => {
+                            print_error!("{}: {}.", path.display(), err);
+                            ExitCode::MinorIssue.set_if_greater();
+                        }
+                    }
+                    continue;
+                }
+
+                // The file type only matters to the colors and the icons, which are disabled.
+                let name = Name::new(
+                    &path,
+                    FileType::File {
+                        uid: false,
+                        exec: false,
+                    },
+                );
+                let name = name.render(&self.colors, &self.icons, &display_option, flags);
+                // Stop with success when stdout is closed, eg: when piped into head.
+                if writeln!(handle, "{}", name).is_err() {
+                    std::process::exit(0);
+                }
+            }
         }
-        if self.flags.total_size.0 {
-            for meta in &mut meta_list.iter_mut() {
-                meta.calculate_total_size();
+        if let Some(summary) = self.summary(filtered) {
+            print_output!("{}", summary);
+        }
+    }
+
+    /// Print entries as they are streamed, stopping with success when stdout is closed.
+    fn stream_or_exit<I, W>(&self, metas: I, display_option: &DisplayOption, writer: &mut W)
+    where
+        I: IntoIterator<Item = Meta>,
+        W: Write,
+    {
+        let res = display::stream(
+            metas,
+            display_option,
+            &self.flags,
+            &self.colors,
+            &self.icons,
+            writer,
+        );
+        if res.is_err() {
+            std::process::exit(0);
+        }
+    }
+
+    /// Print the grid of the given entries, then the listings of the directories among them, one
+    /// directory at a time, depth first, so that only the directories being walked are kept in
+    /// memory instead of the whole tree, unless the total sizes are counted.
+    fn walk(&self, paths: Vec<PathBuf>, depth: usize) {
+        // The whole tree is read to count the total sizes.
+        let deferred = !self.flags.total_size.0;
+        let mut meta_list = self.fetch(paths, depth, deferred);
+        if deferred {
+            for meta in &mut meta_list {
+                self.read_ahead(meta, depth);
             }
+            self.sort_entries(&mut meta_list);
+        } else {
+            self.sort(&mut meta_list);
         }
 
-        meta_list
+        let mut listing = Listing {
+            paged: if self.paging == Paging::Never {
+                None
+            } else {
+                Some(String::new())
+            },
+            started: false,
+        };
+        let with_headers = display::should_display_folder_path(0, &meta_list, &self.flags);
+        let contents: Vec<_> = meta_list
+            .iter_mut()
+            .map(|meta| meta.content.take())
+            .collect();
+        listing.write(&display::grid(
+            &meta_list,
+            &self.flags,
+            &self.colors,
+            &self.icons,
+        ));
+
+        let mut filtered = Filtered::default();
+        for (mut meta, content) in meta_list.into_iter().zip(contents) {
+            if content.is_some() {
+                meta.content = content;
+                filtered += self.walk_into(meta, depth, 1, with_headers, &mut listing);
+            }
+        }
+        if let Some(summary) = self.summary(filtered) {
+            listing.write(&summary);
+        }
+        if let Some(output) = listing.paged {
+            self.page(&output);
+        }
     }
 
-    fn sort(&self, metas: &mut Vec<Meta>) {
-        metas.sort_unstable_by(|a, b| sort::by_meta(&self.sorters, a, b));
+    /// Write the entries of a directory whose content is read, then walk into its
+    /// subdirectories, or walk into them first with the post order.
+    /// This returns the number of entries left out of the directories walked.
+    fn walk_into(
+        &self,
+        mut meta: Meta,
+        depth: usize,
+        level: usize,
+        with_header: bool,
+        listing: &mut Listing,
+    ) -> Filtered {
+        let mut filtered = meta.filtered;
+        let mut entries = meta.content.take().unwrap_or_default();
+        let contents: Vec<_> = entries
+            .iter_mut()
+            .map(|entry| entry.content.take())
+            .collect();
 
+        let mut output = display::grid_of_entries(
+            &meta.path,
+            &entries,
+            level,
+            &self.flags,
+            &self.colors,
+            &self.icons,
+        );
+        if meta.truncated > 0 {
+            output += &display::truncation_notice(meta.truncated);
+            output.push('\n');
+        }
+        let header = if with_header { Some(&meta) } else { None };
+        let post_order = self.flags.recursion.order == RecursionOrder::Post;
+        if !post_order {
+            listing.write_directory(header, &output);
+        }
+
+        for (mut entry, content) in entries.into_iter().zip(contents) {
+            if content.is_some() {
+                entry.content = content;
+                self.read_ahead(&mut entry, depth - 1);
+                filtered += self.walk_into(entry, depth - 1, level + 1, true, listing);
+            }
+        }
+        if post_order {
+            listing.write_directory(header, &output);
+        }
+        filtered
+    }
+
+    /// The footer line printed with `--summary`, if any entry was left out of a text output.
+    fn summary(&self, filtered: Filtered) -> Option<String> {
+        if !self.flags.summary.0
+            || self.flags.output != Output::Text
+            || self.flags.external_formatter.0.is_some()
+        {
+            return None;
+        }
+        filtered.render().map(|summary| format!("{}\n", summary))
+    }
+
+    /// Read the content of the subdirectories of a directory about to be walked, which is needed
+    /// to list them, then sort its entries. Every directory is read once this way, a level ahead
+    /// of the one printed.
+    fn read_ahead(&self, meta: &mut Meta, depth: usize) {
+        if self.flags.total_size.0 {
+            // The whole tree is already read and sorted.
+            return;
+        }
+        meta.recurse_into_entries(depth, &self.flags);
+        if let Some(ref mut content) = meta.content {
+            self.sort_entries(content);
+        }
+        self.truncate(meta);
+    }
+
+    fn sort(&self, metas: &mut Vec<Meta>) {
+        self.sort_entries(metas);
         for meta in metas {
             if let Some(ref mut content) = meta.content {
                 self.sort(content);
             }
+            self.truncate(meta);
+        }
+    }
+
+    /// Sort the entries of a single directory, leaving their content as it is.
+    fn sort_entries(&self, metas: &mut [Meta]) {
+        if self.flags.normalize.0 {
+            for meta in metas.iter_mut() {
+                meta.name.normalize();
+            }
+        }
+        match &self.shuffler {
+            Some(shuffler) => {
+                // Start from the same order whatever the order of the directory, so that a seed
+                // always gives the same order, then keep the shuffled order within the groups.
+                metas.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+                metas.shuffle(&mut *shuffler.borrow_mut());
+                metas.sort_by(|a, b| sort::by_meta(&self.sorters, a, b));
+            }
+            None => metas.sort_unstable_by(|a, b| sort::by_meta(&self.sorters, a, b)),
+        }
+    }
+
+    /// Keep the first entries of a sorted directory, with `--max-entries`.
+    fn truncate(&self, meta: &mut Meta) {
+        if let Some(max) = self.flags.max_entries.0 {
+            meta.truncate_content(max);
         }
     }
 
     fn display(&self, metas: &[Meta]) {
-        let output = if self.flags.layout == Layout::Tree {
-            display::tree(&metas, &self.flags, &self.colors, &self.icons)
+        let output = if let Some(formatter) = &self.flags.external_formatter.0 {
+            let json = display::json(metas, &self.flags);
+            match formatter::run(formatter::command(formatter), json) {
+                Ok(output) => output,
+                Err(err) => {
+                    print_error!("failed to run the external formatter: {}", err);
+                    ExitCode::MajorIssue.set_if_greater();
+                    return;
+                }
+            }
+        } else if self.flags.output == Output::Csv {
+            display::csv(metas, &self.flags, &self.colors, &self.icons)
+        } else if self.flags.output == Output::Json {
+            display::json(metas, &self.flags)
+        } else if self.flags.stat.0 {
+            display::stat(metas, &self.flags, &self.colors, &self.icons)
         } else {
-            display::grid(&metas, &self.flags, &self.colors, &self.icons)
+            display::tree(&metas, &self.flags, &self.colors, &self.icons)
+        };
+        let output = match self.summary(Filtered::total(metas)) {
+            Some(summary) => output + &summary,
+            None => output,
         };
+        self.page(&output);
+    }
 
-        print_output!("{}", output);
+    /// Write the output through the pager, when it is enabled and the output does not fit.
+    fn page(&self, output: &str) {
+        let height = terminal::size().map(|dimensions| dimensions.height);
+        pager::write(output, self.paging, height, |output| {
+            pager::spawn(pager::command(|name| std::env::var(name).ok()), output)
+        });
     }
 }
+
+/// Where a listing is written as it is walked: straight to stdout, or kept to be paged.
+struct Listing {
+    /// The output kept to be paged, or [None] when it is printed as it goes.
+    paged: Option<String>,
+    /// Whether anything is written yet, as only then is a directory separated by a blank line.
+    started: bool,
+}
+
+impl Listing {
+    fn write(&mut self, output: &str) {
+        if output.is_empty() {
+            return;
+        }
+        self.started = true;
+        match &mut self.paged {
+            Some(paged) => paged.push_str(output),
+            None => {
+                print_output!("{}", output);
+            }
+        }
+    }
+
+    /// Write the listing of a directory, after its path if `header` is given.
+    fn write_directory(&mut self, header: Option<&Meta>, output: &str) {
+        if let Some(meta) = header {
+            let header = display::display_folder_path(meta);
+            if self.started {
+                self.write(&header);
+            } else {
+                self.write(header.trim_start_matches('\n'));
+            }
+        }
+        self.write(output);
+    }
+}
+
+/// Resolve the flags whose value depends on the output: the machine readable outputs are written
+/// with the raw values, while the text output adapts to whether stdout is a tty.
+fn resolve_for_output(flags: &mut Flags, tty_available: bool) {
+    if flags.output.is_machine_readable() {
+        flags.control_chars = flags.control_chars.resolve(false);
+        flags.hyperlink = HyperlinkOption::Never;
+        flags.thousands_separator = ThousandsSeparator::None;
+        flags.truncate = Truncate(None);
+        flags.set_title = SetTitle(false);
+    } else {
+        flags.control_chars = flags.control_chars.resolve(tty_available);
+        flags.hyperlink = flags.hyperlink.resolve(tty_available);
+        flags.thousands_separator = flags
+            .thousands_separator
+            .resolve(|name| std::env::var(name).ok());
+        flags.set_title = SetTitle(flags.set_title.0 && tty_available);
+    }
+}
+
+/// Whether the entries are shuffled, either by the sort column or by one of the sort keys.
+fn sorting_column_is_random(flags: &Flags) -> bool {
+    flags.sorting.column == SortColumn::Random
+        || flags
+            .sorting
+            .keys
+            .0
+            .iter()
+            .any(|(column, _)| *column == SortColumn::Random)
+}
+
+/// Describe the direction the entries are sorted in by the sort column, whose default one
+/// depends on the column.
+fn sort_direction(flags: &Flags) -> String {
+    // The first sort key may be reversed by itself, then reversed again by `--reverse`.
+    let order = match flags.sorting.keys.0.first() {
+        Some((_, order)) if *order != flags.sorting.order => SortOrder::Reverse,
+        Some(_) => SortOrder::Default,
+        None => flags.sorting.order,
+    };
+    let (first, last) = match flags.sorting.column {
+        SortColumn::Size => ("largest first", "smallest first"),
+        SortColumn::Time | SortColumn::Recent => ("newest first", "oldest first"),
+        SortColumn::Random | SortColumn::None => ("", ""),
+        _ => ("ascending", "descending"),
+    };
+    match order {
+        SortOrder::Default if first.is_empty() => "default".to_string(),
+        SortOrder::Reverse if last.is_empty() => "reversed".to_string(),
+        SortOrder::Default => format!("default ({})", first),
+        SortOrder::Reverse => format!("reversed ({})", last),
+    }
+}
+
+/// Describe the settings resolved from the arguments, the configuration file, the defaults and
+/// the terminal, to explain the output with `--verbose`.
+fn resolution_notes(
+    flags: &Flags,
+    tty_available: bool,
+    color_theme: color::Theme,
+    icon_theme: icon::Theme,
+) -> Vec<String> {
+    let name = |value: &dyn std::fmt::Debug| format!("{:?}", value).to_lowercase();
+    let layout = match flags.layout {
+        Layout::Grid if !tty_available => "oneline, as stdout is not a tty".to_string(),
+        layout => name(&layout),
+    };
+    let color = match color_theme {
+        color::Theme::NoColor => "never".to_string(),
+        color::Theme::Preset(ThemeOption::File(_)) => "always, with a theme file".to_string(),
+        color::Theme::Preset(preset) => format!("always, with the {} theme", name(&preset)),
+        _ => "always".to_string(),
+    };
+    let icons = match icon_theme {
+        icon::Theme::NoIcon => "never".to_string(),
+        theme => format!("always, with the {} theme", name(&theme)),
+    };
+    let date = match &flags.date {
+        DateFlag::Formatted(format) => format.clone(),
+        date => name(date),
+    };
+    let blocks: Vec<&str> = flags.blocks.0.iter().map(|block| block.name()).collect();
+
+    vec![
+        format!("tty: {}", if tty_available { "yes" } else { "no" }),
+        format!("layout: {}", layout),
+        format!("output: {}", name(&flags.output)),
+        format!("blocks: {}", blocks.join(",")),
+        format!(
+            "sort: {} {}, directories grouped {}",
+            name(&flags.sorting.column),
+            sort_direction(flags),
+            name(&flags.sorting.dir_grouping),
+        ),
+        format!("color: {}", color),
+        format!("icons: {}", icons),
+        format!("size: {}", name(&flags.size)),
+        format!("date: {}", date),
+    ]
+}
@@ -1,12 +1,19 @@
-use crate::color::{ColoredString, Colors};
-use crate::flags::{Block, Display, Flags, Layout};
-use crate::icon::Icons;
+use crate::color::{ColoredString, Colors, Elem, Theme};
+use crate::flags::block_align::Alignment;
+use crate::flags::{
+    Block, Display, Flags, Layout, RecursionOrder, SortColumn, SymlinkDisplay, TtyOption,
+};
+use crate::icon::{self, Icons};
 use crate::meta::name::DisplayOption;
 use crate::meta::{FileType, Meta};
+use crate::sort;
+use crate::terminal::{self, Dimensions};
 use ansi_term::{ANSIString, ANSIStrings};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::Path;
 use term_grid::{Cell, Direction, Filling, Grid, GridOptions};
-use terminal_size::terminal_size;
 use unicode_width::UnicodeWidthStr;
 
 const EDGE: &str = "\u{251c}\u{2500}\u{2500}"; // "├──"
@@ -15,18 +22,62 @@ const CORNER: &str = "\u{2514}\u{2500}\u{2500}"; // "└──"
 const BLANK: &str = "   ";
 
 pub fn grid(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> String {
-    let term_width = match terminal_size() {
-        Some((w, _)) => Some(w.0 as usize),
-        None => None,
-    };
+    grid_sized(&DisplayOption::None, metas, 0, flags, colors, icons, || {
+        size(flags)
+    })
+}
+
+/// Display the grid of the entries of a directory at the given depth.
+pub fn grid_of_entries(
+    base_path: &Path,
+    entries: &[Meta],
+    depth: usize,
+    flags: &Flags,
+    colors: &Colors,
+    icons: &Icons,
+) -> String {
+    grid_sized(
+        &DisplayOption::Relative { base_path },
+        entries,
+        depth,
+        flags,
+        colors,
+        icons,
+        || size(flags),
+    )
+}
+
+/// The size of the terminal, or the assumed one when the terminal is forced with `--tty=force`.
+fn size(flags: &Flags) -> Option<Dimensions> {
+    match flags.tty {
+        TtyOption::Force => terminal::size()
+            .or_else(|| Some(terminal::fallback_size(|name| std::env::var(name).ok()))),
+        TtyOption::Auto => terminal::size(),
+    }
+}
+
+/// Display the grid within the width of the terminal, as queried by `size`.
+fn grid_sized<F>(
+    display_option: &DisplayOption,
+    metas: &[Meta],
+    depth: usize,
+    flags: &Flags,
+    colors: &Colors,
+    icons: &Icons,
+    size: F,
+) -> String
+where
+    F: FnOnce() -> Option<Dimensions>,
+{
+    let term_width = size().map(|dimensions| dimensions.width);
 
     inner_display_grid(
-        &DisplayOption::None,
+        display_option,
         metas,
         &flags,
         colors,
         icons,
-        0,
+        depth,
         term_width,
     )
 }
@@ -46,13 +97,310 @@ pub fn tree(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> St
         }
     }
 
-    for cell in inner_display_tree(metas, &flags, colors, icons, (0, ""), &padding_rules, index) {
+    let mut cells = inner_display_tree(
+        metas,
+        &flags,
+        colors,
+        icons,
+        (0, "", 0),
+        &padding_rules,
+        index,
+    );
+    align_blocks(&mut cells, flags);
+    for cell in cells {
         grid.add(cell);
     }
 
     grid.fit_into_columns(flags.blocks.0.len()).to_string()
 }
 
+/// Display each entry vertically, one attribute per line, with a blank line between entries.
+pub fn stat(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> String {
+    let mut entries = vec![];
+    for meta in metas {
+        let blocks = meta
+            .size
+            .blocks()
+            .map_or_else(|| String::from("-"), |blocks| blocks.to_string());
+        let mut lines: Vec<(&str, String)> = vec![
+            (
+                "Name",
+                meta.name
+                    .render(colors, icons, &DisplayOption::None, flags)
+                    .to_string(),
+            ),
+            ("Type", meta.file_type.name().to_string()),
+            (
+                "Size",
+                format!(
+                    "{} ({} bytes)",
+                    meta.size.render_compact(colors, flags),
+                    meta.size.get_bytes()
+                ),
+            ),
+            ("Blocks", blocks),
+            ("Modified", meta.date.render(colors, flags).to_string()),
+            (
+                "Accessed",
+                meta.accessed
+                    .render_with(colors, &flags.date_accessed.0, &flags.date_relative)
+                    .to_string(),
+            ),
+            ("Changed", meta.changed.render(colors, flags).to_string()),
+            (
+                "Permissions",
+                format!(
+                    "{} ({})",
+                    meta.permissions.octal(),
+                    meta.permissions.render(colors)
+                ),
+            ),
+            ("User", meta.owner.render_user(colors).to_string()),
+            ("Group", meta.owner.render_group(colors).to_string()),
+            ("Inode", meta.inode.render(colors).to_string()),
+            ("Links", meta.links.render(colors, flags).to_string()),
+        ];
+        if let Some(target) = meta.symlink.render_target(colors, flags) {
+            lines.push(("Target", target.to_string()));
+        }
+
+        let width = lines
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or(0);
+        entries.push(
+            lines
+                .iter()
+                .map(|(label, value)| format!("{:>width$}: {}\n", label, value, width = width))
+                .collect::<String>(),
+        );
+    }
+    entries.join("\n")
+}
+
+pub fn csv(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> String {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer
+        .write_record(flags.blocks.0.iter().map(|block| block.name()))
+        .expect("failed to write the csv header");
+
+    inner_display_csv(
+        &mut writer,
+        &DisplayOption::None,
+        metas,
+        flags,
+        colors,
+        icons,
+        0,
+    );
+
+    let output = writer.into_inner().expect("failed to flush the csv output");
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+/// Write each entry on its own line as soon as it is produced by `metas`, so that the output of
+/// a huge directory begins before it is fully read. The blocks are separated by a single space,
+/// as aligning them would require every entry.
+pub fn stream<I, W>(
+    metas: I,
+    display_option: &DisplayOption,
+    flags: &Flags,
+    colors: &Colors,
+    icons: &Icons,
+    writer: &mut W,
+) -> io::Result<()>
+where
+    I: IntoIterator<Item = Meta>,
+    W: Write,
+{
+    for meta in metas {
+        let padding_rules = get_padding_rules(std::slice::from_ref(&meta), flags);
+        let blocks = get_output(
+            &meta,
+            colors,
+            icons,
+            flags,
+            display_option,
+            &padding_rules,
+            (0, ""),
+        );
+        let line = blocks
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        writeln!(writer, "{}", line)?;
+    }
+
+    Ok(())
+}
+
+/// The version of the JSON output, to bump when a field is renamed, removed or changes meaning.
+const JSON_VERSION: u32 = 1;
+
+/// The JSON document, the entries are versioned so consumers can rely on their fields.
+#[derive(Serialize)]
+struct JsonListing {
+    version: u32,
+    entries: Vec<JsonEntry>,
+}
+
+/// An entry of the JSON output. These field names are documented and must stay stable.
+#[derive(Serialize)]
+struct JsonEntry {
+    name: String,
+    path: String,
+    #[serde(rename = "type")]
+    file_type: &'static str,
+    size: u64,
+    permissions: String,
+    user: String,
+    group: String,
+    modified: String,
+    accessed: String,
+    inode: Option<u64>,
+    links: Option<u64>,
+    target: Option<String>,
+    /// Whether this directory is at the depth limit of the recursion, its content not listed.
+    truncated: bool,
+}
+
+/// Write the entries as a JSON document, independently of the blocks and without colors.
+pub fn json(metas: &[Meta], flags: &Flags) -> String {
+    let mut entries = Vec::new();
+    inner_display_json(&mut entries, &DisplayOption::None, metas, flags, 0);
+
+    let listing = JsonListing {
+        version: JSON_VERSION,
+        entries,
+    };
+    let mut output = serde_json::to_string_pretty(&listing).expect("failed to write the json");
+    output.push('\n');
+    output
+}
+
+fn inner_display_json(
+    entries: &mut Vec<JsonEntry>,
+    display_option: &DisplayOption,
+    metas: &[Meta],
+    flags: &Flags,
+    depth: usize,
+) {
+    let no_colors = Colors::new(Theme::NoColor);
+    let no_icons = Icons::new(icon::Theme::NoIcon, String::new());
+
+    // As in the csv output, the directories given by the user are replaced by their contents.
+    let skip_dirs = (depth == 0) && (flags.display != Display::DirectoryOnly);
+    let recursing = flags.layout == Layout::Tree || flags.recursion.enabled;
+
+    for meta in metas {
+        if !(skip_dirs && meta.file_type.is_dirlike()) {
+            let truncated = recursing
+                && depth == flags.recursion.depth
+                && meta.file_type.is_dirlike()
+                && meta.content.is_none()
+                && !matches!(meta.name.name.as_str(), "." | "..");
+            entries.push(JsonEntry {
+                name: meta
+                    .name
+                    .render(&no_colors, &no_icons, display_option, &Flags::default())
+                    .to_string(),
+                path: meta.path.to_string_lossy().into_owned(),
+                file_type: meta.file_type.name(),
+                size: meta.size.get_bytes(),
+                permissions: meta.permissions.render(&no_colors).to_string(),
+                user: meta.owner.user().to_owned(),
+                group: meta.owner.group().to_owned(),
+                modified: meta.date.to_rfc3339(),
+                accessed: meta.accessed.to_rfc3339(),
+                inode: meta.inode.index(),
+                links: meta.links.nlink(),
+                target: meta.symlink.symlink_string(),
+                truncated,
+            });
+        }
+
+        if let Some(content) = &meta.content {
+            let base_path = match display_option {
+                DisplayOption::Relative { base_path } => base_path,
+                _ => meta.path.as_path(),
+            };
+            inner_display_json(
+                entries,
+                &DisplayOption::Relative { base_path },
+                content,
+                flags,
+                depth + 1,
+            );
+        }
+    }
+}
+
+fn inner_display_csv(
+    writer: &mut csv::Writer<Vec<u8>>,
+    display_option: &DisplayOption,
+    metas: &[Meta],
+    flags: &Flags,
+    colors: &Colors,
+    icons: &Icons,
+    depth: usize,
+) {
+    let padding_rules = get_padding_rules(metas, flags);
+
+    // As in the grid, the directories given by the user are replaced by their contents.
+    let skip_dirs = (depth == 0) && (flags.display != Display::DirectoryOnly);
+
+    for meta in metas {
+        let is_dir = matches!(meta.file_type, FileType::Directory { .. })
+            || matches!(meta.file_type, FileType::SymLink { is_dir: true });
+        if !(skip_dirs && is_dir) {
+            let blocks = get_output(
+                meta,
+                colors,
+                icons,
+                flags,
+                display_option,
+                &padding_rules,
+                (0, ""),
+            );
+            let record = blocks
+                .iter()
+                .zip(flags.blocks.0.iter())
+                .map(|(cell, block)| {
+                    let cell = cell.to_string();
+                    // only the name may start or end with meaningful spaces
+                    match block {
+                        Block::Name => cell,
+                        _ => cell.trim().to_string(),
+                    }
+                });
+            writer
+                .write_record(record)
+                .expect("failed to write a csv record");
+        }
+
+        if let Some(content) = &meta.content {
+            // the entries of the nested directories are named by their path from the listed one
+            let base_path = match display_option {
+                DisplayOption::Relative { base_path } => base_path,
+                _ => meta.path.as_path(),
+            };
+            inner_display_csv(
+                writer,
+                &DisplayOption::Relative { base_path },
+                content,
+                flags,
+                colors,
+                icons,
+                depth + 1,
+            );
+        }
+    }
+}
+
+/// Display the grid of the entries of a single directory, the content of the directories among
+/// them being walked by the caller.
 fn inner_display_grid(
     display_option: &DisplayOption,
     metas: &[Meta],
@@ -71,8 +419,12 @@ fn inner_display_grid(
             direction: Direction::LeftToRight,
         }),
         _ => Grid::new(GridOptions {
-            filling: Filling::Spaces(2),
-            direction: Direction::TopToBottom,
+            filling: Filling::Spaces(flags.grid_gap.0),
+            direction: if flags.across.0 {
+                Direction::LeftToRight
+            } else {
+                Direction::TopToBottom
+            },
         }),
     };
 
@@ -81,6 +433,16 @@ fn inner_display_grid(
     // asked to display the directory itself (rather than its contents).
     let skip_dirs = (depth == 0) && (flags.display != Display::DirectoryOnly);
 
+    let separate_groups = flags.group_separators.0 && flags.layout == Layout::OneLine;
+    let mut previous: Option<&Meta> = None;
+    let date_groups = flags.date_groups.0
+        && flags.layout == Layout::OneLine
+        && flags.sorting.column == SortColumn::Time;
+    // The headers of the age groups, with the index of the row they are printed above.
+    let mut headers: Vec<(usize, &str)> = vec![];
+    let mut rows = 0;
+    let mut cells = vec![];
+
     // print the files first.
     for meta in metas {
         // Maybe skip showing the directory meta now; show its contents later.
@@ -92,6 +454,27 @@ fn inner_display_grid(
             continue;
         }
 
+        if separate_groups {
+            if let Some(previous) = previous {
+                if sort::is_group_boundary(flags, previous, meta) {
+                    // a row of empty cells, trimmed to a blank line below
+                    for _ in 0..flags.blocks.0.len() {
+                        cells.push(Cell::from(""));
+                    }
+                    rows += 1;
+                }
+            }
+            previous = Some(meta);
+        }
+
+        if date_groups {
+            let group = meta.date.age_group();
+            if headers.last().map(|(_, last)| *last) != Some(group) {
+                headers.push((rows, group));
+            }
+        }
+        rows += 1;
+
         let blocks = get_output(
             &meta,
             &colors,
@@ -105,69 +488,128 @@ fn inner_display_grid(
         for block in blocks {
             let block_str = block.to_string();
 
-            grid.add(Cell {
+            cells.push(Cell {
                 width: get_visible_width(&block_str),
                 contents: block_str,
             });
         }
     }
 
+    if flags.layout == Layout::Pack {
+        let entries: Vec<String> = cells
+            .chunks(flags.blocks.0.len())
+            .map(|blocks| {
+                blocks
+                    .iter()
+                    .map(|cell| cell.contents.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        output += &pack(&entries, term_width);
+    }
+
+    // the grid layout only holds names, in as many columns as they fit
+    if flags.layout != Layout::Grid {
+        align_blocks(&mut cells, flags);
+    }
+    let cell_count = cells.len();
+    for cell in cells {
+        grid.add(cell);
+    }
+
     if flags.layout == Layout::Grid {
-        if let Some(tw) = term_width {
-            if let Some(gridded_output) = grid.fit_into_width(tw) {
-                output += &gridded_output.to_string();
-            } else {
-                //does not fit into grid, usually because (some) filename(s)
-                //are longer or almost as long as term_width
-                //print line by line instead!
-                output += &grid.fit_into_columns(1).to_string();
+        let natural = term_width.and_then(|tw| grid.fit_into_width(tw));
+        // the minimum columns are only used when they fit, the whole width without a terminal
+        let minimum = flags
+            .min_columns
+            .0
+            .map(|columns| grid.fit_into_columns(columns.min(cell_count).max(1)))
+            .filter(|minimum| match term_width {
+                Some(tw) => minimum.width() <= tw,
+                None => true,
+            });
+        match (natural, minimum) {
+            (Some(natural), Some(minimum)) if minimum.row_count() < natural.row_count() => {
+                output += &minimum.to_string()
             }
-        } else {
-            output += &grid.fit_into_columns(1).to_string();
+            (Some(natural), _) => output += &natural.to_string(),
+            (None, Some(minimum)) => output += &minimum.to_string(),
+            //does not fit into grid, usually because (some) filename(s)
+            //are longer or almost as long as term_width
+            //print line by line instead!
+            (None, None) => output += &grid.fit_into_columns(1).to_string(),
         }
-    } else {
+    } else if separate_groups || date_groups {
+        for (row, line) in grid
+            .fit_into_columns(flags.blocks.0.len())
+            .to_string()
+            .lines()
+            .enumerate()
+        {
+            if let Some((_, header)) = headers.iter().find(|(index, _)| *index == row) {
+                output += header;
+                output.push('\n');
+            }
+            if !line.trim().is_empty() {
+                output += line;
+            }
+            output.push('\n');
+        }
+    } else if flags.layout != Layout::Pack {
         output += &grid.fit_into_columns(flags.blocks.0.len()).to_string();
     }
 
-    let should_display_folder_path = should_display_folder_path(depth, &metas, &flags);
-
-    // print the folder content
-    for meta in metas {
-        if meta.content.is_some() {
-            if should_display_folder_path {
-                output += &display_folder_path(&meta);
-            }
+    output
+}
 
-            let display_option = DisplayOption::Relative {
-                base_path: &meta.path,
-            };
+/// Pack the entries on as few lines as fit in the width, separated by commas like `ls -m`, or
+/// write them one per line when the width is unknown.
+fn pack(entries: &[String], term_width: Option<usize>) -> String {
+    let term_width = match term_width {
+        Some(term_width) => term_width,
+        None => return entries.iter().map(|entry| format!("{}\n", entry)).collect(),
+    };
 
-            output += &inner_display_grid(
-                &display_option,
-                meta.content.as_ref().unwrap(),
-                &flags,
-                colors,
-                icons,
-                depth + 1,
-                term_width,
-            );
+    let mut output = String::new();
+    let mut line_width = 0;
+    for (index, entry) in entries.iter().enumerate() {
+        let width = get_visible_width(entry);
+        if index > 0 {
+            // the comma ending the line is counted in the width
+            if line_width + 2 + width + 1 > term_width {
+                output += ",\n";
+                line_width = 0;
+            } else {
+                output += ", ";
+                line_width += 2;
+            }
         }
+        output += entry;
+        line_width += width;
+    }
+    if !entries.is_empty() {
+        output.push('\n');
     }
 
     output
 }
 
+/// Display the entries of a branch of the tree, `tree_depth_prefix` holding its depth, the
+/// prefix of its lines and the number of its entries left out by `--max-entries`.
 fn inner_display_tree(
     metas: &[Meta],
     flags: &Flags,
     colors: &Colors,
     icons: &Icons,
-    tree_depth_prefix: (usize, &str),
+    tree_depth_prefix: (usize, &str, usize),
     padding_rules: &HashMap<Block, usize>,
     tree_index: usize,
 ) -> Vec<Cell> {
     let mut cells = Vec::new();
-    let last_idx = metas.len();
+    let truncated = tree_depth_prefix.2;
+    // the notice of the truncated entries closes the branch instead of the last entry
+    let last_idx = metas.len() + if truncated > 0 { 1 } else { 0 };
 
     for (idx, meta) in metas.iter().enumerate() {
         let current_prefix = if tree_depth_prefix.0 > 0 {
@@ -215,18 +657,39 @@ fn inner_display_tree(
                 &flags,
                 colors,
                 icons,
-                (tree_depth_prefix.0 + 1, &new_prefix),
+                (tree_depth_prefix.0 + 1, &new_prefix, meta.truncated),
                 padding_rules,
                 tree_index,
             ));
         }
     }
 
+    if truncated > 0 {
+        for i in 0..flags.blocks.0.len() {
+            cells.push(Cell::from(if i == tree_index {
+                format!(
+                    "{}{} {}",
+                    tree_depth_prefix.1,
+                    CORNER,
+                    truncation_notice(truncated)
+                )
+            } else {
+                String::new()
+            }));
+        }
+    }
+
     cells
 }
 
-fn should_display_folder_path(depth: usize, metas: &[Meta], flags: &Flags) -> bool {
-    if depth > 0 {
+/// The notice of the entries of a directory left out by `--max-entries`.
+pub fn truncation_notice(truncated: usize) -> String {
+    format!("... and {} more", truncated)
+}
+
+pub fn should_display_folder_path(depth: usize, metas: &[Meta], flags: &Flags) -> bool {
+    // with the post order, the listing of a directory follows the ones of its subdirectories
+    if depth > 0 || (flags.recursion.enabled && flags.recursion.order == RecursionOrder::Post) {
         true
     } else {
         let folder_number = metas
@@ -242,7 +705,7 @@ fn should_display_folder_path(depth: usize, metas: &[Meta], flags: &Flags) -> bo
     }
 }
 
-fn display_folder_path(meta: &Meta) -> String {
+pub fn display_folder_path(meta: &Meta) -> String {
     let mut output = String::new();
     output.push('\n');
     output += &meta.path.to_string_lossy();
@@ -269,10 +732,28 @@ fn get_output<'a>(
         } else {
             Vec::new()
         };
+        let block_start = block_vec.len();
 
         match block {
             Block::INode => block_vec.push(meta.inode.render(colors)),
-            Block::Links => block_vec.push(meta.links.render(colors)),
+            Block::Links => block_vec.push(meta.links.render(colors, flags)),
+            Block::Capabilities => match &meta.capabilities {
+                Some(capabilities) => block_vec.push(capabilities.render(colors)),
+                None => block_vec.push(colors.colorize(String::from("-"), &Elem::NonFile)),
+            },
+            Block::LinkTargetInfo => match &meta.link_target_info {
+                Some(info) => block_vec.push(info.render(colors, flags)),
+                None => block_vec.push(colors.colorize(String::from("-"), &Elem::NonFile)),
+            },
+            Block::FsType => match &meta.fs_type {
+                Some(fs_type) => block_vec.push(fs_type.render(colors)),
+                None => block_vec.push(colors.colorize(String::from("-"), &Elem::NonFile)),
+            },
+            Block::FileFlags => block_vec.push(meta.file_flags.render(colors)),
+            Block::Children => match &meta.children {
+                Some(children) => block_vec.push(children.render(colors, flags)),
+                None => block_vec.push(colors.colorize(String::from("-"), &Elem::NonFile)),
+            },
             Block::Permission => {
                 block_vec.extend(vec![
                     meta.file_type.render(colors),
@@ -282,33 +763,117 @@ fn get_output<'a>(
             Block::User => block_vec.push(meta.owner.render_user(colors)),
             Block::Group => block_vec.push(meta.owner.render_group(colors)),
             Block::Size => {
-                let pad = if Layout::Tree == flags.layout && 0 == tree.0 && 0 == i {
+                let pad = if (Layout::Tree == flags.layout && 0 == tree.0 && 0 == i)
+                    || flags.block_align.get(&Block::Size).is_some()
+                {
                     None
                 } else {
                     Some(padding_rules[&Block::SizeValue])
                 };
-                block_vec.push(meta.size.render(colors, &flags, pad))
+                match meta.displayed_size(flags) {
+                    Some(size) => block_vec.push(size.render(colors, &flags, pad)),
+                    // aligned with the values of the sizes
+                    None => block_vec.push(
+                        colors.colorize(format!("{:>1$}", "-", pad.unwrap_or(0)), &Elem::NonFile),
+                    ),
+                }
+            }
+            Block::SizeValue => match meta.displayed_size(flags) {
+                Some(size) => block_vec.push(size.render_value(colors, flags)),
+                None => block_vec.push(colors.colorize(String::from("-"), &Elem::NonFile)),
+            },
+            Block::SizeDetail => {
+                if let (FileType::Directory { .. }, false) = (meta.file_type, flags.total_size.0) {
+                    block_vec.push(colors.colorize(String::from("-"), &Elem::NonFile))
+                } else {
+                    block_vec.push(meta.size.render_detail(colors, flags))
+                }
             }
-            Block::SizeValue => block_vec.push(meta.size.render_value(colors, flags)),
             Block::Date => block_vec.push(meta.date.render(colors, &flags)),
+            Block::Accessed => block_vec.push(meta.accessed.render_with(
+                colors,
+                &flags.date_accessed.0,
+                &flags.date_relative,
+            )),
             Block::Name => {
-                block_vec.extend(vec![
-                    meta.name.render(colors, icons, &display_option),
-                    meta.indicator.render(&flags),
-                ]);
-                if !(flags.no_symlink.0 || flags.dereference.0 || flags.layout == Layout::Grid) {
+                let target = match flags.symlink_display {
+                    SymlinkDisplay::Target => meta.symlink.render_target(colors, flags),
+                    _ => None,
+                };
+                if let Some(target) = target {
+                    block_vec.extend(vec![
+                        ANSIString::from(icons.get(&meta.name)),
+                        target,
+                        meta.indicator.render(flags),
+                    ]);
+                } else {
+                    block_vec.extend(vec![
+                        meta.name.render(colors, icons, &display_option, flags),
+                        meta.indicator.render(&flags),
+                    ]);
+                }
+                if let Some(ipc_status) = meta.ipc_status {
+                    block_vec.push(ipc_status.render(colors));
+                }
+                if meta.unreadable {
+                    block_vec.push(colors.colorize(String::from(" [unreadable]"), &Elem::NoAccess));
+                }
+                if !(flags.no_symlink.0
+                    || flags.dereference.0
+                    || matches!(flags.layout, Layout::Grid | Layout::Pack)
+                    || flags.symlink_display != SymlinkDisplay::Full)
+                {
                     block_vec.push(meta.symlink.render(colors, &flags))
                 }
             }
         };
+        if let Some(colour) = flags.block_colors.get(block) {
+            let rendered = ANSIStrings(&block_vec[block_start..]).to_string();
+            block_vec.truncate(block_start);
+            block_vec.push(colors.recolor(rendered, colour));
+        }
         strings.push(ColoredString::from(ANSIStrings(&block_vec).to_string()));
     }
     strings
 }
 
+/// Pad the cells of the blocks aligned to the right or the center with the `block-align` config,
+/// the cells being laid out a row per entry and a column per block.
+fn align_blocks(cells: &mut [Cell], flags: &Flags) {
+    let columns = flags.blocks.0.len();
+    for (column, block) in flags.blocks.0.iter().enumerate() {
+        let alignment = match flags.block_align.get(block) {
+            // the grid already pads the cells on the right
+            Some(Alignment::Left) | None => continue,
+            Some(alignment) => alignment,
+        };
+        let width = cells
+            .iter()
+            .skip(column)
+            .step_by(columns)
+            .map(|cell| cell.width)
+            .max()
+            .unwrap_or(0);
+        for cell in cells.iter_mut().skip(column).step_by(columns) {
+            cell.contents = alignment.pad(&cell.contents, cell.width, width);
+            cell.width = width;
+        }
+    }
+}
+
 fn get_visible_width(input: &str) -> usize {
     let mut nb_invisible_char = 0;
 
+    // If the input has a hyperlink, do not compute the length contributed by its delimiters,
+    // which are written as `ESC ] 8 ; ; <uri> ESC \` before and after the name
+    for (idx, _) in input.match_indices("\u{1b}]8;;") {
+        let (_, s) = input.split_at(idx);
+
+        if let Some(len) = s.find("\u{1b}\\") {
+            nb_invisible_char += UnicodeWidthStr::width(&s[..len + 2]);
+        }
+    }
+
     // If the input has color, do not compute the length contributed by the color to the actual length
     for (idx, _) in input.match_indices("\u{1b}[") {
         let (_, s) = input.split_at(idx);
@@ -326,7 +891,9 @@ fn detect_size_lengths(metas: &[Meta], flags: &Flags) -> usize {
     let mut max_value_length: usize = 0;
 
     for meta in metas {
-        let value_len = meta.size.value_string(flags).len();
+        let value_len = meta
+            .displayed_size(flags)
+            .map_or(0, |size| size.value_string(flags).len());
 
         if value_len > max_value_length {
             max_value_length = value_len;
@@ -362,10 +929,12 @@ mod tests {
     use super::*;
     use crate::color;
     use crate::color::Colors;
+    use crate::flags::HyperlinkOption;
     use crate::icon::Icons;
     use crate::meta::{FileType, Name};
     use crate::Config;
     use crate::{app, flags, icon, sort};
+    use ansi_term::Colour;
     use assert_fs::prelude::*;
     use std::path::Path;
 
@@ -392,6 +961,7 @@ mod tests {
                 &Colors::new(color::Theme::NoColor),
                 &Icons::new(icon::Theme::NoIcon, " ".to_string()),
                 &DisplayOption::FileName,
+                &Flags::default(),
             );
 
             assert_eq!(get_visible_width(&output), *l);
@@ -424,6 +994,7 @@ mod tests {
                     &Colors::new(color::Theme::NoColor),
                     &Icons::new(icon::Theme::Fancy, " ".to_string()),
                     &DisplayOption::FileName,
+                    &Flags::default(),
                 )
                 .to_string();
 
@@ -431,6 +1002,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_display_get_visible_width_with_hyperlinks() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        for (s, l) in &[("ASCII1234-_", 11), ("日本語", 6), ("File with space", 15)] {
+            dir.child(s).touch().unwrap();
+            let name = Name::new(
+                &dir.path().join(s),
+                FileType::File {
+                    exec: false,
+                    uid: false,
+                },
+            );
+            let output = name
+                .render(
+                    &Colors::new(color::Theme::Default),
+                    &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+                    &DisplayOption::FileName,
+                    &Flags {
+                        hyperlink: HyperlinkOption::Always,
+                        ..Flags::default()
+                    },
+                )
+                .to_string();
+
+            assert!(output.contains("\x1B]8;;file://"));
+            assert_eq!(get_visible_width(&output), *l);
+        }
+    }
+
     #[test]
     fn test_display_get_visible_width_with_colors() {
         for (s, l) in &[
@@ -456,6 +1056,7 @@ mod tests {
                     &Colors::new(color::Theme::NoLscolors),
                     &Icons::new(icon::Theme::NoIcon, " ".to_string()),
                     &DisplayOption::FileName,
+                    &Flags::default(),
                 )
                 .to_string();
 
@@ -492,6 +1093,7 @@ mod tests {
                     &Colors::new(color::Theme::NoColor),
                     &Icons::new(icon::Theme::NoIcon, " ".to_string()),
                     &DisplayOption::FileName,
+                    &Flags::default(),
                 )
                 .to_string();
 
@@ -503,7 +1105,7 @@ mod tests {
         }
     }
 
-    fn sort(metas: &mut Vec<Meta>, sorters: &Vec<(flags::SortOrder, sort::SortFn)>) {
+    fn sort(metas: &mut Vec<Meta>, sorters: &[sort::Sorter]) {
         metas.sort_unstable_by(|a, b| sort::by_meta(sorters, a, b));
 
         for meta in metas {
@@ -513,6 +1115,164 @@ mod tests {
         }
     }
 
+    fn display_grid_of_three(argv: Vec<&str>, term_width: usize) -> String {
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("aaa").touch().unwrap();
+        dir.child("bbb").touch().unwrap();
+        dir.child("ccc").touch().unwrap();
+        let mut metas = Meta::from_path(Path::new(dir.path()), false)
+            .unwrap()
+            .recurse_into(1, &flags)
+            .unwrap()
+            .unwrap();
+        sort(&mut metas, &sort::assemble_sorters(&flags));
+        inner_display_grid(
+            &DisplayOption::FileName,
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+            1,
+            Some(term_width),
+        )
+    }
+
+    #[test]
+    fn test_display_grid_queries_terminal_width() {
+        let matches = app::build().get_matches_from_safe(vec!["lsd"]).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("aaa").touch().unwrap();
+        dir.child("bbb").touch().unwrap();
+        dir.child("ccc").touch().unwrap();
+        let mut metas = Meta::from_path(Path::new(dir.path()), false)
+            .unwrap()
+            .recurse_into(1, &flags)
+            .unwrap()
+            .unwrap();
+        sort(&mut metas, &sort::assemble_sorters(&flags));
+
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let display = |width| {
+            let display_option = DisplayOption::Relative {
+                base_path: dir.path(),
+            };
+            grid_sized(&display_option, &metas, 1, &flags, &colors, &icons, || {
+                Some(Dimensions { width, height: 24 })
+            })
+        };
+
+        // the terminal is resized between the two listings
+        assert_eq!("aaa  bbb  ccc\n", display(15));
+        assert_eq!("aaa  ccc\nbbb  \n", display(10));
+    }
+
+    #[test]
+    fn test_display_grid_default_gap() {
+        assert_eq!("aaa  bbb  ccc\n", display_grid_of_three(vec!["lsd"], 15));
+    }
+
+    #[test]
+    fn test_display_grid_gap() {
+        assert_eq!(
+            "aaa    ccc\nbbb    \n",
+            display_grid_of_three(vec!["lsd", "--grid-gap", "4"], 15)
+        );
+    }
+
+    #[test]
+    fn test_display_grid_fill_down() {
+        assert_eq!("aaa  ccc\nbbb  \n", display_grid_of_three(vec!["lsd"], 10));
+    }
+
+    #[test]
+    fn test_display_grid_min_columns() {
+        assert_eq!(
+            "aaa  bbb  ccc\n",
+            display_grid_of_three(vec!["lsd", "--min-columns", "3"], 200)
+        );
+        // the natural packing is kept when the minimum columns do not fit
+        assert_eq!(
+            "aaa  ccc\nbbb  \n",
+            display_grid_of_three(vec!["lsd", "--min-columns", "3"], 10)
+        );
+    }
+
+    #[test]
+    fn test_display_grid_fill_across() {
+        assert_eq!(
+            "aaa  bbb\nccc  \n",
+            display_grid_of_three(vec!["lsd", "--across"], 10)
+        );
+    }
+
+    #[test]
+    fn test_display_pack() {
+        assert_eq!(
+            "aaa, bbb,\nccc\n",
+            display_grid_of_three(vec!["lsd", "--pack"], 12)
+        );
+        assert_eq!(
+            "aaa, bbb, ccc\n",
+            display_grid_of_three(vec!["lsd", "--pack"], 80)
+        );
+    }
+
+    #[test]
+    fn test_pack_without_width() {
+        let entries = vec!["aaa".to_string(), "bbb".to_string()];
+        assert_eq!("aaa\nbbb\n", pack(&entries, None));
+    }
+
+    #[test]
+    fn test_display_block_color_overrides_theme() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("file").write_str("content").unwrap();
+        let meta = Meta::from_path(&dir.path().join("file"), false).unwrap();
+
+        let argv = vec!["lsd", "--blocks", "size,name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let mut block_colors = HashMap::new();
+        block_colors.insert(Block::Size, Colour::Cyan);
+        let flags = Flags {
+            block_colors: flags::BlockColors(block_colors),
+            ..Flags::configure_from(&matches, &Config::with_none()).unwrap()
+        };
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let colors = Colors::new(color::Theme::NoLscolors);
+        let no_colors = Colors::new(color::Theme::NoColor);
+        let padding_rules = get_padding_rules(std::slice::from_ref(&meta), &flags);
+
+        let output = get_output(
+            &meta,
+            &colors,
+            &icons,
+            &flags,
+            &DisplayOption::FileName,
+            &padding_rules,
+            (0, ""),
+        );
+        assert_eq!(Colour::Cyan.paint("7 B").to_string(), output[0].to_string());
+        assert!(!output[1].to_string().contains("\u{1b}[36m"));
+
+        // The colors are still disabled without a theme
+        let output = get_output(
+            &meta,
+            &no_colors,
+            &icons,
+            &flags,
+            &DisplayOption::FileName,
+            &padding_rules,
+            (0, ""),
+        );
+        assert_eq!("7 B", output[0].to_string());
+    }
+
     #[test]
     fn test_display_tree_with_all() {
         let argv = vec!["lsd", "--tree", "--all"];
@@ -645,4 +1405,50 @@ mod tests {
 
         assert!(output.ends_with("└── two\n"));
     }
+
+    /// A writer recording its output in a shared buffer, to look at it while streaming.
+    struct SharedWriter<'a>(&'a std::cell::RefCell<Vec<u8>>);
+
+    impl Write for SharedWriter<'_> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_stream_writes_each_entry_before_reading_the_next() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let names = ["one", "two", "three"];
+        for name in &names {
+            dir.child(name).touch().unwrap();
+        }
+
+        let output = std::cell::RefCell::new(vec![]);
+        let mut written_before_read = vec![];
+        let metas = names.iter().map(|name| {
+            written_before_read.push(String::from_utf8_lossy(&output.borrow()).into_owned());
+            Meta::from_path(&dir.path().join(name), false).unwrap()
+        });
+
+        stream(
+            metas,
+            &DisplayOption::FileName,
+            &Flags::default(),
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+            &mut SharedWriter(&output),
+        )
+        .unwrap();
+
+        assert_eq!(vec!["", "one\n", "one\ntwo\n"], written_before_read);
+        assert_eq!(
+            "one\ntwo\nthree\n",
+            String::from_utf8_lossy(&output.borrow())
+        );
+    }
 }
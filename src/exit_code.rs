@@ -0,0 +1,30 @@
+//! This module keeps track of the exit code of the process. The errors encountered while listing
+//! are reported as they happen, and the most serious one sets the exit code at the end.
+
+use std::sync::atomic::{AtomicI32, Ordering};
+
+static EXIT_CODE: AtomicI32 = AtomicI32::new(0);
+
+/// The issues changing the exit code of the process, like GNU ls does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitCode {
+    /// Minor problems, eg: an entry of a directory could not be accessed.
+    MinorIssue = 1,
+    /// Serious trouble, eg: a path given as argument or a directory could not be read.
+    MajorIssue = 2,
+}
+
+impl ExitCode {
+    /// Raise the exit code of the process to this one, unless a more serious issue was reported.
+    pub fn set_if_greater(self) {
+        // The entries are listed by a single thread, so the code can not change in between.
+        if EXIT_CODE.load(Ordering::Relaxed) < self as i32 {
+            EXIT_CODE.store(self as i32, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The exit code the process should end with.
+pub fn get() -> i32 {
+    EXIT_CODE.load(Ordering::Relaxed)
+}
@@ -1,42 +1,120 @@
+pub mod across;
+pub mod block_align;
+pub mod block_colors;
 pub mod blocks;
+pub mod classic;
 pub mod color;
+pub mod compound_extensions;
+pub mod control_chars;
 pub mod date;
+pub mod date_groups;
 pub mod dereference;
 pub mod display;
+pub mod external_formatter;
+pub mod grid_gap;
+pub mod group_separators;
+pub mod hyperlink;
 pub mod icons;
 pub mod ignore_globs;
 pub mod indicators;
+pub mod ipc_status;
 pub mod layout;
+pub mod magic;
+pub mod max_entries;
+pub mod min_columns;
+pub mod name_colors;
+pub mod no_hidden_recurse;
+pub mod normalize;
+pub mod one_file_system;
+pub mod output;
+pub mod paging;
+pub mod quote_name;
+pub mod quoting_style;
 pub mod recursion;
+pub mod respect_gitattributes;
+pub mod set_title;
 pub mod size;
+pub mod sort_priority;
 pub mod sorting;
+pub mod stat;
+pub mod stream;
+pub mod summary;
 pub mod symlink_arrow;
+pub mod symlink_display;
+pub mod symlink_size;
+pub mod symlink_target;
 pub mod symlinks;
+pub mod thousands_separator;
+pub mod time_range;
 pub mod total_size;
+pub mod truncate;
+pub mod tty;
+pub mod verbose;
 
+pub use across::Across;
+pub use block_align::BlockAlign;
+pub use block_colors::BlockColors;
 pub use blocks::Block;
 pub use blocks::Blocks;
+pub use classic::ClassicOverrides;
 pub use color::Color;
 pub use color::ColorOption;
-pub use date::DateFlag;
+pub use color::ThemeOption;
+pub use compound_extensions::CompoundExtensions;
+pub use control_chars::ControlChars;
+pub use date::{AccessedDateFlag, DateFlag, RelativeDateFlag, RelativeUnit};
+pub use date_groups::DateGroups;
 pub use dereference::Dereference;
 pub use display::Display;
+pub use external_formatter::ExternalFormatter;
+pub use grid_gap::GridGap;
+pub use group_separators::GroupSeparators;
+pub use hyperlink::HyperlinkOption;
 pub use icons::IconOption;
+pub use icons::IconOverrides;
 pub use icons::IconSeparator;
 pub use icons::IconTheme;
 pub use icons::Icons;
+pub use icons::NerdFontVersion;
 pub use ignore_globs::IgnoreGlobs;
 pub use indicators::Indicators;
+pub use ipc_status::IpcStatusFlag;
 pub use layout::Layout;
-pub use recursion::Recursion;
+pub use layout::LayoutNonTty;
+pub use magic::Magic;
+pub use max_entries::MaxEntries;
+pub use min_columns::MinColumns;
+pub use name_colors::NameColors;
+pub use no_hidden_recurse::NoHiddenRecurse;
+pub use normalize::Normalize;
+pub use one_file_system::OneFileSystem;
+pub use output::Output;
+pub use paging::Paging;
+pub use quote_name::QuoteName;
+pub use quoting_style::QuotingStyle;
+pub use recursion::{Recursion, RecursionOrder};
+pub use respect_gitattributes::RespectGitattributes;
+pub use set_title::SetTitle;
 pub use size::SizeFlag;
+pub use sort_priority::SortPriority;
 pub use sorting::DirGrouping;
 pub use sorting::SortColumn;
 pub use sorting::SortOrder;
 pub use sorting::Sorting;
+pub use stat::Stat;
+pub use stream::Stream;
+pub use summary::Summary;
 pub use symlink_arrow::SymlinkArrow;
+pub use symlink_display::SymlinkDisplay;
+pub use symlink_size::SymlinkSize;
+pub use symlink_target::SymlinkTarget;
 pub use symlinks::NoSymlink;
+pub use thousands_separator::ThousandsSeparator;
+pub use time_range::TimeRange;
 pub use total_size::TotalSize;
+pub use truncate::Truncate;
+pub use tty::TtyOption;
+pub use verbose::Verbose;
 
 use crate::config_file::Config;
 
@@ -51,18 +129,58 @@ pub struct Flags {
     pub blocks: Blocks,
     pub color: Color,
     pub date: DateFlag,
+    pub date_accessed: AccessedDateFlag,
+    pub date_relative: RelativeDateFlag,
     pub dereference: Dereference,
     pub display: Display,
     pub display_indicators: Indicators,
     pub icons: Icons,
     pub ignore_globs: IgnoreGlobs,
     pub layout: Layout,
+    pub layout_non_tty: LayoutNonTty,
     pub no_symlink: NoSymlink,
     pub recursion: Recursion,
     pub size: SizeFlag,
     pub sorting: Sorting,
+    pub sort_priority: SortPriority,
     pub total_size: TotalSize,
     pub symlink_arrow: SymlinkArrow,
+    pub symlink_display: SymlinkDisplay,
+    pub no_hidden_recurse: NoHiddenRecurse,
+    pub group_separators: GroupSeparators,
+    pub grid_gap: GridGap,
+    pub output: Output,
+    pub ipc_status: IpcStatusFlag,
+    pub time_range: TimeRange,
+    pub stream: Stream,
+    pub across: Across,
+    pub paging: Paging,
+    pub hyperlink: HyperlinkOption,
+    pub tty: TtyOption,
+    pub block_colors: BlockColors,
+    pub truncate: Truncate,
+    pub date_groups: DateGroups,
+    pub respect_gitattributes: RespectGitattributes,
+    pub set_title: SetTitle,
+    pub verbose: Verbose,
+    pub classic_overrides: ClassicOverrides,
+    pub min_columns: MinColumns,
+    pub max_entries: MaxEntries,
+    pub stat: Stat,
+    pub name_colors: NameColors,
+    pub compound_extensions: CompoundExtensions,
+    pub quote_name: QuoteName,
+    pub one_file_system: OneFileSystem,
+    pub control_chars: ControlChars,
+    pub block_align: BlockAlign,
+    pub summary: Summary,
+    pub magic: Magic,
+    pub symlink_size: SymlinkSize,
+    pub symlink_target: SymlinkTarget,
+    pub quoting_style: QuotingStyle,
+    pub external_formatter: ExternalFormatter,
+    pub thousands_separator: ThousandsSeparator,
+    pub normalize: Normalize,
 }
 
 impl Flags {
@@ -77,9 +195,12 @@ impl Flags {
             blocks: Blocks::configure_from(matches, config)?,
             color: Color::configure_from(matches, config),
             date: DateFlag::configure_from(matches, config),
+            date_accessed: AccessedDateFlag::configure_from(matches, config),
+            date_relative: RelativeDateFlag::configure_from(matches, config),
             dereference: Dereference::configure_from(matches, config),
             display: Display::configure_from(matches, config),
             layout: Layout::configure_from(matches, config),
+            layout_non_tty: LayoutNonTty::configure_from(matches, config),
             size: SizeFlag::configure_from(matches, config),
             display_indicators: Indicators::configure_from(matches, config),
             icons: Icons::configure_from(matches, config),
@@ -87,8 +208,45 @@ impl Flags {
             no_symlink: NoSymlink::configure_from(matches, config),
             recursion: Recursion::configure_from(matches, config)?,
             sorting: Sorting::configure_from(matches, config),
+            sort_priority: SortPriority::configure_from(matches, config),
             total_size: TotalSize::configure_from(matches, config),
             symlink_arrow: SymlinkArrow::configure_from(matches, config),
+            symlink_display: SymlinkDisplay::configure_from(matches, config),
+            no_hidden_recurse: NoHiddenRecurse::configure_from(matches, config),
+            group_separators: GroupSeparators::configure_from(matches, config),
+            grid_gap: GridGap::configure_from(matches, config),
+            output: Output::configure_from(matches, config),
+            ipc_status: IpcStatusFlag::configure_from(matches, config),
+            time_range: TimeRange::configure_from(matches)?,
+            stream: Stream::configure_from(matches, config),
+            across: Across::configure_from(matches, config),
+            paging: Paging::configure_from(matches, config),
+            hyperlink: HyperlinkOption::configure_from(matches, config),
+            tty: TtyOption::configure_from(matches, config),
+            block_colors: BlockColors::configure_from(matches, config),
+            truncate: Truncate::configure_from(matches, config),
+            date_groups: DateGroups::configure_from(matches, config),
+            respect_gitattributes: RespectGitattributes::configure_from(matches, config),
+            set_title: SetTitle::configure_from(matches, config),
+            verbose: Verbose::configure_from(matches, config),
+            classic_overrides: ClassicOverrides::configure_from(matches, config),
+            min_columns: MinColumns::configure_from(matches, config),
+            max_entries: MaxEntries::configure_from(matches, config),
+            stat: Stat::configure_from(matches, config),
+            name_colors: NameColors::configure_from(matches, config),
+            compound_extensions: CompoundExtensions::configure_from(matches, config),
+            quote_name: QuoteName::configure_from(matches, config),
+            one_file_system: OneFileSystem::configure_from(matches, config),
+            control_chars: ControlChars::configure_from(matches, config),
+            block_align: BlockAlign::configure_from(matches, config),
+            summary: Summary::configure_from(matches, config),
+            magic: Magic::configure_from(matches, config),
+            symlink_size: SymlinkSize::configure_from(matches, config),
+            symlink_target: SymlinkTarget::configure_from(matches, config),
+            quoting_style: QuotingStyle::configure_from(matches, config),
+            external_formatter: ExternalFormatter::configure_from(matches, config),
+            thousands_separator: ThousandsSeparator::configure_from(matches, config),
+            normalize: Normalize::configure_from(matches, config),
         })
     }
 }
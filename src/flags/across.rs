@@ -0,0 +1,84 @@
+//! This module defines the [Across] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to fill the grid row by row instead of column by column.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Across(pub bool);
+
+impl Configurable<Self> for Across {
+    /// Get a potential `Across` value from [ArgMatches].
+    ///
+    /// If the "across" argument is passed, this returns a `Across` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("across") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Across` value from a [Config].
+    ///
+    /// If the `Config::across` has value,
+    /// this returns it as the value of the `Across`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.across.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Across;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Across::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--across"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Across(true)), Across::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_short() {
+        let argv = vec!["lsd", "-x"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Across(true)), Across::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Across::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.across = Some(true);
+        assert_eq!(Some(Across(true)), Across::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.across = Some(false);
+        assert_eq!(Some(Across(false)), Across::from_config(&c));
+    }
+}
@@ -0,0 +1,128 @@
+//! This module defines the [BlockAlign] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::{Block, Configurable};
+
+use crate::config_file::Config;
+use crate::print_error;
+
+use clap::ArgMatches;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// The alignment of the content of a block within its column.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    Right,
+    Center,
+}
+
+impl TryFrom<&str> for Alignment {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "center" => Ok(Self::Center),
+            _ => Err(format!("Not a valid alignment: {}", value)),
+        }
+    }
+}
+
+impl Alignment {
+    /// Pad a string of the given visible width with spaces up to the width of the column.
+    pub fn pad(self, string: &str, width: usize, column_width: usize) -> String {
+        let padding = column_width.saturating_sub(width);
+        let (left, right) = match self {
+            Self::Left => (0, padding),
+            Self::Right => (padding, 0),
+            Self::Center => (padding / 2, padding - padding / 2),
+        };
+        format!("{}{}{}", " ".repeat(left), string, " ".repeat(right))
+    }
+}
+
+/// The flag showing the alignments of the blocks in the long layout, which replace the default
+/// ones.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct BlockAlign(pub HashMap<Block, Alignment>);
+
+impl BlockAlign {
+    /// Get the alignment of a block, if it is overridden.
+    pub fn get(&self, block: &Block) -> Option<Alignment> {
+        self.0.get(block).copied()
+    }
+}
+
+impl Configurable<Self> for BlockAlign {
+    /// `BlockAlign` can not be configured by [ArgMatches]
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `BlockAlign` value from a [Config].
+    ///
+    /// If the `Config::block-align` has value, this returns the alignments of the valid block
+    /// names in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        let block_align = config.block_align.as_ref()?;
+        let mut alignments = HashMap::new();
+        for (name, value) in block_align {
+            match Block::try_from(name.as_str())
+                .and_then(|block| Ok((block, Alignment::try_from(value.as_str())?)))
+            {
+                Ok((block, alignment)) => {
+                    alignments.insert(block, alignment);
+                }
+                Err(err) => print_error!("{}.", err),
+            }
+        }
+        Some(Self(alignments))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Alignment, BlockAlign};
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::{Block, Configurable};
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, BlockAlign::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, BlockAlign::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let c: Config = serde_yaml::from_str(
+            "block-align:\n  size: left\n  user: center\n  name: top\n  nope: left",
+        )
+        .unwrap();
+        let alignments = BlockAlign::from_config(&c).unwrap();
+
+        assert_eq!(Some(Alignment::Left), alignments.get(&Block::Size));
+        assert_eq!(Some(Alignment::Center), alignments.get(&Block::User));
+        assert_eq!(2, alignments.0.len());
+    }
+
+    #[test]
+    fn test_pad() {
+        assert_eq!("ab   ", Alignment::Left.pad("ab", 2, 5));
+        assert_eq!("   ab", Alignment::Right.pad("ab", 2, 5));
+        assert_eq!(" ab  ", Alignment::Center.pad("ab", 2, 5));
+        assert_eq!("abc", Alignment::Right.pad("abc", 3, 2));
+    }
+}
@@ -0,0 +1,139 @@
+//! This module defines the [BlockColors] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::{Block, Configurable};
+
+use crate::config_file::Config;
+use crate::print_error;
+
+use ansi_term::Colour;
+use clap::ArgMatches;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// The flag showing the colors of the blocks, which replace the colors of the theme.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct BlockColors(pub HashMap<Block, Colour>);
+
+impl BlockColors {
+    /// Get the color of a block, if it is overridden.
+    pub fn get(&self, block: &Block) -> Option<Colour> {
+        self.0.get(block).copied()
+    }
+}
+
+/// Parse a color, either the name of one of the 8 basic colors, a number from 0 to 255 or an
+/// hexadecimal RGB value such as `#00afaf`.
+pub fn parse_colour(value: &str) -> Result<Colour, String> {
+    let colour = match value.to_lowercase().as_str() {
+        "black" => Colour::Black,
+        "red" => Colour::Red,
+        "green" => Colour::Green,
+        "yellow" => Colour::Yellow,
+        "blue" => Colour::Blue,
+        "purple" | "magenta" => Colour::Purple,
+        "cyan" => Colour::Cyan,
+        "white" => Colour::White,
+        hex if hex.starts_with('#') => {
+            let channel = |range| hex.get(range).map(|digits| u8::from_str_radix(digits, 16));
+            match (hex.len(), channel(1..3), channel(3..5), channel(5..7)) {
+                (7, Some(Ok(r)), Some(Ok(g)), Some(Ok(b))) => Colour::RGB(r, g, b),
+                _ => {
+                    return Err(format!(
+                        "Not a valid hexadecimal color: {}, expected #rrggbb",
+                        value
+                    ))
+                }
+            }
+        }
+        number => match number.parse() {
+            Ok(number) => Colour::Fixed(number),
+            Err(_) => return Err(format!("Not a valid color: {}", value)),
+        },
+    };
+    Ok(colour)
+}
+
+impl Configurable<Self> for BlockColors {
+    /// `BlockColors` can not be configured by [ArgMatches]
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `BlockColors` value from a [Config].
+    ///
+    /// If the `Config::block-colors` has value, this returns the colors of the valid block names
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        let block_colors = config.block_colors.as_ref()?;
+        let mut colors = HashMap::new();
+        for (name, value) in block_colors {
+            let block = match Block::try_from(name.as_str()) {
+                Ok(block) => block,
+                Err(err) => {
+                    print_error!("{}.", err);
+                    continue;
+                }
+            };
+            match parse_colour(value) {
+                Ok(colour) => {
+                    colors.insert(block, colour);
+                }
+                Err(err) => print_error!("{}, for the block '{}' in block-colors.", err, name),
+            }
+        }
+        Some(Self(colors))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_colour, BlockColors};
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::{Block, Configurable};
+
+    use ansi_term::Colour;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, BlockColors::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, BlockColors::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let c: Config =
+            serde_yaml::from_str("block-colors:\n  size: cyan\n  user: '214'\n  nope: red")
+                .unwrap();
+        let colors = BlockColors::from_config(&c).unwrap();
+
+        assert_eq!(Some(Colour::Cyan), colors.get(&Block::Size));
+        assert_eq!(Some(Colour::Fixed(214)), colors.get(&Block::User));
+        assert_eq!(2, colors.0.len());
+    }
+
+    #[test]
+    fn test_parse_colour() {
+        assert_eq!(Ok(Colour::Purple), parse_colour("Magenta"));
+        assert_eq!(Ok(Colour::Fixed(30)), parse_colour("30"));
+        assert_eq!(Ok(Colour::RGB(0, 175, 255)), parse_colour("#00afff"));
+        assert!(parse_colour("256").is_err());
+        assert!(parse_colour("#00afzz").is_err());
+        assert_eq!(
+            Err("Not a valid hexadecimal color: #0af, expected #rrggbb".to_string()),
+            parse_colour("#0af")
+        );
+        assert!(parse_colour("#00afé").is_err());
+        assert!(parse_colour("teal").is_err());
+    }
+}
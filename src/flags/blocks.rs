@@ -24,6 +24,9 @@ impl Blocks {
     /// `Blocks` does not contain a [Block] of variant [INode](Block::INode) yet, one is prepended
     /// to the returned value.
     ///
+    /// A warning is printed for every [Block] that is requested more than once, but the blocks
+    /// are still rendered as requested.
+    ///
     /// # Errors
     ///
     /// This errors if any of the [ArgMatches] parameter arguments causes [Block]'s implementation
@@ -45,6 +48,15 @@ impl Blocks {
             result = value;
         }
 
+        if let Ok(blocks) = result.as_ref() {
+            for block in blocks.duplicates() {
+                print_error!(
+                    "block '{}' is listed more than once, it is rendered every time.",
+                    block.name()
+                );
+            }
+        }
+
         if matches.is_present("inode") {
             if let Ok(blocks) = result.as_mut() {
                 blocks.optional_prepend_inode();
@@ -95,6 +107,10 @@ impl Blocks {
     /// Otherwise it returns [None].
     fn from_config(config: &Config) -> Option<Self> {
         if let Some(c) = &config.blocks {
+            if c.is_empty() {
+                print_error!("The blocks list of the configuration is empty, it is ignored.");
+                return None;
+            }
             let mut blocks: Vec<Block> = vec![];
             for b in c.iter() {
                 match Block::try_from(b.as_str()) {
@@ -127,6 +143,18 @@ impl Blocks {
         ])
     }
 
+    /// Returns every [Block] that appears more than once in `self`, in order of its first
+    /// repetition.
+    fn duplicates(&self) -> Vec<Block> {
+        let mut duplicates: Vec<Block> = vec![];
+        for (index, block) in self.0.iter().enumerate() {
+            if self.0[..index].contains(block) && !duplicates.contains(block) {
+                duplicates.push(*block);
+            }
+        }
+        duplicates
+    }
+
     /// Checks whether `self` already contains a [Block] of variant [INode](Block::INode).
     fn contains_inode(&self) -> bool {
         self.0.contains(&Block::INode)
@@ -161,28 +189,103 @@ pub enum Block {
     Group,
     Size,
     SizeValue,
+    SizeDetail,
     Date,
+    Accessed,
     Name,
     INode,
     Links,
+    Children,
+    LinkTargetInfo,
+    Capabilities,
+    FsType,
+    FileFlags,
+}
+
+impl Block {
+    /// Every [Block] variant, in the order they are listed to the user.
+    pub const ALL: [Block; 16] = [
+        Block::Permission,
+        Block::User,
+        Block::Group,
+        Block::Size,
+        Block::SizeValue,
+        Block::SizeDetail,
+        Block::Date,
+        Block::Accessed,
+        Block::Name,
+        Block::INode,
+        Block::Links,
+        Block::Children,
+        Block::LinkTargetInfo,
+        Block::Capabilities,
+        Block::FsType,
+        Block::FileFlags,
+    ];
+
+    /// The name used to select this block in the arguments and the configuration file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Permission => "permission",
+            Self::User => "user",
+            Self::Group => "group",
+            Self::Size => "size",
+            Self::SizeValue => "size_value",
+            Self::SizeDetail => "size_detail",
+            Self::Date => "date",
+            Self::Accessed => "accessed",
+            Self::Name => "name",
+            Self::INode => "inode",
+            Self::Links => "links",
+            Self::Children => "children",
+            Self::LinkTargetInfo => "link_target_info",
+            Self::Capabilities => "caps",
+            Self::FsType => "fstype",
+            Self::FileFlags => "flags",
+        }
+    }
+
+    /// A one-line description of what this block shows.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Permission => "File type and permissions",
+            Self::User => "Name of the owner",
+            Self::Group => "Name of the owner group",
+            Self::Size => "Size with its unit",
+            Self::SizeValue => "Size without its unit",
+            Self::SizeDetail => "Apparent and allocated size side by side",
+            Self::Date => "Date of the last modification",
+            Self::Accessed => "Date of the last access",
+            Self::Name => "File name, icon, indicator and symlink target",
+            Self::INode => "Index number",
+            Self::Links => "Number of hard links",
+            Self::Children => "Number of entries listed in a directory",
+            Self::LinkTargetInfo => "Type and size of the target of a symbolic link",
+            Self::Capabilities => "Capabilities of a file, on Linux",
+            Self::FsType => "Type of the filesystem mounted on a directory, on Linux",
+            Self::FileFlags => "Flags set with chflags, eg: uchg, on FreeBSD and macOS",
+        }
+    }
+
+    /// Lists every block with its description, one per line.
+    pub fn list() -> String {
+        let width = Self::ALL.iter().map(|b| b.name().len()).max().unwrap_or(0);
+        Self::ALL
+            .iter()
+            .map(|b| format!("{:width$}  {}\n", b.name(), b.description(), width = width))
+            .collect()
+    }
 }
 
 impl TryFrom<&str> for Block {
     type Error = String;
 
     fn try_from(string: &str) -> Result<Self, Self::Error> {
-        match string {
-            "permission" => Ok(Self::Permission),
-            "user" => Ok(Self::User),
-            "group" => Ok(Self::Group),
-            "size" => Ok(Self::Size),
-            "size_value" => Ok(Self::SizeValue),
-            "date" => Ok(Self::Date),
-            "name" => Ok(Self::Name),
-            "inode" => Ok(Self::INode),
-            "links" => Ok(Self::Links),
-            _ => Err(format!("Not a valid block name: {}", &string)),
-        }
+        Self::ALL
+            .iter()
+            .find(|block| block.name() == string)
+            .copied()
+            .ok_or_else(|| format!("Not a valid block name: {}", &string))
     }
 }
 
@@ -446,6 +549,38 @@ mod test_blocks {
         let blocks = Blocks(vec![Block::Permission, Block::Date]);
         assert_eq!(Some(blocks), Blocks::from_config(&c));
     }
+
+    #[test]
+    fn test_from_config_empty_is_ignored() {
+        let mut c = Config::with_none();
+        c.blocks = Some(vec![]);
+        assert_eq!(None, Blocks::from_config(&c));
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let blocks = Blocks(vec![
+            Block::Name,
+            Block::Size,
+            Block::Name,
+            Block::Date,
+            Block::Size,
+            Block::Name,
+        ]);
+        assert_eq!(vec![Block::Name, Block::Size], blocks.duplicates());
+        assert!(Blocks::long().duplicates().is_empty());
+    }
+
+    #[test]
+    fn test_configure_from_keeps_duplicates() {
+        let argv = vec!["lsd", "--blocks", "name,name"];
+        let target = Ok::<_, Error>(Blocks(vec![Block::Name, Block::Name]));
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+    }
 }
 
 #[cfg(test)]
@@ -487,6 +622,11 @@ mod test_block {
         assert_eq!(Ok(Block::SizeValue), Block::try_from("size_value"));
     }
 
+    #[test]
+    fn test_size_detail() {
+        assert_eq!(Ok(Block::SizeDetail), Block::try_from("size_detail"));
+    }
+
     #[test]
     fn test_date() {
         assert_eq!(Ok(Block::Date), Block::try_from("date"));
@@ -506,4 +646,49 @@ mod test_block {
     fn test_links() {
         assert_eq!(Ok(Block::Links), Block::try_from("links"));
     }
+
+    #[test]
+    fn test_children() {
+        assert_eq!(Ok(Block::Children), Block::try_from("children"));
+    }
+
+    #[test]
+    fn test_caps() {
+        assert_eq!(Ok(Block::Capabilities), Block::try_from("caps"));
+    }
+
+    #[test]
+    fn test_fstype() {
+        assert_eq!(Ok(Block::FsType), Block::try_from("fstype"));
+    }
+
+    #[test]
+    fn test_link_target_info() {
+        assert_eq!(
+            Ok(Block::LinkTargetInfo),
+            Block::try_from("link_target_info")
+        );
+        assert!(Block::try_from("link-target-info").is_err());
+    }
+
+    #[test]
+    fn test_accessed() {
+        assert_eq!(Ok(Block::Accessed), Block::try_from("accessed"));
+    }
+
+    #[test]
+    fn test_all_names_round_trip() {
+        for block in Block::ALL.iter() {
+            assert_eq!(Ok(*block), Block::try_from(block.name()));
+        }
+    }
+
+    #[test]
+    fn test_list_contains_all() {
+        let list = Block::list();
+        assert_eq!(Block::ALL.len(), list.lines().count());
+        for block in Block::ALL.iter() {
+            assert!(list.contains(block.name()));
+        }
+    }
 }
@@ -0,0 +1,147 @@
+//! This module defines the [ClassicOverrides]. To set it up from [ArgMatches] and a [Config], use
+//! its [configure_from](ClassicOverrides::configure_from) method.
+
+use crate::config_file::{self, Config};
+
+use clap::ArgMatches;
+use std::fmt::Debug;
+
+/// The options given in the arguments or in the configuration file which the classic mode
+/// overrides, described to explain them with `--verbose`.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ClassicOverrides(pub Vec<String>);
+
+impl ClassicOverrides {
+    /// Get the options overridden by the classic mode, when it is enabled.
+    ///
+    /// The "classic" argument overrides the options of both the arguments and the [Config],
+    /// while the `Config::classic` only overrides the ones of the [Config]. The options already
+    /// set to the value of the classic mode are not listed.
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
+        let classic_arg = matches.is_present("classic");
+        if !classic_arg && config.classic != Some(true) {
+            return Self::default();
+        }
+
+        let name = |value: &dyn Debug| format!("{:?}", value).to_lowercase();
+        let date = match &config.date {
+            Some(config_file::Date::Format(date)) => Some(date.clone()),
+            Some(config_file::Date::PerBlock(formats)) => formats.modified.clone(),
+            None => None,
+        };
+        // The arguments with their key and value in the config, and the value of the classic mode
+        let options = [
+            (
+                "color",
+                "color.when",
+                config.color.as_ref().map(|color| name(&color.when)),
+                "never",
+            ),
+            ("date", "date", date, "date"),
+            (
+                "icon",
+                "icons.when",
+                config
+                    .icons
+                    .as_ref()
+                    .and_then(|icons| icons.when)
+                    .map(|when| name(&when)),
+                "never",
+            ),
+            ("size", "size", config.size.map(|size| name(&size)), "bytes"),
+            (
+                "group-dirs",
+                "sorting.dir-grouping",
+                config
+                    .sorting
+                    .as_ref()
+                    .and_then(|sorting| sorting.dir_grouping)
+                    .map(|dir_grouping| name(&dir_grouping)),
+                "none",
+            ),
+        ];
+
+        let mut overrides = vec![];
+        for (arg, key, config_value, classic) in options.iter() {
+            let overridden = if matches.occurrences_of(arg) > 0 {
+                match matches
+                    .values_of(arg)
+                    .and_then(|mut values| values.next_back())
+                {
+                    Some(value) if classic_arg && value != *classic => {
+                        Some(format!("--{} {}", arg, value))
+                    }
+                    _ => None,
+                }
+            } else {
+                match config_value {
+                    Some(value) if value != classic => {
+                        Some(format!("{}: {} of the configuration file", key, value))
+                    }
+                    _ => None,
+                }
+            };
+            if let Some(overridden) = overridden {
+                overrides.push(format!(
+                    "the classic mode overrides {} with {}",
+                    overridden, classic
+                ));
+            }
+        }
+        Self(overrides)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ClassicOverrides;
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    #[test]
+    fn test_configure_from_without_classic() {
+        let argv = vec!["lsd", "--group-dirs", "first"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            ClassicOverrides::default(),
+            ClassicOverrides::configure_from(&matches, &Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_configure_from_classic_arg() {
+        let argv = vec![
+            "lsd",
+            "--classic",
+            "--group-dirs",
+            "first",
+            "--color",
+            "never",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let c: Config = serde_yaml::from_str("icons:\n  when: always\nsize: short").unwrap();
+
+        assert_eq!(
+            vec![
+                "the classic mode overrides icons.when: always of the configuration file with never",
+                "the classic mode overrides size: short of the configuration file with bytes",
+                "the classic mode overrides --group-dirs first with none",
+            ],
+            ClassicOverrides::configure_from(&matches, &c).0
+        );
+    }
+
+    #[test]
+    fn test_configure_from_classic_config() {
+        let argv = vec!["lsd", "--group-dirs", "first"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let c: Config = serde_yaml::from_str("classic: true\ndate: relative").unwrap();
+
+        // The arguments take precedence over the classic mode of the configuration file.
+        assert_eq!(
+            vec!["the classic mode overrides date: relative of the configuration file with date"],
+            ClassicOverrides::configure_from(&matches, &c).0
+        );
+    }
+}
@@ -3,26 +3,32 @@
 
 use super::Configurable;
 
+use crate::color::ThemeColors;
 use crate::config_file::Config;
 use crate::print_error;
 
 use clap::ArgMatches;
 use serde::Deserialize;
+use std::path::Path;
 
 /// A collection of flags on how to use colors.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, Copy, PartialEq, Default)]
 pub struct Color {
     /// When to use color.
     pub when: ColorOption,
+    /// Which color theme to use.
+    pub theme: ThemeOption,
 }
 
 impl Color {
     /// Get a `Color` struct from [ArgMatches], a [Config] or the [Default] values.
     ///
-    /// The [ColorOption] is configured with their respective [Configurable] implementation.
+    /// The [ColorOption] and [ThemeOption] are configured with their respective [Configurable]
+    /// implementation.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
         let when = ColorOption::configure_from(matches, config);
-        Self { when }
+        let theme = ThemeOption::configure_from(matches, config);
+        Self { when, theme }
     }
 }
 
@@ -30,18 +36,24 @@ impl Color {
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ColorOption {
+    #[serde(alias = "yes", alias = "force")]
     Always,
+    #[serde(alias = "tty", alias = "if-tty")]
     Auto,
+    #[serde(alias = "no")]
     Never,
 }
 
 impl ColorOption {
     /// Get a Color value from a [String].
+    ///
+    /// Besides "always", "auto" and "never", the synonyms used by other tools are accepted:
+    /// "yes" and "force" for always, "tty" and "if-tty" for auto, and "no" for never.
     fn from_str(value: &str) -> Option<Self> {
         match value {
-            "always" => Some(Self::Always),
-            "auto" => Some(Self::Auto),
-            "never" => Some(Self::Never),
+            "always" | "yes" | "force" => Some(Self::Always),
+            "auto" | "tty" | "if-tty" => Some(Self::Auto),
+            "never" | "no" => Some(Self::Never),
             _ => {
                 print_error!(
                     "Config color.when could only be one of auto, always and never, got {}.",
@@ -98,6 +110,75 @@ impl Default for ColorOption {
     }
 }
 
+/// The flag showing which color theme to use, a built-in one or the one of a theme file.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum ThemeOption {
+    Default,
+    Nord,
+    Gruvbox,
+    SolarizedDark,
+    /// The colors read from a theme file.
+    File(ThemeColors),
+}
+
+impl ThemeOption {
+    /// Get a `ThemeOption` from the name of a built-in theme or the path of a theme file, which
+    /// is read right away. An unknown name or a theme file which can not be read or parsed is
+    /// reported, and this returns [None].
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "default" => Some(Self::Default),
+            "nord" => Some(Self::Nord),
+            "gruvbox" => Some(Self::Gruvbox),
+            "solarized-dark" => Some(Self::SolarizedDark),
+            path if Path::new(path).is_file() => match ThemeColors::from_file(Path::new(path)) {
+                Ok(colors) => Some(Self::File(colors)),
+                Err(err) => {
+                    print_error!("{}.", err);
+                    None
+                }
+            },
+            _ => {
+                print_error!(
+                    "Config color.theme could only be one of default, nord, gruvbox, solarized-dark or the path of a theme file, got {}.",
+                    value
+                );
+                None
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for ThemeOption {
+    /// Get a potential `ThemeOption` variant from [ArgMatches].
+    ///
+    /// If the argument is passed, this returns the variant corresponding to its parameter in a
+    /// [Some], unless it is a theme file which can not be read. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("color-theme") > 0 {
+            Self::from_str(matches.values_of("color-theme")?.next_back()?)
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `ThemeOption` variant from a [Config].
+    ///
+    /// If the `Config::color::theme` has value and is one of "default", "nord", "gruvbox",
+    /// "solarized-dark" or the path of a theme file which can be read, this returns its
+    /// corresponding variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        Self::from_str(config.color.as_ref()?.theme.as_ref()?)
+    }
+}
+
+/// The default value for `ThemeOption` is [ThemeOption::Default].
+impl Default for ThemeOption {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
 #[cfg(test)]
 mod test_color_option {
     use super::ColorOption;
@@ -143,6 +224,35 @@ mod test_color_option {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_synonyms() {
+        for (value, expected) in &[
+            ("yes", ColorOption::Always),
+            ("force", ColorOption::Always),
+            ("tty", ColorOption::Auto),
+            ("if-tty", ColorOption::Auto),
+            ("no", ColorOption::Never),
+        ] {
+            let argv = vec!["lsd", "--color", value];
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            assert_eq!(Some(*expected), ColorOption::from_arg_matches(&matches));
+        }
+    }
+
+    #[test]
+    fn test_from_config_synonyms() {
+        for (value, expected) in &[
+            ("yes", ColorOption::Always),
+            ("force", ColorOption::Always),
+            ("tty", ColorOption::Auto),
+            ("if-tty", ColorOption::Auto),
+            ("no", ColorOption::Never),
+        ] {
+            let c: Config = serde_yaml::from_str(&format!("color:\n  when: {}", value)).unwrap();
+            assert_eq!(Some(*expected), ColorOption::from_config(&c));
+        }
+    }
+
     #[test]
     fn test_from_arg_matches_classic_mode() {
         let argv = vec!["lsd", "--color", "always", "--classic"];
@@ -173,6 +283,7 @@ mod test_color_option {
         let mut c = Config::with_none();
         c.color = Some(config_file::Color {
             when: ColorOption::Always,
+            theme: None,
         });
 
         assert_eq!(Some(ColorOption::Always), ColorOption::from_config(&c));
@@ -183,6 +294,7 @@ mod test_color_option {
         let mut c = Config::with_none();
         c.color = Some(config_file::Color {
             when: ColorOption::Auto,
+            theme: None,
         });
         assert_eq!(Some(ColorOption::Auto), ColorOption::from_config(&c));
     }
@@ -192,6 +304,7 @@ mod test_color_option {
         let mut c = Config::with_none();
         c.color = Some(config_file::Color {
             when: ColorOption::Never,
+            theme: None,
         });
         assert_eq!(Some(ColorOption::Never), ColorOption::from_config(&c));
     }
@@ -201,8 +314,101 @@ mod test_color_option {
         let mut c = Config::with_none();
         c.color = Some(config_file::Color {
             when: ColorOption::Always,
+            theme: None,
         });
         c.classic = Some(true);
         assert_eq!(Some(ColorOption::Never), ColorOption::from_config(&c));
     }
 }
+
+#[cfg(test)]
+mod test_theme_option {
+    use super::ThemeOption;
+
+    use crate::app;
+    use crate::color::ThemeColors;
+    use crate::config_file::{self, Config};
+    use crate::flags::{ColorOption, Configurable};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ThemeOption::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_nord() {
+        let argv = vec!["lsd", "--color-theme", "nord"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ThemeOption::Nord),
+            ThemeOption::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_theme_alias() {
+        let argv = vec!["lsd", "--theme", "solarized-dark"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ThemeOption::SolarizedDark),
+            ThemeOption::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_unknown() {
+        let argv = vec!["lsd", "--theme", "unknown"];
+        let err = app::build().get_matches_from_safe(argv).unwrap_err();
+        assert!(err
+            .message
+            .contains("default, nord, gruvbox and solarized-dark"));
+    }
+
+    #[test]
+    fn test_from_arg_matches_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("theme.yaml");
+        fs::write(&path, "dir: '#81a1c1'\nuser: '214'\n").expect("failed to write file");
+        let argv = vec!["lsd", "--color-theme", path.to_str().unwrap()];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+
+        match ThemeOption::from_arg_matches(&matches) {
+            Some(ThemeOption::File(colors)) => {
+                assert_eq!(colors, ThemeColors::from_file(&path).unwrap(),)
+            }
+            theme => panic!("unexpected theme {:?}", theme),
+        }
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("theme.yaml");
+        fs::write(&path, "dir: teal\n").expect("failed to write file");
+        let argv = vec!["lsd", "--color-theme", path.to_str().unwrap()];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ThemeOption::from_arg_matches(&matches));
+
+        fs::write(&path, "dir: [").expect("failed to write file");
+        assert_eq!(None, ThemeOption::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ThemeOption::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_gruvbox() {
+        let mut c = Config::with_none();
+        c.color = Some(config_file::Color {
+            when: ColorOption::Always,
+            theme: Some("gruvbox".into()),
+        });
+        assert_eq!(Some(ThemeOption::Gruvbox), ThemeOption::from_config(&c));
+    }
+}
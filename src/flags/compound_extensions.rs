@@ -0,0 +1,87 @@
+//! This module defines the [CompoundExtensions] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to treat the known compound extensions, like `tar.gz`, as a single
+/// extension when sorting and looking up the icons.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct CompoundExtensions(pub bool);
+
+impl Configurable<Self> for CompoundExtensions {
+    /// Get a potential `CompoundExtensions` value from [ArgMatches].
+    ///
+    /// If the "compound-extensions" argument is passed, this returns a `CompoundExtensions` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("compound-extensions") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `CompoundExtensions` value from a [Config].
+    ///
+    /// If the `Config::compound-extensions` has value,
+    /// this returns it as the value of the `CompoundExtensions`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.compound_extensions.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CompoundExtensions;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, CompoundExtensions::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--compound-extensions"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(CompoundExtensions(true)),
+            CompoundExtensions::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, CompoundExtensions::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.compound_extensions = Some(true);
+        assert_eq!(
+            Some(CompoundExtensions(true)),
+            CompoundExtensions::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.compound_extensions = Some(false);
+        assert_eq!(
+            Some(CompoundExtensions(false)),
+            CompoundExtensions::from_config(&c)
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! This module defines the [ControlChars] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing how to print the control characters of the names.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum ControlChars {
+    /// The variant to hide the control characters if the output is a tty, or to escape them
+    /// otherwise.
+    Auto,
+    /// The variant to print each control character as a `?`.
+    Hide,
+    /// The variant to print the control characters escaped, like `\n`.
+    Escape,
+    /// The variant to print the control characters as they are.
+    Show,
+}
+
+impl ControlChars {
+    /// Resolve [ControlChars::Auto] to [ControlChars::Hide] if the output is a tty, like GNU ls,
+    /// or to [ControlChars::Escape] otherwise.
+    pub fn resolve(self, tty_available: bool) -> Self {
+        match self {
+            Self::Auto if tty_available => Self::Hide,
+            Self::Auto => Self::Escape,
+            control_chars => control_chars,
+        }
+    }
+}
+
+impl Configurable<Self> for ControlChars {
+    /// Get a potential `ControlChars` variant from [ArgMatches].
+    ///
+    /// If the "show-control-chars" argument is passed, this returns [ControlChars::Show] in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("show-control-chars") {
+            Some(Self::Show)
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `ControlChars` variant from a [Config].
+    ///
+    /// If the `Config::show-control-chars` has value, this returns [ControlChars::Show] for
+    /// `true` and [ControlChars::Auto] for `false`, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .show_control_chars
+            .map(|show| if show { Self::Show } else { Self::Auto })
+    }
+}
+
+/// The default value for `ControlChars` is [ControlChars::Auto].
+impl Default for ControlChars {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ControlChars;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ControlChars::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_show() {
+        let argv = vec!["lsd", "--show-control-chars"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ControlChars::Show),
+            ControlChars::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ControlChars::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.show_control_chars = Some(true);
+        assert_eq!(Some(ControlChars::Show), ControlChars::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.show_control_chars = Some(false);
+        assert_eq!(Some(ControlChars::Auto), ControlChars::from_config(&c));
+    }
+
+    #[test]
+    fn test_resolve_auto() {
+        assert_eq!(ControlChars::Hide, ControlChars::Auto.resolve(true));
+        assert_eq!(ControlChars::Escape, ControlChars::Auto.resolve(false));
+        assert_eq!(ControlChars::Show, ControlChars::Show.resolve(true));
+    }
+}
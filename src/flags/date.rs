@@ -3,41 +3,58 @@
 
 use super::Configurable;
 
-use crate::app;
-use crate::config_file::Config;
+use crate::config_file::{self, Config};
 use crate::print_error;
 
 use clap::ArgMatches;
+use serde::Deserialize;
 
 /// The flag showing which kind of time stamps to display.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DateFlag {
     Date,
     Relative,
+    /// Like [DateFlag::Relative], but "today" and "yesterday" for the dates of these calendar
+    /// days.
+    RelativeSmart,
     ISO,
     Formatted(String),
 }
 
 impl DateFlag {
-    /// Get a value from a date format string
-    fn from_format_string(value: &str) -> Option<Self> {
-        match app::validate_time_format(&value) {
-            Ok(()) => Some(Self::Formatted(value[1..].to_string())),
-            _ => {
-                print_error!("Not a valid date format: {}.", value);
-                None
-            }
+    /// Get a value from a str, or the error to report when it is not a valid date value.
+    ///
+    /// A date-time format is checked here once, so that an invalid one is reported at startup
+    /// instead of failing for every listed entry.
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "date" => Ok(Self::Date),
+            "relative" => Ok(Self::Relative),
+            "relative-smart" => Ok(Self::RelativeSmart),
+            _ if value.starts_with('+') => match Self::validate_format(&value[1..]) {
+                Ok(()) => Ok(Self::Formatted(value[1..].to_string())),
+                Err(err) => Err(format!("Not a valid date format: {}, {}.", value, err)),
+            },
+            _ => Err(format!("Not a valid date value: {}.", value)),
         }
     }
 
-    /// Get a value from a str.
+    /// Check a date-time format by formatting a sample date with it, as an invalid one is only
+    /// found out when formatting.
+    fn validate_format(format: &str) -> Result<(), String> {
+        use std::fmt::Write;
+
+        let mut sample = String::new();
+        write!(sample, "{}", chrono::Local::now().format(format))
+            .map_err(|_| format!("invalid date-time format: {}", format))
+    }
+
+    /// Get a value from a str, printing an error when it is not a valid date value.
     fn from_str(value: &str) -> Option<Self> {
-        match value {
-            "date" => Some(Self::Date),
-            "relative" => Some(Self::Relative),
-            _ if value.starts_with('+') => Self::from_format_string(&value),
-            _ => {
-                print_error!("Not a valid date value: {}.", value);
+        match Self::parse(value) {
+            Ok(date) => Some(date),
+            Err(err) => {
+                print_error!("{}", err);
                 None
             }
         }
@@ -57,6 +74,7 @@ impl Configurable<Self> for DateFlag {
             match matches.values_of("date")?.last() {
                 Some("date") => Some(Self::Date),
                 Some("relative") => Some(Self::Relative),
+                Some("relative-smart") => Some(Self::RelativeSmart),
                 Some(format) if format.starts_with('+') => {
                     Some(Self::Formatted(format[1..].to_owned()))
                 }
@@ -70,18 +88,20 @@ impl Configurable<Self> for DateFlag {
     /// Get a potential `DateFlag` variant from a [Config].
     ///
     /// If the `Config::classic` is `true` then this returns the Some(DateFlag::Date),
-    /// Otherwise if the `Config::date` has value and is one of "date" or "relative",
-    /// this returns its corresponding variant in a [Some].
+    /// Otherwise if the `Config::date` (or its `modified` format) has value and is one of "date",
+    /// "relative" or "relative-smart", this returns its corresponding variant in a [Some].
     /// Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
         if let Some(true) = &config.classic {
             return Some(Self::Date);
         }
 
-        if let Some(date) = &config.date {
-            Self::from_str(&date)
-        } else {
-            None
+        match &config.date {
+            Some(config_file::Date::Format(date)) => Self::from_str(date),
+            Some(config_file::Date::PerBlock(formats)) => {
+                Self::from_str(formats.modified.as_ref()?)
+            }
+            None => None,
         }
     }
 
@@ -92,7 +112,7 @@ impl Configurable<Self> for DateFlag {
                 "full-iso" => Some(Self::Formatted("%F %T.%f %z".into())),
                 "long-iso" => Some(Self::Formatted("%F %R".into())),
                 "iso" => Some(Self::ISO),
-                _ if value.starts_with('+') => Self::from_format_string(&value),
+                _ if value.starts_with('+') => Self::from_str(&value),
                 _ => {
                     print_error!("Not a valid date value: {}.", value);
                     None
@@ -111,12 +131,108 @@ impl Default for DateFlag {
     }
 }
 
+/// The flag showing which kind of time stamps to display in the accessed date column.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct AccessedDateFlag(pub DateFlag);
+
+impl Configurable<Self> for AccessedDateFlag {
+    /// Get a potential `AccessedDateFlag` from [ArgMatches].
+    ///
+    /// The "date" argument applies to every date column, so this returns the same value as
+    /// [DateFlag::from_arg_matches].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        DateFlag::from_arg_matches(matches).map(Self)
+    }
+
+    /// Get a potential `AccessedDateFlag` from a [Config].
+    ///
+    /// If the `Config::classic` is `true` then this returns the Some(DateFlag::Date),
+    /// Otherwise if the `Config::date` is a single format, or has an `accessed` format, this
+    /// returns its corresponding variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(true) = &config.classic {
+            return Some(Self(DateFlag::Date));
+        }
+
+        match &config.date {
+            // The modified date column reports an invalid single format already.
+            Some(config_file::Date::Format(date)) => DateFlag::parse(date).ok().map(Self),
+            Some(config_file::Date::PerBlock(formats)) => {
+                DateFlag::from_str(formats.accessed.as_ref()?).map(Self)
+            }
+            None => None,
+        }
+    }
+
+    /// Get a potential `AccessedDateFlag` from the environment.
+    fn from_environment() -> Option<Self> {
+        DateFlag::from_environment().map(Self)
+    }
+}
+
+/// The units the relative dates are counted in, from the smallest to the largest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RelativeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// The flag showing how to count the relative dates.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct RelativeDateFlag {
+    /// The largest unit the dates are counted in, eg: "400 days ago" instead of "a year ago"
+    /// when it is [RelativeUnit::Day].
+    pub max_unit: Option<RelativeUnit>,
+    /// Whether to count the dates in two units, eg: "1h 5m ago".
+    pub compound: bool,
+}
+
+impl RelativeDateFlag {
+    /// Whether the relative dates are counted as by default, in a single unit rounded to the
+    /// most meaningful one, eg: "an hour ago".
+    pub fn is_default(&self) -> bool {
+        self.max_unit.is_none() && !self.compound
+    }
+}
+
+impl Configurable<Self> for RelativeDateFlag {
+    /// `RelativeDateFlag` can not be configured by [ArgMatches]
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `RelativeDateFlag` from a [Config].
+    ///
+    /// If the `Config::date` has a `relative` section, this returns its values in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        match &config.date {
+            Some(config_file::Date::PerBlock(formats)) => {
+                let relative = formats.relative.as_ref()?;
+                Some(Self {
+                    max_unit: relative.max_unit,
+                    compound: relative.compound.unwrap_or(false),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::DateFlag;
+    use super::{AccessedDateFlag, DateFlag, RelativeDateFlag, RelativeUnit};
 
     use crate::app;
-    use crate::config_file::Config;
+    use crate::config_file::{self, Config};
     use crate::flags::Configurable;
 
     #[test]
@@ -143,6 +259,16 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_relative_smart() {
+        let argv = vec!["lsd", "--date", "relative-smart"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateFlag::RelativeSmart),
+            DateFlag::from_arg_matches(&matches)
+        );
+    }
+
     #[test]
     fn test_from_arg_matches_format() {
         let argv = vec!["lsd", "--date", "+%F"];
@@ -212,6 +338,46 @@ mod test {
         assert_eq!(None, DateFlag::from_config(&c));
     }
 
+    #[test]
+    fn test_from_config_per_block() {
+        let mut c = Config::with_none();
+        c.date = Some(config_file::Date::PerBlock(config_file::DateFormats {
+            modified: Some("+%F".into()),
+            accessed: Some("+%T".into()),
+            relative: None,
+        }));
+        assert_eq!(
+            Some(DateFlag::Formatted("%F".into())),
+            DateFlag::from_config(&c)
+        );
+        assert_eq!(
+            Some(AccessedDateFlag(DateFlag::Formatted("%T".into()))),
+            AccessedDateFlag::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_per_block_unspecified() {
+        let mut c = Config::with_none();
+        c.date = Some(config_file::Date::PerBlock(config_file::DateFormats {
+            modified: Some("relative".into()),
+            accessed: None,
+            relative: None,
+        }));
+        assert_eq!(Some(DateFlag::Relative), DateFlag::from_config(&c));
+        assert_eq!(None, AccessedDateFlag::from_config(&c));
+    }
+
+    #[test]
+    fn test_accessed_from_config_single_format() {
+        let mut c = Config::with_none();
+        c.date = Some("relative".into());
+        assert_eq!(
+            Some(AccessedDateFlag(DateFlag::Relative)),
+            AccessedDateFlag::from_config(&c)
+        );
+    }
+
     #[test]
     fn test_from_config_classic_mode() {
         let mut c = Config::with_none();
@@ -305,4 +471,27 @@ mod test {
             DateFlag::configure_from(&matches, &config)
         );
     }
+
+    #[test]
+    fn test_relative_from_config_none() {
+        let mut c = Config::with_none();
+        c.date = Some(config_file::Date::Format("relative".into()));
+        assert_eq!(None, RelativeDateFlag::from_config(&c));
+    }
+
+    #[test]
+    fn test_relative_from_config() {
+        let c: Config = serde_yaml::from_str(
+            "date:\n  modified: relative\n  relative:\n    max-unit: day\n    compound: true",
+        )
+        .unwrap();
+        assert_eq!(
+            Some(RelativeDateFlag {
+                max_unit: Some(RelativeUnit::Day),
+                compound: true,
+            }),
+            RelativeDateFlag::from_config(&c)
+        );
+        assert_eq!(Some(DateFlag::Relative), DateFlag::from_config(&c));
+    }
 }
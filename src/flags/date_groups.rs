@@ -0,0 +1,81 @@
+//! This module defines the [DateGroups] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to list the entries sorted by time under the headers of their age
+/// groups, eg: "Today".
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DateGroups(pub bool);
+
+impl Configurable<Self> for DateGroups {
+    /// Get a potential `DateGroups` value from [ArgMatches].
+    ///
+    /// If the "date-groups" argument is passed, this returns a `DateGroups` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("date-groups") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `DateGroups` value from a [Config].
+    ///
+    /// If the `Config::date-groups` has value,
+    /// this returns it as the value of the `DateGroups`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.date_groups.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DateGroups;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DateGroups::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--date-groups"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateGroups(true)),
+            DateGroups::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, DateGroups::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.date_groups = Some(true);
+        assert_eq!(Some(DateGroups(true)), DateGroups::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.date_groups = Some(false);
+        assert_eq!(Some(DateGroups(false)), DateGroups::from_config(&c));
+    }
+}
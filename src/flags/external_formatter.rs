@@ -0,0 +1,75 @@
+//! This module defines the [ExternalFormatter] flag. To set it up from [ArgMatches], a [Config]
+//! and its [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag holding the command the JSON document of the entries is written to, to print its
+/// output instead of the listing.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ExternalFormatter(pub Option<String>);
+
+impl Configurable<Self> for ExternalFormatter {
+    /// Get a potential `ExternalFormatter` value from [ArgMatches].
+    ///
+    /// If the "external-formatter" argument is passed, this returns an `ExternalFormatter` with
+    /// its last value in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        let command = matches.values_of("external-formatter")?.next_back()?;
+        Some(Self(Some(command.to_owned())))
+    }
+
+    /// Get a potential `ExternalFormatter` value from a [Config].
+    ///
+    /// If the `Config::external-formatter` has value, this returns it as the value of the
+    /// `ExternalFormatter`, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .external_formatter
+            .as_ref()
+            .map(|command| Self(Some(command.clone())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ExternalFormatter;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ExternalFormatter::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_value() {
+        let argv = vec!["lsd", "--external-formatter", "jq .entries"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ExternalFormatter(Some(String::from("jq .entries")))),
+            ExternalFormatter::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ExternalFormatter::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_value() {
+        let c: Config = serde_yaml::from_str("external-formatter: cat").unwrap();
+        assert_eq!(
+            Some(ExternalFormatter(Some(String::from("cat")))),
+            ExternalFormatter::from_config(&c)
+        );
+    }
+}
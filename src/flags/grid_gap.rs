@@ -0,0 +1,79 @@
+//! This module defines the [GridGap] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing how many spaces separate the columns of the grid layout.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct GridGap(pub usize);
+
+impl Configurable<Self> for GridGap {
+    /// Get a potential `GridGap` value from [ArgMatches].
+    ///
+    /// If the "grid-gap" argument is passed, this returns a `GridGap` with its value in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        let gap = matches.values_of("grid-gap")?.next_back()?;
+        gap.parse().ok().map(Self)
+    }
+
+    /// Get a potential `GridGap` value from a [Config].
+    ///
+    /// If the `Config::grid-gap` has value, this returns it as the value of the `GridGap`, in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.grid_gap.map(Self)
+    }
+}
+
+/// The default value for `GridGap` is two spaces.
+impl Default for GridGap {
+    fn default() -> Self {
+        Self(2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GridGap;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, GridGap::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_value() {
+        let argv = vec!["lsd", "--grid-gap", "4"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(GridGap(4)), GridGap::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--grid-gap", "wide"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, GridGap::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_value() {
+        let mut c = Config::with_none();
+        c.grid_gap = Some(0);
+        assert_eq!(Some(GridGap(0)), GridGap::from_config(&c));
+    }
+}
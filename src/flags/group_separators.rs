@@ -0,0 +1,86 @@
+//! This module defines the [GroupSeparators] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to separate the groups of sorted entries with a blank line.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct GroupSeparators(pub bool);
+
+impl Configurable<Self> for GroupSeparators {
+    /// Get a potential `GroupSeparators` value from [ArgMatches].
+    ///
+    /// If the "group-separators" argument is passed, this returns a `GroupSeparators` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("group-separators") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `GroupSeparators` value from a [Config].
+    ///
+    /// If the `Config::group-separators` has value,
+    /// this returns it as the value of the `GroupSeparators`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.group_separators.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GroupSeparators;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, GroupSeparators::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--group-separators"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(GroupSeparators(true)),
+            GroupSeparators::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, GroupSeparators::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.group_separators = Some(true);
+        assert_eq!(
+            Some(GroupSeparators(true)),
+            GroupSeparators::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.group_separators = Some(false);
+        assert_eq!(
+            Some(GroupSeparators(false)),
+            GroupSeparators::from_config(&c)
+        );
+    }
+}
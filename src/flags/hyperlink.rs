@@ -0,0 +1,148 @@
+//! This module defines the [HyperlinkOption] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing when to print the file names as hyperlinks to the files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HyperlinkOption {
+    /// The variant to print the hyperlinks only if the output is a tty.
+    Auto,
+    /// The variant to always print the hyperlinks.
+    Always,
+    /// The variant to never print the hyperlinks.
+    Never,
+}
+
+impl HyperlinkOption {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => {
+                panic!(
+                    "Hyperlink can only be one of auto, always or never, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+
+    /// Resolve [HyperlinkOption::Auto] to [HyperlinkOption::Always] if the output is a tty, or
+    /// to [HyperlinkOption::Never] otherwise.
+    pub fn resolve(self, tty_available: bool) -> Self {
+        match self {
+            Self::Auto if tty_available => Self::Always,
+            Self::Auto => Self::Never,
+            hyperlink => hyperlink,
+        }
+    }
+}
+
+impl Configurable<Self> for HyperlinkOption {
+    /// Get a potential `HyperlinkOption` variant from [ArgMatches].
+    ///
+    /// If the "hyperlink" argument is passed, this returns the corresponding `HyperlinkOption` variant in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("hyperlink") > 0 {
+            if let Some(output) = matches.values_of("hyperlink")?.next_back() {
+                return Self::from_str(output);
+            }
+        }
+        None
+    }
+
+    /// Get a potential `HyperlinkOption` variant from a [Config].
+    ///
+    /// If the `Config::hyperlink` has value and is one of "auto", "always" or "never", this returns the
+    /// corresponding `HyperlinkOption` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.hyperlink
+    }
+}
+
+/// The default value for `HyperlinkOption` is [HyperlinkOption::Never].
+impl Default for HyperlinkOption {
+    fn default() -> Self {
+        Self::Never
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HyperlinkOption;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, HyperlinkOption::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_always() {
+        let argv = vec!["lsd", "--hyperlink", "always"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(HyperlinkOption::Always),
+            HyperlinkOption::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_auto() {
+        let argv = vec!["lsd", "--hyperlink=auto"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(HyperlinkOption::Auto),
+            HyperlinkOption::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_multi() {
+        let argv = vec!["lsd", "--hyperlink", "always", "--hyperlink", "never"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(HyperlinkOption::Never),
+            HyperlinkOption::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, HyperlinkOption::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_auto() {
+        let mut c = Config::with_none();
+        c.hyperlink = Some(HyperlinkOption::Auto);
+        assert_eq!(
+            Some(HyperlinkOption::Auto),
+            HyperlinkOption::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_resolve_auto() {
+        assert_eq!(HyperlinkOption::Always, HyperlinkOption::Auto.resolve(true));
+        assert_eq!(HyperlinkOption::Never, HyperlinkOption::Auto.resolve(false));
+        assert_eq!(
+            HyperlinkOption::Always,
+            HyperlinkOption::Always.resolve(false)
+        );
+    }
+}
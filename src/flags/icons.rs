@@ -4,9 +4,14 @@
 use super::Configurable;
 
 use crate::config_file::Config;
+use crate::meta::FileType;
+
+use crate::app;
 
 use clap::ArgMatches;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 
 /// A collection of flags on how to use icons.
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
@@ -17,21 +22,37 @@ pub struct Icons {
     pub theme: IconTheme,
     /// String between icon and name.
     pub separator: IconSeparator,
+    /// Which version of the Nerd Fonts the fancy icons are taken from.
+    pub nerd_font_version: NerdFontVersion,
+    /// The icons replacing the ones of some names or extensions.
+    pub overrides: IconOverrides,
+    /// The order the icons of the files are looked up in.
+    pub lookup: IconLookup,
+    /// The file types printed without an icon.
+    pub hide_for: IconHideFor,
 }
 
 impl Icons {
     /// Get an `Icons` struct from [ArgMatches], a [Config] or the [Default] values.
     ///
-    /// The [IconOption] and [IconTheme] are configured with their respective [Configurable]
-    /// implementation.
+    /// The [IconOption], [IconTheme], [NerdFontVersion], [IconOverrides], [IconLookup] and
+    /// [IconHideFor] are configured with their respective [Configurable] implementation.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
         let when = IconOption::configure_from(matches, config);
         let theme = IconTheme::configure_from(matches, config);
         let separator = IconSeparator::configure_from(matches, config);
+        let nerd_font_version = NerdFontVersion::configure_from(matches, config);
+        let overrides = IconOverrides::configure_from(matches, config);
+        let lookup = IconLookup::configure_from(matches, config);
+        let hide_for = IconHideFor::configure_from(matches, config);
         Self {
             when,
             theme,
             separator,
+            nerd_font_version,
+            overrides,
+            lookup,
+            hide_for,
         }
     }
 }
@@ -139,6 +160,154 @@ impl Default for IconTheme {
     }
 }
 
+/// The flag showing which version of the Nerd Fonts the fancy icons are taken from. The version 3
+/// moved the Material Design icons to other codepoints, so they render as boxes with the other
+/// versions of the fonts.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "u8")]
+pub enum NerdFontVersion {
+    V2,
+    V3,
+}
+
+impl TryFrom<u8> for NerdFontVersion {
+    type Error = String;
+
+    fn try_from(version: u8) -> Result<Self, Self::Error> {
+        match version {
+            2 => Ok(Self::V2),
+            3 => Ok(Self::V3),
+            _ => Err(format!(
+                "nerd-font-version can only be one of 2 or 3, but got {}",
+                version
+            )),
+        }
+    }
+}
+
+impl Configurable<Self> for NerdFontVersion {
+    /// Get a potential `NerdFontVersion` variant from [ArgMatches].
+    ///
+    /// If the argument is passed, this returns the variant corresponding to its parameter in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("nerd-font-version") > 0 {
+            match matches.values_of("nerd-font-version")?.next_back() {
+                Some("2") => Some(Self::V2),
+                Some("3") => Some(Self::V3),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `NerdFontVersion` variant from a [Config].
+    ///
+    /// If the `Config::icons::nerd-font-version` has value, this returns it in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .icons
+            .as_ref()
+            .and_then(|icon| icon.nerd_font_version)
+    }
+}
+
+/// The default value for `NerdFontVersion` is [NerdFontVersion::V3], the current version of the
+/// fonts.
+impl Default for NerdFontVersion {
+    fn default() -> Self {
+        Self::V3
+    }
+}
+
+/// The flag showing the icons replacing the ones of some file names or extensions, keyed by their
+/// lower-case name or extension.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct IconOverrides {
+    pub by_name: HashMap<String, String>,
+    pub by_extension: HashMap<String, String>,
+}
+
+impl Configurable<Self> for IconOverrides {
+    /// Get a potential `IconOverrides` value from [ArgMatches].
+    ///
+    /// If the "icon-override" argument is passed, this returns the icons of the names, and of the
+    /// extensions written with a leading dot, in a [Some]. The later overrides of a same name or
+    /// extension win. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        let values = matches.values_of("icon-override")?;
+        let mut overrides = Self::default();
+        for value in values {
+            let (key, icon) =
+                app::parse_icon_override(value).expect("validated by the argument parser");
+            if key.len() > 1 && key.as_bytes()[0] == b'.' {
+                overrides.by_extension.insert(key[1..].to_string(), icon);
+            } else {
+                overrides.by_name.insert(key, icon);
+            }
+        }
+        Some(overrides)
+    }
+
+    /// `IconOverrides` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+/// A way to look up the icon of a file, once its file type has no icon of its own.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconLookupStep {
+    /// The icons of the known file names, like `Cargo.toml`.
+    Name,
+    /// The icons of the known extensions.
+    Extension,
+    /// The icons of the categories of the known extensions, like any image extension.
+    Category,
+}
+
+/// The flag showing the order the icon of a file is looked up in, the default icons being used
+/// when none of the steps finds one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IconLookup(pub Vec<IconLookupStep>);
+
+impl Configurable<Self> for IconLookup {
+    /// `IconLookup` can not be configured by [ArgMatches].
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `IconLookup` value from a [Config].
+    ///
+    /// If the `Config::icons::lookup` has value, this returns it as the value of the
+    /// `IconLookup`, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .icons
+            .as_ref()
+            .and_then(|icons| icons.lookup.clone())
+            .map(Self)
+    }
+}
+
+/// The default value for `IconLookup` is the name, then the extension, then its category.
+impl Default for IconLookup {
+    fn default() -> Self {
+        Self(vec![
+            IconLookupStep::Name,
+            IconLookupStep::Extension,
+            IconLookupStep::Category,
+        ])
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct IconSeparator(pub String);
@@ -173,6 +342,69 @@ impl Default for IconSeparator {
     }
 }
 
+/// A file type whose icon may be hidden.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconFileType {
+    File,
+    Directory,
+    Symlink,
+    Pipe,
+    Socket,
+    BlockDevice,
+    CharDevice,
+    Special,
+}
+
+impl IconFileType {
+    /// Whether a file is of this type.
+    pub fn matches(self, file_type: FileType) -> bool {
+        matches!(
+            (self, file_type),
+            (Self::File, FileType::File { .. })
+                | (Self::Directory, FileType::Directory { .. })
+                | (Self::Symlink, FileType::SymLink { .. })
+                | (Self::Pipe, FileType::Pipe)
+                | (Self::Socket, FileType::Socket)
+                | (Self::BlockDevice, FileType::BlockDevice)
+                | (Self::CharDevice, FileType::CharDevice)
+                | (Self::Special, FileType::Special)
+        )
+    }
+}
+
+/// The flag showing the file types printed without an icon, even when the icons are shown.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct IconHideFor(pub Vec<IconFileType>);
+
+impl IconHideFor {
+    /// Whether the icon of a file of this type is hidden.
+    pub fn hides(&self, file_type: FileType) -> bool {
+        self.0.iter().any(|hidden| hidden.matches(file_type))
+    }
+}
+
+impl Configurable<Self> for IconHideFor {
+    /// `IconHideFor` can not be configured by [ArgMatches].
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `IconHideFor` value from a [Config].
+    ///
+    /// If the `Config::icons::hide-for` has value, this returns it as the value of the
+    /// `IconHideFor`, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .icons
+            .as_ref()
+            .and_then(|icons| icons.hide_for.clone())
+            .map(Self)
+    }
+}
+
 #[cfg(test)]
 mod test_icon_option {
     use super::IconOption;
@@ -250,6 +482,9 @@ mod test_icon_option {
             when: Some(IconOption::Always),
             theme: None,
             separator: None,
+            nerd_font_version: None,
+            lookup: None,
+            hide_for: None,
         });
         assert_eq!(Some(IconOption::Always), IconOption::from_config(&c));
     }
@@ -261,6 +496,9 @@ mod test_icon_option {
             when: Some(IconOption::Auto),
             theme: None,
             separator: None,
+            nerd_font_version: None,
+            lookup: None,
+            hide_for: None,
         });
         assert_eq!(Some(IconOption::Auto), IconOption::from_config(&c));
     }
@@ -272,6 +510,9 @@ mod test_icon_option {
             when: Some(IconOption::Never),
             theme: None,
             separator: None,
+            nerd_font_version: None,
+            lookup: None,
+            hide_for: None,
         });
         assert_eq!(Some(IconOption::Never), IconOption::from_config(&c));
     }
@@ -284,6 +525,9 @@ mod test_icon_option {
             when: Some(IconOption::Always),
             theme: None,
             separator: None,
+            nerd_font_version: None,
+            lookup: None,
+            hide_for: None,
         });
         assert_eq!(Some(IconOption::Never), IconOption::from_config(&c));
     }
@@ -346,6 +590,9 @@ mod test_icon_theme {
             when: None,
             theme: Some(IconTheme::Fancy),
             separator: None,
+            nerd_font_version: None,
+            lookup: None,
+            hide_for: None,
         });
         assert_eq!(Some(IconTheme::Fancy), IconTheme::from_config(&c));
     }
@@ -357,6 +604,9 @@ mod test_icon_theme {
             when: None,
             theme: Some(IconTheme::Unicode),
             separator: None,
+            nerd_font_version: None,
+            lookup: None,
+            hide_for: None,
         });
         assert_eq!(Some(IconTheme::Unicode), IconTheme::from_config(&c));
     }
@@ -376,6 +626,9 @@ mod test_icon_separator {
             when: None,
             theme: None,
             separator: Some(" ".to_string()),
+            nerd_font_version: None,
+            lookup: None,
+            hide_for: None,
         });
         let expected = Some(IconSeparator(" ".to_string()));
         assert_eq!(expected, IconSeparator::from_config(&c));
@@ -388,8 +641,182 @@ mod test_icon_separator {
             when: None,
             theme: None,
             separator: Some(" |".to_string()),
+            nerd_font_version: None,
+            lookup: None,
+            hide_for: None,
         });
         let expected = Some(IconSeparator(" |".to_string()));
         assert_eq!(expected, IconSeparator::from_config(&c));
     }
 }
+
+#[cfg(test)]
+mod test_nerd_font_version {
+    use super::NerdFontVersion;
+
+    use crate::app;
+    use crate::config_file::{Config, Icons};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, NerdFontVersion::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_v2() {
+        let argv = vec!["lsd", "--nerd-font-version", "2"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(NerdFontVersion::V2),
+            NerdFontVersion::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--nerd-font-version", "1"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_v2() {
+        let mut c = Config::with_none();
+        c.icons = Some(Icons {
+            when: None,
+            theme: None,
+            separator: None,
+            nerd_font_version: Some(NerdFontVersion::V2),
+            lookup: None,
+            hide_for: None,
+        });
+        assert_eq!(Some(NerdFontVersion::V2), NerdFontVersion::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_yaml() {
+        let c: Config = serde_yaml::from_str("icons:\n  nerd-font-version: 2").unwrap();
+        assert_eq!(Some(NerdFontVersion::V2), NerdFontVersion::from_config(&c));
+        assert!(serde_yaml::from_str::<Config>("icons:\n  nerd-font-version: 4").is_err());
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(NerdFontVersion::V3, NerdFontVersion::default());
+    }
+}
+
+#[cfg(test)]
+mod test_icon_overrides {
+    use super::IconOverrides;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, IconOverrides::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches() {
+        let argv = vec![
+            "lsd",
+            "--icon-override",
+            ".RS=\\ue7a8",
+            "--icon-override",
+            "Makefile=\\u{f423}",
+            "--icon-override",
+            ".rs=R",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let overrides = IconOverrides::from_arg_matches(&matches).unwrap();
+
+        assert_eq!(
+            Some("R"),
+            overrides.by_extension.get("rs").map(String::as_str)
+        );
+        assert_eq!(
+            Some("\u{f423}"),
+            overrides.by_name.get("makefile").map(String::as_str)
+        );
+        assert_eq!(1, overrides.by_extension.len());
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid_codepoint() {
+        for value in &[
+            ".rs=\\ud800",
+            ".rs=\\u{110000}",
+            ".rs=\\uzzzz",
+            "=x",
+            ".rs=",
+        ] {
+            let argv = vec!["lsd", "--icon-override", value];
+            assert!(app::build().get_matches_from_safe(argv).is_err());
+        }
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, IconOverrides::from_config(&Config::with_none()));
+    }
+}
+
+#[cfg(test)]
+mod test_icon_lookup {
+    use super::{IconLookup, IconLookupStep};
+
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, IconLookup::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_steps() {
+        let c: Config = serde_yaml::from_str("icons:\n  lookup: [extension, name]").unwrap();
+        assert_eq!(
+            Some(IconLookup(vec![
+                IconLookupStep::Extension,
+                IconLookupStep::Name
+            ])),
+            IconLookup::from_config(&c)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_icon_hide_for {
+    use super::{IconFileType, IconHideFor};
+
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+    use crate::meta::FileType;
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, IconHideFor::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_types() {
+        let c: Config =
+            serde_yaml::from_str("icons:\n  hide-for: [directory, block-device]").unwrap();
+        let hide_for = IconHideFor::from_config(&c).unwrap();
+        assert_eq!(
+            IconHideFor(vec![IconFileType::Directory, IconFileType::BlockDevice]),
+            hide_for
+        );
+        assert!(hide_for.hides(FileType::Directory { uid: false }));
+        assert!(hide_for.hides(FileType::BlockDevice));
+        assert!(!hide_for.hides(FileType::SymLink { is_dir: true }));
+    }
+}
@@ -11,85 +11,67 @@ use globset::{Glob, GlobSet, GlobSetBuilder};
 pub struct IgnoreGlobs(pub GlobSet);
 
 impl IgnoreGlobs {
-    /// Returns a value from either [ArgMatches], a [Config] or a [Default] value. The first value
-    /// that is not [None] is used. The order of precedence for the value used is:
-    /// - [from_arg_matches](IgnoreGlobs::from_arg_matches)
-    /// - [from_config](IgnoreGlobs::from_config)
-    /// - [Default::default]
+    /// Returns a value built from the globs of both [ArgMatches] and a [Config], or the
+    /// [Default] value. The globs are merged rather than replacing each other:
+    /// - the globs of [from_config](IgnoreGlobs::from_config), unless "ignore-config" is passed,
+    /// - followed by the globs of [from_arg_matches](IgnoreGlobs::from_arg_matches).
+    ///
+    /// If the "no-ignore" argument is passed, no glob is used at all and the [Default] value is
+    /// returned.
     ///
     /// # Errors
     ///
     /// If either of the [Glob::new] or [GlobSetBuilder.build] methods return an [Err].
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
-        let mut result: Result<Self, Error> = Ok(Default::default());
+        if matches.is_present("no-ignore") {
+            return Ok(Default::default());
+        }
+
+        let mut glob_set_builder = GlobSetBuilder::new();
 
         if !matches.is_present("ignore-config") {
             if let Some(value) = Self::from_config(config) {
-                match value {
-                    Ok(glob_set) => result = Ok(Self(glob_set)),
-                    Err(err) => result = Err(err),
+                for glob in value? {
+                    glob_set_builder.add(glob);
                 }
             }
         }
 
         if let Some(value) = Self::from_arg_matches(matches) {
-            match value {
-                Ok(glob_set) => result = Ok(Self(glob_set)),
-                Err(err) => result = Err(err),
+            for glob in value? {
+                glob_set_builder.add(glob);
             }
         }
 
-        result
+        Ok(Self(Self::create_glob_set(&glob_set_builder)?))
     }
 
-    /// Get a potential [GlobSet] from [ArgMatches].
+    /// Get the potential [Glob]s from [ArgMatches].
     ///
     /// If the "ignore-glob" argument has been passed, this returns a [Result] in a [Some] with
-    /// either the built [GlobSet] or an [Error], if any error was encountered while creating the
-    /// [GlobSet]. If the argument has not been passed, this returns [None].
-    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<GlobSet, Error>> {
+    /// either the built [Glob]s or an [Error], if any error was encountered while creating them.
+    /// If the argument has not been passed, this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<Vec<Glob>, Error>> {
         if matches.occurrences_of("ignore-glob") > 0 {
-            if let Some(values) = matches.values_of("ignore-glob") {
-                let mut glob_set_builder = GlobSetBuilder::new();
-                for value in values {
-                    match Self::create_glob(value) {
-                        Ok(glob) => {
-                            glob_set_builder.add(glob);
-                        }
-                        Err(err) => return Some(Err(err)),
-                    }
-                }
-                Some(Self::create_glob_set(&glob_set_builder))
-            } else {
-                None
-            }
+            matches
+                .values_of("ignore-glob")
+                .map(|values| values.map(Self::create_glob).collect())
         } else {
             None
         }
     }
 
-    /// Get a potential [GlobSet] from a [Config].
+    /// Get the potential [Glob]s from a [Config].
     ///
-    /// If the `Config::ignore-globs` contains an Array of Strings,
-    /// each of its values is used to build the [GlobSet]. If the building
-    /// succeeds, the [GlobSet] is returned in the [Result] in a [Some]. If any error is
-    /// encountered while building, an [Error] is returned in the Result instead. If the Config does
-    /// not contain such a key, this returns [None].
-    fn from_config(config: &Config) -> Option<Result<GlobSet, Error>> {
-        if let Some(globs) = &config.ignore_globs {
-            let mut glob_set_builder = GlobSetBuilder::new();
-            for glob in globs.iter() {
-                match Self::create_glob(glob) {
-                    Ok(glob) => {
-                        glob_set_builder.add(glob);
-                    }
-                    Err(err) => return Some(Err(err)),
-                }
-            }
-            Some(Self::create_glob_set(&glob_set_builder))
-        } else {
-            None
-        }
+    /// If the `Config::ignore-globs` contains an Array of Strings, each of its values is used to
+    /// build a [Glob]. If the building succeeds, the [Glob]s are returned in the [Result] in a
+    /// [Some]. If any error is encountered while building, an [Error] is returned in the Result
+    /// instead. If the Config does not contain such a key, this returns [None].
+    fn from_config(config: &Config) -> Option<Result<Vec<Glob>, Error>> {
+        config
+            .ignore_globs
+            .as_ref()
+            .map(|globs| globs.iter().map(|glob| Self::create_glob(glob)).collect())
     }
 
     /// Create a [Glob] from a provided pattern.
@@ -175,6 +157,39 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_configuration_merges_args_and_config() {
+        let argv = vec!["lsd", "--ignore-glob", "*.bak"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let mut c = Config::with_none();
+        c.ignore_globs = Some(vec![".git".into()]);
+        let globs = IgnoreGlobs::configure_from(&matches, &c).unwrap().0;
+        assert!(globs.is_match(".git"));
+        assert!(globs.is_match("file.bak"));
+        assert!(!globs.is_match("file.rs"));
+    }
+
+    #[test]
+    fn test_configuration_ignore_config_keeps_args() {
+        let argv = vec!["lsd", "--ignore-glob", "*.bak", "--ignore-config"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let mut c = Config::with_none();
+        c.ignore_globs = Some(vec![".git".into()]);
+        let globs = IgnoreGlobs::configure_from(&matches, &c).unwrap().0;
+        assert!(!globs.is_match(".git"));
+        assert!(globs.is_match("file.bak"));
+    }
+
+    #[test]
+    fn test_configuration_no_ignore() {
+        let argv = vec!["lsd", "--ignore-glob", "*.bak", "--no-ignore"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let mut c = Config::with_none();
+        c.ignore_globs = Some(vec![".git".into()]);
+        let globs = IgnoreGlobs::configure_from(&matches, &c).unwrap().0;
+        assert!(globs.is_empty());
+    }
+
     #[test]
     fn test_from_arg_matches_none() {
         let argv = vec!["lsd"];
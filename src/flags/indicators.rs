@@ -6,35 +6,105 @@ use super::Configurable;
 use crate::config_file::Config;
 
 use clap::ArgMatches;
+use serde::Deserialize;
+use std::convert::TryFrom;
 
-/// The flag showing whether to print file type indicators.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
-pub struct Indicators(pub bool);
+/// The flag showing when to print file type indicators.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "IndicatorsValue")]
+pub enum Indicators {
+    /// The variant to always print the indicators.
+    Always,
+    /// The variant to print the indicators only if the output is a tty.
+    Auto,
+    /// The variant to never print the indicators.
+    Never,
+}
+
+impl Indicators {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "always" => Some(Self::Always),
+            "auto" => Some(Self::Auto),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    /// Resolve [Indicators::Auto] to [Indicators::Always] if the output is a tty, or to
+    /// [Indicators::Never] otherwise, so automated consumers don't get stray indicators.
+    pub fn resolve(self, tty_available: bool) -> Self {
+        match self {
+            Self::Auto if tty_available => Self::Always,
+            Self::Auto => Self::Never,
+            indicators => indicators,
+        }
+    }
+
+    /// Whether the indicators are printed. [Indicators::Auto] is expected to be resolved first.
+    pub fn is_enabled(self) -> bool {
+        self == Self::Always
+    }
+}
+
+/// The value of the indicators in a [Config], either a boolean or the name of a variant.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IndicatorsValue {
+    Bool(bool),
+    Name(String),
+}
+
+impl TryFrom<IndicatorsValue> for Indicators {
+    type Error = String;
+
+    fn try_from(value: IndicatorsValue) -> Result<Self, Self::Error> {
+        match value {
+            IndicatorsValue::Bool(true) => Ok(Self::Always),
+            IndicatorsValue::Bool(false) => Ok(Self::Never),
+            IndicatorsValue::Name(name) => Self::from_str(&name).ok_or_else(|| {
+                format!(
+                    "indicators can only be one of true, false or auto, but got {}",
+                    name
+                )
+            }),
+        }
+    }
+}
 
 impl Configurable<Self> for Indicators {
-    /// Get a potential `Indicators` value from [ArgMatches].
+    /// Get a potential `Indicators` variant from [ArgMatches].
     ///
-    /// If the "indicators" argument is passed, this returns an `Indicators` with value `true` in a
-    /// [Some]. Otherwise this returns [None].
+    /// If the "indicators" argument is passed without a value, this returns
+    /// [Indicators::Always] in a [Some]. If it is passed with a value, this returns the
+    /// corresponding variant in a [Some]. Otherwise this returns [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
-        if matches.is_present("indicators") {
-            Some(Self(true))
-        } else {
-            None
+        if matches.occurrences_of("indicators") == 0 {
+            return None;
+        }
+
+        match matches
+            .values_of("indicators")
+            .and_then(|mut values| values.next_back())
+        {
+            Some(value) => Self::from_str(value),
+            None => Some(Self::Always),
         }
     }
 
-    /// Get a potential `Indicators` value from a [Config].
+    /// Get a potential `Indicators` variant from a [Config].
     ///
-    /// If the `Config::indicators` has value,
-    /// this returns its value as the value of the `Indicators`, in a [Some].
-    /// Otherwise this returns [None].
+    /// If the `Config::indicators` has value, this returns it in a [Some]. Otherwise this
+    /// returns [None].
     fn from_config(config: &Config) -> Option<Self> {
-        if let Some(ind) = &config.indicators {
-            Some(Self(*ind))
-        } else {
-            None
-        }
+        config.indicators
+    }
+}
+
+/// The default value for `Indicators` is [Indicators::Never].
+impl Default for Indicators {
+    fn default() -> Self {
+        Self::Never
     }
 }
 
@@ -58,11 +128,32 @@ mod test {
         let argv = vec!["lsd", "--classify"];
         let matches = app::build().get_matches_from_safe(argv).unwrap();
         assert_eq!(
-            Some(Indicators(true)),
+            Some(Indicators::Always),
+            Indicators::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_auto() {
+        let argv = vec!["lsd", "--classify=auto"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(Indicators::Auto),
             Indicators::from_arg_matches(&matches)
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_short_keeps_file() {
+        let argv = vec!["lsd", "-F", "dir"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(Indicators::Always),
+            Indicators::from_arg_matches(&matches)
+        );
+        assert_eq!(Some("dir"), matches.value_of("FILE"));
+    }
+
     #[test]
     fn test_from_config_none() {
         assert_eq!(None, Indicators::from_config(&Config::with_none()));
@@ -71,14 +162,22 @@ mod test {
     #[test]
     fn test_from_config_true() {
         let mut c = Config::with_none();
-        c.indicators = Some(true);
-        assert_eq!(Some(Indicators(true)), Indicators::from_config(&c));
+        c.indicators = Some(Indicators::Always);
+        assert_eq!(Some(Indicators::Always), Indicators::from_config(&c));
     }
 
     #[test]
     fn test_from_config_false() {
         let mut c = Config::with_none();
-        c.indicators = Some(false);
-        assert_eq!(Some(Indicators(false)), Indicators::from_config(&c));
+        c.indicators = Some(Indicators::Never);
+        assert_eq!(Some(Indicators::Never), Indicators::from_config(&c));
+    }
+
+    #[test]
+    fn test_resolve_auto() {
+        assert_eq!(Indicators::Always, Indicators::Auto.resolve(true));
+        assert_eq!(Indicators::Never, Indicators::Auto.resolve(false));
+        assert_eq!(Indicators::Always, Indicators::Always.resolve(false));
+        assert_eq!(Indicators::Never, Indicators::Never.resolve(true));
     }
 }
@@ -0,0 +1,80 @@
+//! This module defines the [IpcStatusFlag] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to annotate sockets and FIFOs with their status.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct IpcStatusFlag(pub bool);
+
+impl Configurable<Self> for IpcStatusFlag {
+    /// Get a potential `IpcStatusFlag` value from [ArgMatches].
+    ///
+    /// If the "ipc-status" argument is passed, this returns a `IpcStatusFlag` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("ipc-status") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `IpcStatusFlag` value from a [Config].
+    ///
+    /// If the `Config::ipc-status` has value,
+    /// this returns it as the value of the `IpcStatusFlag`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.ipc_status.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IpcStatusFlag;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, IpcStatusFlag::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--ipc-status"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(IpcStatusFlag(true)),
+            IpcStatusFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, IpcStatusFlag::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.ipc_status = Some(true);
+        assert_eq!(Some(IpcStatusFlag(true)), IpcStatusFlag::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.ipc_status = Some(false);
+        assert_eq!(Some(IpcStatusFlag(false)), IpcStatusFlag::from_config(&c));
+    }
+}
@@ -1,5 +1,6 @@
-//! This module defines the [Layout] flag. To set it up from [ArgMatches], a [Config] and its
-//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+//! This module defines the [Layout] and [LayoutNonTty] flags. To set them up from [ArgMatches], a
+//! [Config] and their [Default] value, use their [configure_from](Configurable::configure_from)
+//! method.
 
 use crate::config_file::Config;
 
@@ -16,18 +17,22 @@ pub enum Layout {
     Tree,
     #[serde(rename = "oneline")]
     OneLine,
+    /// As many entries per line as fit in the width, separated by commas like `ls -m`.
+    Pack,
 }
 
 impl Configurable<Layout> for Layout {
     /// Get a potential `Layout` variant from [ArgMatches].
     ///
-    /// If any of the "tree", "long" or "oneline" arguments is passed, this returns the
+    /// If any of the "tree", "pack", "long" or "oneline" arguments is passed, this returns the
     /// corresponding `Layout` variant in a [Some]. Otherwise if the number of passed "blocks"
     /// arguments is greater than 1, this also returns the [OneLine](Layout::OneLine) variant.
     /// Finally if neither of them is passed, this returns [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
         if matches.is_present("tree") {
             Some(Self::Tree)
+        } else if matches.is_present("pack") {
+            Some(Self::Pack)
         } else if matches.is_present("long")
             || matches.is_present("oneline")
             || matches.is_present("inode")
@@ -42,7 +47,7 @@ impl Configurable<Layout> for Layout {
 
     /// Get a potential Layout variant from a [Config].
     ///
-    /// If the `Config::layout` has value and is one of "tree", "oneline" or "grid",
+    /// If the `Config::layout` has value and is one of "tree", "oneline", "pack" or "grid",
     /// this returns the corresponding `Layout` variant in a [Some].
     /// Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
@@ -57,9 +62,43 @@ impl Default for Layout {
     }
 }
 
+/// The flag showing which output layout to print when stdout is not a tty, in place of the
+/// [Layout] one.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct LayoutNonTty(pub Option<Layout>);
+
+impl LayoutNonTty {
+    /// Get the layout to print with, given the configured [Layout] and whether stdout is a tty.
+    pub fn resolve(self, layout: Layout, tty_available: bool) -> Layout {
+        match self.0 {
+            Some(non_tty) if !tty_available => non_tty,
+            _ => layout,
+        }
+    }
+}
+
+impl Configurable<Self> for LayoutNonTty {
+    /// Get a potential `LayoutNonTty` value from [ArgMatches].
+    ///
+    /// If any argument choosing the [Layout] is passed, this returns a `LayoutNonTty` without
+    /// value in a [Some], so that the layout of the command line is used whatever the output.
+    /// Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        Layout::from_arg_matches(matches).map(|_| Self(None))
+    }
+
+    /// Get a potential `LayoutNonTty` value from a [Config].
+    ///
+    /// If the `Config::layout_non_tty` has value, this returns it as the value of the
+    /// `LayoutNonTty`, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.layout_non_tty.map(|layout| Self(Some(layout)))
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Layout;
+    use super::{Layout, LayoutNonTty};
 
     use crate::app;
     use crate::config_file::Config;
@@ -86,6 +125,13 @@ mod test {
         assert_eq!(Some(Layout::OneLine), Layout::from_arg_matches(&matches));
     }
 
+    #[test]
+    fn test_from_arg_matches_pack() {
+        let argv = vec!["lsd", "--pack"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Layout::Pack), Layout::from_arg_matches(&matches));
+    }
+
     #[test]
     fn test_from_arg_matches_oneline_through_long() {
         let argv = vec!["lsd", "--long"];
@@ -125,4 +171,44 @@ mod test {
         c.layout = Some(Layout::Grid);
         assert_eq!(Some(Layout::Grid), Layout::from_config(&c));
     }
+
+    #[test]
+    fn test_non_tty_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, LayoutNonTty::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_non_tty_from_arg_matches_layout_given() {
+        let argv = vec!["lsd", "--tree"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(LayoutNonTty(None)),
+            LayoutNonTty::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_non_tty_from_config() {
+        assert_eq!(None, LayoutNonTty::from_config(&Config::with_none()));
+
+        let mut c = Config::with_none();
+        c.layout_non_tty = Some(Layout::OneLine);
+        assert_eq!(
+            Some(LayoutNonTty(Some(Layout::OneLine))),
+            LayoutNonTty::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_non_tty_resolve() {
+        let non_tty = LayoutNonTty(Some(Layout::Tree));
+        assert_eq!(Layout::Grid, non_tty.resolve(Layout::Grid, true));
+        assert_eq!(Layout::Tree, non_tty.resolve(Layout::Grid, false));
+        assert_eq!(
+            Layout::Pack,
+            LayoutNonTty(None).resolve(Layout::Pack, false)
+        );
+    }
 }
@@ -0,0 +1,77 @@
+//! This module defines the [Magic] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to tell the type of the files without an extension by their first bytes.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Magic(pub bool);
+
+impl Configurable<Self> for Magic {
+    /// Get a potential `Magic` value from [ArgMatches].
+    ///
+    /// If the "magic" argument is passed, this returns a `Magic` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("magic") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Magic` value from a [Config].
+    ///
+    /// If the `Config::magic` has value,
+    /// this returns it as the value of the `Magic`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.magic.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Magic;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Magic::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--magic"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Magic(true)), Magic::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Magic::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.magic = Some(true);
+        assert_eq!(Some(Magic(true)), Magic::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.magic = Some(false);
+        assert_eq!(Some(Magic(false)), Magic::from_config(&c));
+    }
+}
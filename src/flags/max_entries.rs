@@ -0,0 +1,78 @@
+//! This module defines the [MaxEntries] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing the maximum number of entries listed per directory, the other ones being
+/// counted in a footer.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct MaxEntries(pub Option<usize>);
+
+impl Configurable<Self> for MaxEntries {
+    /// Get a potential `MaxEntries` value from [ArgMatches].
+    ///
+    /// If the "max-entries" argument is passed, this returns a `MaxEntries` with its value in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        let entries = matches.values_of("max-entries")?.next_back()?;
+        entries.parse().ok().map(|entries| Self(Some(entries)))
+    }
+
+    /// Get a potential `MaxEntries` value from a [Config].
+    ///
+    /// If the `Config::max_entries` has value, this returns it as the value of the `MaxEntries`,
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.max_entries.map(|entries| Self(Some(entries)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MaxEntries;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, MaxEntries::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_value() {
+        let argv = vec!["lsd", "--max-entries", "10"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(MaxEntries(Some(10))),
+            MaxEntries::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        for entries in &["0", "many"] {
+            let argv = vec!["lsd", "--max-entries", entries];
+            assert!(app::build().get_matches_from_safe(argv).is_err());
+        }
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, MaxEntries::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_value() {
+        let mut c = Config::with_none();
+        c.max_entries = Some(20);
+        assert_eq!(Some(MaxEntries(Some(20))), MaxEntries::from_config(&c));
+    }
+}
@@ -0,0 +1,78 @@
+//! This module defines the [MinColumns] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing the minimum number of columns of the grid layout, used when the entries fit
+/// in the width of the terminal.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct MinColumns(pub Option<usize>);
+
+impl Configurable<Self> for MinColumns {
+    /// Get a potential `MinColumns` value from [ArgMatches].
+    ///
+    /// If the "min-columns" argument is passed, this returns a `MinColumns` with its value in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        let columns = matches.values_of("min-columns")?.next_back()?;
+        columns.parse().ok().map(|columns| Self(Some(columns)))
+    }
+
+    /// Get a potential `MinColumns` value from a [Config].
+    ///
+    /// If the `Config::min_columns` has value, this returns it as the value of the `MinColumns`, in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.min_columns.map(|columns| Self(Some(columns)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MinColumns;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, MinColumns::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_value() {
+        let argv = vec!["lsd", "--min-columns", "10"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(MinColumns(Some(10))),
+            MinColumns::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        for columns in &["0", "wide"] {
+            let argv = vec!["lsd", "--min-columns", columns];
+            assert!(app::build().get_matches_from_safe(argv).is_err());
+        }
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, MinColumns::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_value() {
+        let mut c = Config::with_none();
+        c.min_columns = Some(20);
+        assert_eq!(Some(MinColumns(Some(20))), MinColumns::from_config(&c));
+    }
+}
@@ -0,0 +1,133 @@
+//! This module defines the [NameColors] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::block_colors::parse_colour;
+use super::Configurable;
+
+use crate::config_file::Config;
+use crate::print_error;
+
+use ansi_term::Colour;
+use clap::ArgMatches;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde_yaml::Value;
+
+/// The flag showing the colors of the names matching glob patterns, which replace the colors of
+/// the theme and of `LS_COLORS`.
+#[derive(Clone, Debug)]
+pub struct NameColors {
+    globs: GlobSet,
+    colors: Vec<Colour>,
+}
+
+impl NameColors {
+    /// Get the color of the first pattern matching a file name, if any.
+    pub fn get(&self, name: &str) -> Option<Colour> {
+        let index = self.globs.matches(name).into_iter().min()?;
+        Some(self.colors[index])
+    }
+}
+
+impl Default for NameColors {
+    fn default() -> Self {
+        Self {
+            globs: GlobSet::empty(),
+            colors: vec![],
+        }
+    }
+}
+
+impl Configurable<Self> for NameColors {
+    /// `NameColors` can not be configured by [ArgMatches]
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `NameColors` value from a [Config].
+    ///
+    /// If the `Config::name-colors` has value, this returns the colors of the valid patterns, in
+    /// the order of the configuration file, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        let name_colors = config.name_colors.as_ref()?;
+        let text = |value: &Value| match value {
+            Value::String(text) => Some(text.clone()),
+            Value::Number(number) => Some(number.to_string()),
+            _ => None,
+        };
+
+        let mut builder = GlobSetBuilder::new();
+        let mut colors = vec![];
+        for (pattern, value) in name_colors {
+            let (pattern, value) = match (text(pattern), text(value)) {
+                (Some(pattern), Some(value)) => (pattern, value),
+                _ => {
+                    print_error!("Not a valid name color: {:?}: {:?}.", pattern, value);
+                    continue;
+                }
+            };
+            let glob = match Glob::new(&pattern) {
+                Ok(glob) => glob,
+                Err(err) => {
+                    print_error!("{}.", err);
+                    continue;
+                }
+            };
+            match parse_colour(&value) {
+                Ok(colour) => {
+                    builder.add(glob);
+                    colors.push(colour);
+                }
+                Err(err) => {
+                    print_error!("{}, for the pattern '{}' in name-colors.", err, pattern)
+                }
+            }
+        }
+
+        match builder.build() {
+            Ok(globs) => Some(Self { globs, colors }),
+            Err(err) => {
+                print_error!("{}.", err);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NameColors;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use ansi_term::Colour;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(NameColors::from_arg_matches(&matches).is_none());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert!(NameColors::from_config(&Config::with_none()).is_none());
+    }
+
+    #[test]
+    fn test_from_config_first_match_wins() {
+        let c: Config = serde_yaml::from_str(
+            "name-colors:\n  '*.test.js': green\n  '*.js': 214\n  TODO: red\n  '[': blue",
+        )
+        .unwrap();
+        let colors = NameColors::from_config(&c).unwrap();
+
+        assert_eq!(Some(Colour::Green), colors.get("app.test.js"));
+        assert_eq!(Some(Colour::Fixed(214)), colors.get("app.js"));
+        assert_eq!(Some(Colour::Red), colors.get("TODO"));
+        assert_eq!(None, colors.get("README"));
+    }
+}
@@ -0,0 +1,86 @@
+//! This module defines the [NoHiddenRecurse] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to skip descending into hidden directories when recursing.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct NoHiddenRecurse(pub bool);
+
+impl Configurable<Self> for NoHiddenRecurse {
+    /// Get a potential `NoHiddenRecurse` value from [ArgMatches].
+    ///
+    /// If the "no-hidden-recurse" argument is passed, this returns a `NoHiddenRecurse` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("no-hidden-recurse") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `NoHiddenRecurse` value from a [Config].
+    ///
+    /// If the `Config::no-hidden-recurse` has value,
+    /// this returns it as the value of the `NoHiddenRecurse`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.no_hidden_recurse.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NoHiddenRecurse;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, NoHiddenRecurse::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--no-hidden-recurse"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(NoHiddenRecurse(true)),
+            NoHiddenRecurse::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, NoHiddenRecurse::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.no_hidden_recurse = Some(true);
+        assert_eq!(
+            Some(NoHiddenRecurse(true)),
+            NoHiddenRecurse::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.no_hidden_recurse = Some(false);
+        assert_eq!(
+            Some(NoHiddenRecurse(false)),
+            NoHiddenRecurse::from_config(&c)
+        );
+    }
+}
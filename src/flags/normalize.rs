@@ -0,0 +1,67 @@
+//! This module defines the [Normalize] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to compose the names in the NFC form of Unicode before sorting and
+/// printing them, like the names typed in, whatever the form stored by the file system.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Normalize(pub bool);
+
+impl Configurable<Self> for Normalize {
+    /// Get a potential `Normalize` value from [ArgMatches].
+    ///
+    /// If the "normalize" argument is passed, this returns a `Normalize` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("normalize") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Normalize` value from a [Config].
+    ///
+    /// If the `Config::normalize` has value, this returns it as the value of the `Normalize`, in
+    /// a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.normalize.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Normalize;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Normalize::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--normalize"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Normalize(true)), Normalize::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(None, Normalize::from_config(&Config::with_none()));
+
+        let mut c = Config::with_none();
+        c.normalize = Some(true);
+        assert_eq!(Some(Normalize(true)), Normalize::from_config(&c));
+    }
+}
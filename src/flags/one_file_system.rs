@@ -0,0 +1,80 @@
+//! This module defines the [OneFileSystem] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to stay on the file system of the listed directory when recursing.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct OneFileSystem(pub bool);
+
+impl Configurable<Self> for OneFileSystem {
+    /// Get a potential `OneFileSystem` value from [ArgMatches].
+    ///
+    /// If the "one-file-system" argument is passed, this returns a `OneFileSystem` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("one-file-system") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `OneFileSystem` value from a [Config].
+    ///
+    /// If the `Config::one-file-system` has value,
+    /// this returns it as the value of the `OneFileSystem`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.one_file_system.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OneFileSystem;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, OneFileSystem::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--one-file-system"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(OneFileSystem(true)),
+            OneFileSystem::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, OneFileSystem::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.one_file_system = Some(true);
+        assert_eq!(Some(OneFileSystem(true)), OneFileSystem::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.one_file_system = Some(false);
+        assert_eq!(Some(OneFileSystem(false)), OneFileSystem::from_config(&c));
+    }
+}
@@ -0,0 +1,136 @@
+//! This module defines the [Output] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing which format to write the output in.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Output {
+    /// The variant to write the layout meant to be read in a terminal.
+    Text,
+    /// The variant to write one CSV record per entry, without colors nor icons.
+    Csv,
+    /// The variant to write the entries as a versioned JSON document, with stable field names.
+    Json,
+}
+
+impl Output {
+    /// Whether this output is meant to be read by a program rather than in a terminal.
+    pub fn is_machine_readable(self) -> bool {
+        self != Self::Text
+    }
+
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "text" => Some(Self::Text),
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => {
+                panic!(
+                    "Output can only be one of text, csv or json, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for Output {
+    /// Get a potential `Output` variant from [ArgMatches].
+    ///
+    /// If the "json" argument is passed, this returns [Output::Json] in a [Some]. Otherwise if
+    /// the "output" argument is passed, this returns the corresponding `Output` variant in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("json") {
+            return Some(Self::Json);
+        }
+        if matches.occurrences_of("output") > 0 {
+            if let Some(output) = matches.values_of("output")?.next_back() {
+                return Self::from_str(output);
+            }
+        }
+        None
+    }
+
+    /// Get a potential `Output` variant from a [Config].
+    ///
+    /// If the `Config::output` has value and is one of "text", "csv" or "json", this returns the
+    /// corresponding `Output` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.output
+    }
+}
+
+/// The default value for `Output` is [Output::Text].
+impl Default for Output {
+    fn default() -> Self {
+        Self::Text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Output;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Output::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_csv() {
+        let argv = vec!["lsd", "--output", "csv"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Output::Csv), Output::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_json() {
+        let argv = vec!["lsd", "--json"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Output::Json), Output::from_arg_matches(&matches));
+
+        let argv = vec!["lsd", "--output", "json"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Output::Json), Output::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_multi() {
+        let argv = vec!["lsd", "--output", "csv", "--output", "text"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Output::Text), Output::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Output::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_csv() {
+        let mut c = Config::with_none();
+        c.output = Some(Output::Csv);
+        assert_eq!(Some(Output::Csv), Output::from_config(&c));
+    }
+
+    #[test]
+    fn test_is_machine_readable() {
+        assert!(!Output::Text.is_machine_readable());
+        assert!(Output::Csv.is_machine_readable());
+        assert!(Output::Json.is_machine_readable());
+    }
+}
@@ -0,0 +1,116 @@
+//! This module defines the [Paging] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing when to write the output through a pager.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Paging {
+    /// The variant to page the output when it is taller than the terminal.
+    Auto,
+    /// The variant to always page the output.
+    Always,
+    /// The variant to never page the output.
+    Never,
+}
+
+impl Paging {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => {
+                panic!(
+                    "Paging can only be one of auto, always or never, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for Paging {
+    /// Get a potential `Paging` variant from [ArgMatches].
+    ///
+    /// If the "paging" argument is passed, this returns the corresponding `Paging` variant in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("paging") > 0 {
+            if let Some(output) = matches.values_of("paging")?.next_back() {
+                return Self::from_str(output);
+            }
+        }
+        None
+    }
+
+    /// Get a potential `Paging` variant from a [Config].
+    ///
+    /// If the `Config::paging` has value and is one of "auto", "always" or "never", this returns the
+    /// corresponding `Paging` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.paging
+    }
+}
+
+/// The default value for `Paging` is [Paging::Never].
+impl Default for Paging {
+    fn default() -> Self {
+        Self::Never
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Paging;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Paging::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_always() {
+        let argv = vec!["lsd", "--paging", "always"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Paging::Always), Paging::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_auto() {
+        let argv = vec!["lsd", "--paging=auto"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Paging::Auto), Paging::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_multi() {
+        let argv = vec!["lsd", "--paging", "always", "--paging", "never"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Paging::Never), Paging::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Paging::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_auto() {
+        let mut c = Config::with_none();
+        c.paging = Some(Paging::Auto);
+        assert_eq!(Some(Paging::Auto), Paging::from_config(&c));
+    }
+}
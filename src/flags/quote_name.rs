@@ -0,0 +1,77 @@
+//! This module defines the [QuoteName] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to wrap the names in double quotes, escaping them as C strings.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct QuoteName(pub bool);
+
+impl Configurable<Self> for QuoteName {
+    /// Get a potential `QuoteName` value from [ArgMatches].
+    ///
+    /// If the "quote-name" argument is passed, this returns a `QuoteName` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("quote-name") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `QuoteName` value from a [Config].
+    ///
+    /// If the `Config::quote-name` has value,
+    /// this returns it as the value of the `QuoteName`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.quote_name.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::QuoteName;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, QuoteName::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--quote-name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(QuoteName(true)), QuoteName::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, QuoteName::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.quote_name = Some(true);
+        assert_eq!(Some(QuoteName(true)), QuoteName::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.quote_name = Some(false);
+        assert_eq!(Some(QuoteName(false)), QuoteName::from_config(&c));
+    }
+}
@@ -0,0 +1,119 @@
+//! This module defines the [QuotingStyle] flag. To set it up from [ArgMatches], a [Config] and
+//! its [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing how to quote and escape the names.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuotingStyle {
+    /// The variant to escape the names according to `--quote-name` and `--show-control-chars`.
+    Auto,
+    /// The variant to print the names as they are.
+    Literal,
+    /// The variant to escape the names as C strings, without quotes.
+    Escape,
+    /// The variant to escape the names as C strings, within double quotes.
+    C,
+    /// The variant to escape the names as C strings, within the quotation marks ‘ and ’.
+    Locale,
+}
+
+impl QuotingStyle {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "literal" => Some(Self::Literal),
+            "escape" => Some(Self::Escape),
+            "c" => Some(Self::C),
+            "locale" => Some(Self::Locale),
+            _ => {
+                panic!(
+                    "Quoting style can only be one of literal, escape, c or locale, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for QuotingStyle {
+    /// Get a potential `QuotingStyle` variant from [ArgMatches].
+    ///
+    /// If the "quoting-style" argument is passed, this returns the corresponding `QuotingStyle`
+    /// variant in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("quoting-style") > 0 {
+            if let Some(style) = matches.values_of("quoting-style")?.next_back() {
+                return Self::from_str(style);
+            }
+        }
+        None
+    }
+
+    /// Get a potential `QuotingStyle` variant from a [Config].
+    ///
+    /// If the `Config::quoting-style` has value, this returns it in a [Some]. Otherwise this
+    /// returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.quoting_style
+    }
+}
+
+/// The default value for `QuotingStyle` is [QuotingStyle::Auto].
+impl Default for QuotingStyle {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::QuotingStyle;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, QuotingStyle::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_locale() {
+        let argv = vec!["lsd", "--quoting-style", "locale"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(QuotingStyle::Locale),
+            QuotingStyle::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_multi() {
+        let argv = vec!["lsd", "--quoting-style", "c", "--quoting-style", "literal"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(QuotingStyle::Literal),
+            QuotingStyle::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, QuotingStyle::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_escape() {
+        let c: Config = serde_yaml::from_str("quoting-style: escape").unwrap();
+        assert_eq!(Some(QuotingStyle::Escape), QuotingStyle::from_config(&c));
+    }
+}
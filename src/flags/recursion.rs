@@ -4,21 +4,44 @@
 use crate::config_file::Config;
 
 use clap::{ArgMatches, Error, ErrorKind};
+use serde::Deserialize;
 
 /// The options relating to recursion.
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
 pub struct Recursion {
     /// Whether the recursion into directories is enabled.
     pub enabled: bool,
-    /// The depth for how far to recurse into directories.
+    /// The depth for how far to recurse into directories. The listed directory itself is not
+    /// counted, so a depth of 1 only lists its immediate children, and a depth of 0 only lists
+    /// the given entries themselves.
     pub depth: usize,
+    /// Whether a directory is listed before or after its subdirectories.
+    pub order: RecursionOrder,
+}
+
+/// The order a directory is listed in, relatively to its subdirectories.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RecursionOrder {
+    /// The directory is listed before walking into its subdirectories.
+    Pre,
+    /// The directory is listed after its subdirectories, eg: to remove them in order.
+    Post,
+}
+
+/// The default value for `RecursionOrder` is [RecursionOrder::Pre].
+impl Default for RecursionOrder {
+    fn default() -> Self {
+        Self::Pre
+    }
 }
 
 impl Recursion {
     /// Get the Recursion from either [ArgMatches], a [Config] or the [Default] value.
     ///
-    /// The "enabled" value is determined by [enabled_from](Recursion::enabled_from) and the depth
-    /// value is determined by [depth_from](Recursion::depth_from).
+    /// The "enabled" value is determined by [enabled_from](Recursion::enabled_from), the depth
+    /// value by [depth_from](Recursion::depth_from) and the order by
+    /// [order_from](Recursion::order_from).
     ///
     /// # Errors
     ///
@@ -26,7 +49,29 @@ impl Recursion {
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
         let enabled = Self::enabled_from(matches, config);
         let depth = Self::depth_from(matches, config)?;
-        Ok(Self { enabled, depth })
+        let order = Self::order_from(matches, config);
+        Ok(Self {
+            enabled,
+            depth,
+            order,
+        })
+    }
+
+    /// Get the [RecursionOrder] from [ArgMatches], a [Config] or the [Default] value. The first
+    /// value that is not [None] is used. The order of precedence for the value used is:
+    /// - the last "recurse-order" argument
+    /// - [Config.recursion.order]
+    /// - [Default::default]
+    fn order_from(matches: &ArgMatches, config: &Config) -> RecursionOrder {
+        match matches.values_of("recurse-order").and_then(Iterator::last) {
+            Some("post") => RecursionOrder::Post,
+            Some(_) => RecursionOrder::Pre,
+            None => config
+                .recursion
+                .as_ref()
+                .and_then(|recursion| recursion.order)
+                .unwrap_or_default(),
+        }
     }
 
     /// Get the "enabled" boolean from [ArgMatches], a [Config] or the [Default] value. The first
@@ -89,7 +134,7 @@ impl Recursion {
 
     /// Get a potential "depth" value from [ArgMatches].
     ///
-    /// If the "depth" argument is passed, its parameter is evaluated. If it can be parsed into a
+    /// If the "depth" (or its "max-depth" alias) argument is passed, its parameter is evaluated. If it can be parsed into a
     /// [usize], the [Result] is returned in the [Some]. If it can not be parsed an [Error] is
     /// returned in the [Some]. If the argument has not been passed, a [None] is returned.
     ///
@@ -117,19 +162,21 @@ impl Recursion {
     }
 }
 
-/// The default values for `Recursion` are the boolean default and [prim@usize::max_value()].
+/// The default values for `Recursion` are the boolean default, [prim@usize::max_value()] and
+/// [RecursionOrder::Pre].
 impl Default for Recursion {
     fn default() -> Self {
         Self {
             depth: usize::max_value(),
             enabled: false,
+            order: RecursionOrder::default(),
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::Recursion;
+    use super::{Recursion, RecursionOrder};
 
     use crate::app;
     use crate::config_file::{self, Config};
@@ -169,6 +216,7 @@ mod test {
         c.recursion = Some(config_file::Recursion {
             enabled: Some(true),
             depth: None,
+            order: None,
         });
         assert_eq!(
             true,
@@ -183,6 +231,7 @@ mod test {
         c.recursion = Some(config_file::Recursion {
             enabled: Some(false),
             depth: None,
+            order: None,
         });
         assert_eq!(
             false,
@@ -190,6 +239,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_order_from_arg_matches_and_config() {
+        let mut c = Config::with_none();
+        let matches = app::build().get_matches_from_safe(vec!["lsd"]).unwrap();
+        assert_eq!(RecursionOrder::Pre, Recursion::order_from(&matches, &c));
+
+        c.recursion = Some(config_file::Recursion {
+            enabled: None,
+            depth: None,
+            order: Some(RecursionOrder::Post),
+        });
+        assert_eq!(RecursionOrder::Post, Recursion::order_from(&matches, &c));
+
+        let argv = vec!["lsd", "--recurse-order", "post", "--recurse-order", "pre"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(RecursionOrder::Pre, Recursion::order_from(&matches, &c));
+    }
+
     // The following depth_from_arg_matches tests are implemented using match expressions instead
     // of the assert_eq macro, because clap::Error does not implement PartialEq.
 
@@ -233,6 +300,26 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_depth_from_arg_matches_max_depth_alias() {
+        let argv = vec!["lsd", "--max-depth", "3"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match Recursion::depth_from_arg_matches(&matches) {
+            Some(Ok(value)) => value == 3,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_depth_from_arg_matches_before_file() {
+        let argv = vec!["lsd", "--depth", "2", "folder"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match Recursion::depth_from_arg_matches(&matches) {
+            Some(Ok(value)) => value == 2,
+            _ => false,
+        });
+    }
+
     #[test]
     fn test_depth_from_arg_matches_neg_int() {
         let argv = vec!["lsd", "--depth", "\\-42"];
@@ -283,6 +370,7 @@ mod test {
         c.recursion = Some(config_file::Recursion {
             enabled: None,
             depth: Some(42),
+            order: None,
         });
         assert_eq!(
             42,
@@ -0,0 +1,90 @@
+//! This module defines the [RespectGitattributes] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to hide the files with the `export-ignore` attribute in the
+/// `.gitattributes` files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct RespectGitattributes(pub bool);
+
+impl Configurable<Self> for RespectGitattributes {
+    /// Get a potential `RespectGitattributes` value from [ArgMatches].
+    ///
+    /// If the "respect-gitattributes" argument is passed, this returns a `RespectGitattributes` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("respect-gitattributes") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `RespectGitattributes` value from a [Config].
+    ///
+    /// If the `Config::respect-gitattributes` has value,
+    /// this returns it as the value of the `RespectGitattributes`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.respect_gitattributes.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RespectGitattributes;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, RespectGitattributes::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--respect-gitattributes"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(RespectGitattributes(true)),
+            RespectGitattributes::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(
+            None,
+            RespectGitattributes::from_config(&Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.respect_gitattributes = Some(true);
+        assert_eq!(
+            Some(RespectGitattributes(true)),
+            RespectGitattributes::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.respect_gitattributes = Some(false);
+        assert_eq!(
+            Some(RespectGitattributes(false)),
+            RespectGitattributes::from_config(&c)
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! This module defines the [SetTitle] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to set the title of the terminal to the listed paths.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct SetTitle(pub bool);
+
+impl Configurable<Self> for SetTitle {
+    /// Get a potential `SetTitle` value from [ArgMatches].
+    ///
+    /// If the "set-title" argument is passed, this returns a `SetTitle` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("set-title") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `SetTitle` value from a [Config].
+    ///
+    /// If the `Config::set-title` has value,
+    /// this returns it as the value of the `SetTitle`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.set_title.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SetTitle;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SetTitle::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--set-title"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(SetTitle(true)), SetTitle::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SetTitle::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.set_title = Some(true);
+        assert_eq!(Some(SetTitle(true)), SetTitle::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.set_title = Some(false);
+        assert_eq!(Some(SetTitle(false)), SetTitle::from_config(&c));
+    }
+}
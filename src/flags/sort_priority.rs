@@ -0,0 +1,102 @@
+//! This module defines the [SortPriority] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+use crate::print_error;
+
+use clap::ArgMatches;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+/// The flag showing the names or glob patterns of the entries sorted first, in the order of the
+/// patterns, before the other entries sorted as usual.
+#[derive(Clone, Debug)]
+pub struct SortPriority(GlobSet);
+
+impl SortPriority {
+    /// Get the rank of the first pattern matching a file name, if any.
+    pub fn rank(&self, name: &str) -> Option<usize> {
+        self.0.matches(name).into_iter().min()
+    }
+
+    /// Whether no pattern is given, so that the entries are sorted as usual.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Default for SortPriority {
+    fn default() -> Self {
+        Self(GlobSet::empty())
+    }
+}
+
+impl Configurable<Self> for SortPriority {
+    /// `SortPriority` can not be configured by [ArgMatches]
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `SortPriority` value from a [Config].
+    ///
+    /// If the `Config::sort-priority` has value, this returns its valid patterns, in the order
+    /// of the configuration file, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        let patterns = config.sort_priority.as_ref()?;
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            match Glob::new(pattern) {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(err) => print_error!("{}.", err),
+            }
+        }
+
+        match builder.build() {
+            Ok(globs) => Some(Self(globs)),
+            Err(err) => {
+                print_error!("{}.", err);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SortPriority;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(SortPriority::from_arg_matches(&matches).is_none());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert!(SortPriority::from_config(&Config::with_none()).is_none());
+    }
+
+    #[test]
+    fn test_from_config_rank_of_first_match() {
+        let c: Config =
+            serde_yaml::from_str("sort-priority:\n  - README*\n  - '*.md'\n  - '['\n  - src")
+                .unwrap();
+        let priority = SortPriority::from_config(&c).unwrap();
+
+        assert_eq!(Some(0), priority.rank("README.md"));
+        assert_eq!(Some(1), priority.rank("CHANGELOG.md"));
+        assert_eq!(Some(2), priority.rank("src"));
+        assert_eq!(None, priority.rank("Cargo.toml"));
+    }
+}
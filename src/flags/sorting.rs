@@ -4,31 +4,62 @@
 use super::Configurable;
 
 use crate::config_file::Config;
+use crate::print_error;
 
 use clap::ArgMatches;
 use serde::Deserialize;
 
 /// A collection of flags on how to sort the output.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Sorting {
     pub column: SortColumn,
+    pub keys: SortKeys,
     pub order: SortOrder,
     pub dir_grouping: DirGrouping,
+    pub dir_column: DirColumn,
+    pub dotfiles: DotFiles,
+    pub natural_locale: NaturalLocale,
+    pub case_sensitive_extensions: CaseSensitiveExtensions,
+    pub seed: Seed,
 }
 
 impl Sorting {
+    /// Whether the entries are listed in the order of the directory, `none` being the only
+    /// sort column.
+    pub fn is_unsorted(&self) -> bool {
+        self.column == SortColumn::None
+            && self
+                .keys
+                .0
+                .iter()
+                .all(|(column, _)| *column == SortColumn::None)
+    }
+
     /// Get a `Sorting` struct from [ArgMatches], a [Config] or the [Default] values.
     ///
-    /// The [SortColumn], [SortOrder] and [DirGrouping] are configured with their respective
+    /// The [SortColumn], [SortKeys], [SortOrder], [DirGrouping], [DirColumn], [DotFiles],
+    /// [NaturalLocale], [CaseSensitiveExtensions] and [Seed] are configured with their respective
     /// [Configurable] implementation.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
         let column = SortColumn::configure_from(matches, config);
+        let keys = SortKeys::configure_from(matches, config);
         let order = SortOrder::configure_from(matches, config);
         let dir_grouping = DirGrouping::configure_from(matches, config);
+        let dir_column = DirColumn::configure_from(matches, config);
+        let dotfiles = DotFiles::configure_from(matches, config);
+        let natural_locale = NaturalLocale::configure_from(matches, config).resolve();
+        let case_sensitive_extensions = CaseSensitiveExtensions::configure_from(matches, config);
+        let seed = Seed::configure_from(matches, config);
         Self {
             column,
+            keys,
             order,
             dir_grouping,
+            dir_column,
+            dotfiles,
+            natural_locale,
+            case_sensitive_extensions,
+            seed,
         }
     }
 }
@@ -42,34 +73,42 @@ pub enum SortColumn {
     Time,
     Size,
     Version,
+    Owner,
+    Group,
+    Inode,
+    Random,
+    Recent,
+    /// The entries are listed in the order of the directory.
+    None,
 }
 
 impl Configurable<Self> for SortColumn {
     /// Get a potential `SortColumn` variant from [ArgMatches].
     ///
     /// If either the "timesort" or "sizesort" arguments are passed, this returns the corresponding
-    /// `SortColumn` variant in a [Some]. Otherwise this returns [None].
+    /// `SortColumn` variant in a [Some]. If the "sort" argument is passed, this returns the column
+    /// of its first key in a [Some]. Otherwise this returns [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
-        let sort = match matches.values_of("sort") {
-            Some(s) => s.last(),
+        let sort = match SortKeys::from_arg_matches(matches) {
+            Some(keys) => keys.0.first().map(|(column, _)| *column),
             None => None,
         };
-        if matches.is_present("timesort") || sort == Some("time") {
+        if matches.is_present("timesort") || sort == Some(Self::Time) {
             Some(Self::Time)
-        } else if matches.is_present("sizesort") || sort == Some("size") {
+        } else if matches.is_present("sizesort") || sort == Some(Self::Size) {
             Some(Self::Size)
-        } else if matches.is_present("extensionsort") || sort == Some("extension") {
+        } else if matches.is_present("extensionsort") || sort == Some(Self::Extension) {
             Some(Self::Extension)
-        } else if matches.is_present("versionsort") || sort == Some("version") {
+        } else if matches.is_present("versionsort") || sort == Some(Self::Version) {
             Some(Self::Version)
         } else {
-            None
+            sort
         }
     }
 
     /// Get a potential `SortColumn` variant from a [Config].
     ///
-    /// If the `Config::sorting::column` has value and is one of "time", "size" or "name",
+    /// If the `Config::sorting::column` has value and is one of the sort columns,
     /// this returns the corresponding variant in a [Some].
     /// Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
@@ -81,6 +120,81 @@ impl Configurable<Self> for SortColumn {
     }
 }
 
+impl SortColumn {
+    /// Every [SortColumn] variant, in the order they are listed to the user.
+    const ALL: [SortColumn; 11] = [
+        SortColumn::Name,
+        SortColumn::Size,
+        SortColumn::Time,
+        SortColumn::Version,
+        SortColumn::Extension,
+        SortColumn::Owner,
+        SortColumn::Group,
+        SortColumn::Inode,
+        SortColumn::Random,
+        SortColumn::Recent,
+        SortColumn::None,
+    ];
+
+    /// The name used to select this column in the arguments and the configuration file.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Size => "size",
+            Self::Time => "time",
+            Self::Version => "version",
+            Self::Extension => "extension",
+            Self::Owner => "owner",
+            Self::Group => "group",
+            Self::Inode => "inode",
+            Self::Random => "random",
+            Self::Recent => "recent",
+            Self::None => "none",
+        }
+    }
+
+    /// A one-line description of how this column sorts the entries.
+    fn description(self) -> &'static str {
+        match self {
+            Self::Name => "Sort by name (default)",
+            Self::Size => "Sort by size, largest first",
+            Self::Time => "Sort by time modified, newest first",
+            Self::Version => "Natural sort of (version) numbers",
+            Self::Extension => "Sort by file extension",
+            Self::Owner => "Sort by owner name, then group name",
+            Self::Group => "Sort by group name",
+            Self::Inode => "Sort by inode number",
+            Self::Random => "Shuffle the entries, in the same order with --seed",
+            Self::Recent => {
+                "Sort by the latest of the times accessed, modified and changed, newest first"
+            }
+            Self::None => "Do not sort, list the entries in the order of the directory",
+        }
+    }
+
+    /// Get a value from the name of a column.
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|column| column.name() == name)
+    }
+
+    /// Whether this column sorts the largest or newest entries first by default.
+    fn is_descending_by_default(self) -> bool {
+        matches!(self, Self::Size | Self::Time | Self::Recent)
+    }
+
+    /// Lists every sort column with its description, one per line.
+    pub fn list() -> String {
+        let width = Self::ALL.iter().map(|c| c.name().len()).max().unwrap_or(0);
+        Self::ALL
+            .iter()
+            .map(|c| format!("{:width$}  {}\n", c.name(), c.description(), width = width))
+            .collect()
+    }
+}
+
 /// The default value for `SortColumn` is [SortColumn::Name].
 impl Default for SortColumn {
     fn default() -> Self {
@@ -88,6 +202,55 @@ impl Default for SortColumn {
     }
 }
 
+/// The sort keys, given as a comma separated list to the "sort" argument, along with the order
+/// to apply to the comparator of each column.
+///
+/// A key prefixed with `-` sorts in descending order, and a key prefixed with `+` sorts in
+/// ascending order. Otherwise the key sorts in the default order of its column, which is
+/// descending for size and time, and ascending for the others.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SortKeys(pub Vec<(SortColumn, SortOrder)>);
+
+impl SortKeys {
+    /// Get a value from a comma separated list of keys, or [None] if any key is not valid.
+    fn from_str(value: &str) -> Option<Self> {
+        value
+            .split(',')
+            .map(|key| {
+                let (descending, name) = match key.chars().next() {
+                    Some('-') => (Some(true), &key[1..]),
+                    Some('+') => (Some(false), &key[1..]),
+                    _ => (None, key),
+                };
+                let column = SortColumn::from_name(name)?;
+                let order = match descending {
+                    Some(descending) if descending != column.is_descending_by_default() => {
+                        SortOrder::Reverse
+                    }
+                    _ => SortOrder::Default,
+                };
+                Some((column, order))
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(Self)
+    }
+}
+
+impl Configurable<Self> for SortKeys {
+    /// Get potential `SortKeys` from [ArgMatches].
+    ///
+    /// If the "sort" argument is passed, this returns the keys of its last value in a [Some].
+    /// Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        Self::from_str(matches.values_of("sort")?.next_back()?)
+    }
+
+    /// The sort keys can not be configured in a [Config], so this always returns [None].
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
 /// The flag showing which sort order to use.
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
 pub enum SortOrder {
@@ -164,19 +327,33 @@ impl Configurable<Self> for DirGrouping {
     /// Get a potential `DirGrouping` variant from [ArgMatches].
     ///
     /// If the "classic" argument is passed, then this returns the [DirGrouping::None] variant in a
-    /// [Some]. Otherwise if the argument is passed, this returns the variant corresponding to its
-    /// parameter in a [Some]. Otherwise this returns [None].
+    /// [Some]. Otherwise if the "group-dirs", "group-directories-first" or
+    /// "group-directories-last" arguments are passed, this returns the variant corresponding to
+    /// the last one in a [Some]. Otherwise this returns [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
         if matches.is_present("classic") {
             return Some(Self::None);
         }
 
+        let last_index = |arg| matches.indices_of(arg).and_then(|indices| indices.last());
+        let mut candidates = vec![
+            (last_index("group-directories-first"), Some(Self::First)),
+            (last_index("group-directories-last"), Some(Self::Last)),
+        ];
         if matches.occurrences_of("group-dirs") > 0 {
-            if let Some(group_dirs) = matches.values_of("group-dirs")?.last() {
-                return Self::from_str(group_dirs);
-            }
+            candidates.push((
+                last_index("group-dirs"),
+                matches
+                    .values_of("group-dirs")
+                    .and_then(|values| values.last())
+                    .and_then(Self::from_str),
+            ));
         }
-        None
+        candidates
+            .into_iter()
+            .filter(|(index, _)| index.is_some())
+            .max_by_key(|(index, _)| *index)
+            .and_then(|(_, dir_grouping)| dir_grouping)
     }
 
     /// Get a potential `DirGrouping` variant from a [Config].
@@ -204,6 +381,174 @@ impl Default for DirGrouping {
     }
 }
 
+/// The flag showing where to place the files whose name starts with a dot.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DotFiles {
+    Mixed,
+    First,
+    Last,
+}
+
+impl Configurable<Self> for DotFiles {
+    /// Get a potential `DotFiles` variant from [ArgMatches].
+    ///
+    /// If the "dotfiles" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        match matches.values_of("dotfiles")?.next_back() {
+            Some("mixed") => Some(Self::Mixed),
+            Some("first") => Some(Self::First),
+            Some("last") => Some(Self::Last),
+            _ => None,
+        }
+    }
+
+    /// Get a potential `DotFiles` variant from a [Config].
+    ///
+    /// If `Config::sorting::dotfiles` has value, this returns it in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.sorting.as_ref()?.dotfiles
+    }
+}
+
+/// The default value for `DotFiles` is [DotFiles::Mixed], sorting the dotfiles along with the
+/// other files.
+impl Default for DotFiles {
+    fn default() -> Self {
+        Self::Mixed
+    }
+}
+
+/// The flag showing whether to sort names with the Unicode collation algorithm, so that accented
+/// letters sort near their base letters.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct NaturalLocale(pub bool);
+
+impl NaturalLocale {
+    /// Keep the locale sort when lsd is built with the `natural-locale` feature, or report that
+    /// it is ignored.
+    fn resolve(self) -> Self {
+        if self.0 && !cfg!(feature = "natural-locale") {
+            print_error!(
+                "lsd is built without the natural-locale feature, the locale sort is ignored."
+            );
+            return Self(false);
+        }
+        self
+    }
+}
+
+impl Configurable<Self> for NaturalLocale {
+    /// Get a potential `NaturalLocale` value from [ArgMatches].
+    ///
+    /// If the "natural-locale" argument is passed, this returns a `NaturalLocale` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("natural-locale") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `NaturalLocale` value from a [Config].
+    ///
+    /// If the `Config::sorting::natural-locale` has value,
+    /// this returns it as the value of the `NaturalLocale`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .sorting
+            .as_ref()
+            .and_then(|sort| sort.natural_locale)
+            .map(Self)
+    }
+}
+
+/// The flag showing whether to sort and group the extensions which differ in case apart, like
+/// `.JPG` and `.jpg`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct CaseSensitiveExtensions(pub bool);
+
+impl Configurable<Self> for CaseSensitiveExtensions {
+    /// Get a potential `CaseSensitiveExtensions` value from [ArgMatches].
+    ///
+    /// If the "case-sensitive-extensions" argument is passed, this returns a
+    /// `CaseSensitiveExtensions` with value `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("case-sensitive-extensions") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `CaseSensitiveExtensions` value from a [Config].
+    ///
+    /// If the `Config::sorting::case-sensitive-extensions` has value,
+    /// this returns it as the value of the `CaseSensitiveExtensions`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .sorting
+            .as_ref()
+            .and_then(|sort| sort.case_sensitive_extensions)
+            .map(Self)
+    }
+}
+
+/// The flag showing the column the directories are sorted by, independently of the files, when
+/// they are grouped with [DirGrouping]. Without it, the directories are sorted like the files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DirColumn(pub Option<SortColumn>);
+
+impl Configurable<Self> for DirColumn {
+    /// Get a potential `DirColumn` value from [ArgMatches].
+    ///
+    /// If the "sort-dirs" argument is passed, this returns a `DirColumn` with the corresponding
+    /// [SortColumn] in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        let name = matches.values_of("sort-dirs")?.next_back()?;
+        SortColumn::from_name(name).map(|column| Self(Some(column)))
+    }
+
+    /// Get a potential `DirColumn` value from a [Config].
+    ///
+    /// If the `Config::sorting::dir-column` has value, this returns it as the value of the
+    /// `DirColumn`, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .sorting
+            .as_ref()
+            .and_then(|sort| sort.dir_column)
+            .map(|column| Self(Some(column)))
+    }
+}
+
+/// The flag showing the seed of the shuffle of [SortColumn::Random], to list the entries in the
+/// same order on every run. Without it, the entries are shuffled differently on every run.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Seed(pub Option<u64>);
+
+impl Configurable<Self> for Seed {
+    /// Get a potential `Seed` value from [ArgMatches].
+    ///
+    /// If the "seed" argument is passed, this returns a `Seed` with its value in a [Some].
+    /// Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        let seed = matches.values_of("seed")?.next_back()?;
+        seed.parse().ok().map(|seed| Self(Some(seed)))
+    }
+
+    /// A seed is meant to reproduce a single listing, so it can not be configured in a [Config]
+    /// and this always returns [None].
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
 #[cfg(test)]
 mod test_sort_column {
     use super::SortColumn;
@@ -300,6 +645,54 @@ mod test_sort_column {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_sort_name() {
+        let argv = vec!["lsd", "--sort", "name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortColumn::Name),
+            SortColumn::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_sort_keys_first() {
+        let argv = vec!["lsd", "--sort", "-size,name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortColumn::Size),
+            SortColumn::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_none_column() {
+        let argv = vec!["lsd", "--sort", "none"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortColumn::None),
+            SortColumn::from_arg_matches(&matches)
+        );
+        let sorting = super::Sorting::configure_from(&matches, &Config::with_none());
+        assert!(sorting.is_unsorted());
+
+        let argv = vec!["lsd", "--sort", "none,name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let sorting = super::Sorting::configure_from(&matches, &Config::with_none());
+        assert!(!sorting.is_unsorted());
+    }
+
+    #[test]
+    fn test_sort_before_file() {
+        let argv = vec!["lsd", "--sort", "size", "foo"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortColumn::Size),
+            SortColumn::from_arg_matches(&matches)
+        );
+        assert_eq!(Some("foo"), matches.value_of("FILE"));
+    }
+
     #[test]
     fn test_multi_sort_use_last() {
         let argv = vec!["lsd", "--sort", "size", "-t", "-S", "-X", "--sort", "time"];
@@ -322,6 +715,10 @@ mod test_sort_column {
             column: None,
             reverse: None,
             dir_grouping: None,
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
 
         assert_eq!(None, SortColumn::from_config(&c));
@@ -334,6 +731,10 @@ mod test_sort_column {
             column: Some(SortColumn::Extension),
             reverse: None,
             dir_grouping: None,
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         assert_eq!(Some(SortColumn::Extension), SortColumn::from_config(&c));
     }
@@ -345,6 +746,10 @@ mod test_sort_column {
             column: Some(SortColumn::Name),
             reverse: None,
             dir_grouping: None,
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         assert_eq!(Some(SortColumn::Name), SortColumn::from_config(&c));
     }
@@ -356,6 +761,10 @@ mod test_sort_column {
             column: Some(SortColumn::Time),
             reverse: None,
             dir_grouping: None,
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         assert_eq!(Some(SortColumn::Time), SortColumn::from_config(&c));
     }
@@ -367,6 +776,10 @@ mod test_sort_column {
             column: Some(SortColumn::Size),
             reverse: None,
             dir_grouping: None,
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         assert_eq!(Some(SortColumn::Size), SortColumn::from_config(&c));
     }
@@ -378,6 +791,10 @@ mod test_sort_column {
             column: Some(SortColumn::Version),
             reverse: None,
             dir_grouping: None,
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         assert_eq!(Some(SortColumn::Version), SortColumn::from_config(&c));
     }
@@ -428,6 +845,10 @@ mod test_sort_order {
             column: None,
             reverse: None,
             dir_grouping: None,
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         assert_eq!(None, SortOrder::from_config(&c));
     }
@@ -439,6 +860,10 @@ mod test_sort_order {
             column: None,
             reverse: Some(true),
             dir_grouping: None,
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         assert_eq!(Some(SortOrder::Reverse), SortOrder::from_config(&c));
     }
@@ -450,6 +875,10 @@ mod test_sort_order {
             column: None,
             reverse: Some(false),
             dir_grouping: None,
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         assert_eq!(Some(SortOrder::Default), SortOrder::from_config(&c));
     }
@@ -518,6 +947,33 @@ mod test_dir_grouping {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_group_directories_first_and_last() {
+        for (argv, expected) in &[
+            (vec!["lsd", "--group-directories-first"], DirGrouping::First),
+            (vec!["lsd", "--group-directories-last"], DirGrouping::Last),
+            (
+                vec![
+                    "lsd",
+                    "--group-directories-first",
+                    "--group-directories-last",
+                ],
+                DirGrouping::Last,
+            ),
+            (
+                vec!["lsd", "--group-directories-last", "--group-dirs", "none"],
+                DirGrouping::None,
+            ),
+            (
+                vec!["lsd", "--group-dirs", "none", "--group-directories-first"],
+                DirGrouping::First,
+            ),
+        ] {
+            let matches = app::build().get_matches_from_safe(argv.clone()).unwrap();
+            assert_eq!(Some(*expected), DirGrouping::from_arg_matches(&matches));
+        }
+    }
+
     #[test]
     fn test_from_arg_matches_group_dirs_multi() {
         let argv = vec!["lsd", "--group-dirs", "first", "--group-dirs", "last"];
@@ -540,6 +996,10 @@ mod test_dir_grouping {
             column: None,
             reverse: None,
             dir_grouping: Some(DirGrouping::First),
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         assert_eq!(Some(DirGrouping::First), DirGrouping::from_config(&c));
     }
@@ -551,6 +1011,10 @@ mod test_dir_grouping {
             column: None,
             reverse: None,
             dir_grouping: Some(DirGrouping::Last),
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         assert_eq!(Some(DirGrouping::Last), DirGrouping::from_config(&c));
     }
@@ -562,6 +1026,10 @@ mod test_dir_grouping {
             column: None,
             reverse: None,
             dir_grouping: None,
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         assert_eq!(None, DirGrouping::from_config(&c));
     }
@@ -573,8 +1041,305 @@ mod test_dir_grouping {
             column: None,
             reverse: None,
             dir_grouping: Some(DirGrouping::Last),
+            dotfiles: None,
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
         });
         c.classic = Some(true);
         assert_eq!(Some(DirGrouping::None), DirGrouping::from_config(&c));
     }
 }
+
+#[cfg(test)]
+mod test_dir_column {
+    use super::{DirColumn, SortColumn};
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DirColumn::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_name() {
+        let argv = vec!["lsd", "--sort-dirs", "name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DirColumn(Some(SortColumn::Name))),
+            DirColumn::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_time() {
+        let c: Config = serde_yaml::from_str("sorting:\n  dir-column: time").unwrap();
+        assert_eq!(
+            Some(DirColumn(Some(SortColumn::Time))),
+            DirColumn::from_config(&c)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_natural_locale {
+    use super::NaturalLocale;
+
+    use crate::app;
+    use crate::config_file::{Config, Sorting};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, NaturalLocale::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--natural-locale"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(NaturalLocale(true)),
+            NaturalLocale::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        assert_eq!(None, NaturalLocale::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            dir_grouping: None,
+            dotfiles: None,
+            natural_locale: Some(true),
+            dir_column: None,
+            case_sensitive_extensions: None,
+        });
+        assert_eq!(Some(NaturalLocale(true)), NaturalLocale::from_config(&c));
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(NaturalLocale(false), NaturalLocale(false).resolve());
+        // The locale sort is only kept when lsd is built with the collation.
+        assert_eq!(
+            NaturalLocale(cfg!(feature = "natural-locale")),
+            NaturalLocale(true).resolve()
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_case_sensitive_extensions {
+    use super::CaseSensitiveExtensions;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, CaseSensitiveExtensions::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--case-sensitive-extensions"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(CaseSensitiveExtensions(true)),
+            CaseSensitiveExtensions::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(
+            None,
+            CaseSensitiveExtensions::from_config(&Config::with_none())
+        );
+
+        let c: Config =
+            serde_yaml::from_str("sorting:\n  case-sensitive-extensions: true").unwrap();
+        assert_eq!(
+            Some(CaseSensitiveExtensions(true)),
+            CaseSensitiveExtensions::from_config(&c)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_sort_keys {
+    use super::{SortColumn, SortKeys, SortOrder};
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SortKeys::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_single() {
+        let argv = vec!["lsd", "--sort", "time"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortKeys(vec![(SortColumn::Time, SortOrder::Default)])),
+            SortKeys::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_descending_size_then_name() {
+        let argv = vec!["lsd", "--sort", "-size,name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortKeys(vec![
+                (SortColumn::Size, SortOrder::Default),
+                (SortColumn::Name, SortOrder::Default),
+            ])),
+            SortKeys::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_prefixes() {
+        let argv = vec!["lsd", "--sort=+size,-name,+time,extension"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortKeys(vec![
+                (SortColumn::Size, SortOrder::Reverse),
+                (SortColumn::Name, SortOrder::Reverse),
+                (SortColumn::Time, SortOrder::Reverse),
+                (SortColumn::Extension, SortOrder::Default),
+            ])),
+            SortKeys::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid_key() {
+        let argv = vec!["lsd", "--sort", "size,color"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_arg_matches_empty_key() {
+        let argv = vec!["lsd", "--sort", "size,"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(None, SortKeys::from_config(&Config::with_none()));
+    }
+}
+
+#[cfg(test)]
+mod test_dotfiles {
+    use super::DotFiles;
+
+    use crate::app;
+    use crate::config_file::{Config, Sorting};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DotFiles::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_first() {
+        let argv = vec!["lsd", "--dotfiles", "first"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(DotFiles::First), DotFiles::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_multiple() {
+        let argv = vec!["lsd", "--dotfiles", "first", "--dotfiles", "last"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(DotFiles::Last), DotFiles::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        assert_eq!(None, DotFiles::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_mixed() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            dir_grouping: None,
+            dotfiles: Some(DotFiles::Mixed),
+            natural_locale: None,
+            dir_column: None,
+            case_sensitive_extensions: None,
+        });
+        assert_eq!(Some(DotFiles::Mixed), DotFiles::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_first() {
+        let c: Config = serde_yaml::from_str("sorting:\n  dotfiles: first").unwrap();
+        assert_eq!(Some(DotFiles::First), DotFiles::from_config(&c));
+    }
+}
+
+#[cfg(test)]
+mod test_seed {
+    use super::Seed;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Seed::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_value() {
+        let argv = vec!["lsd", "--sort", "random", "--seed", "42"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Seed(Some(42))), Seed::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--seed", "-1"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(None, Seed::from_config(&Config::with_none()));
+    }
+}
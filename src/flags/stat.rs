@@ -0,0 +1,61 @@
+//! This module defines the [Stat] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to print the given files vertically, one attribute per line, instead
+/// of listing them.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Stat(pub bool);
+
+impl Configurable<Self> for Stat {
+    /// Get a potential `Stat` value from [ArgMatches].
+    ///
+    /// If the "stat" argument is passed, this returns a `Stat` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("stat") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// The detailed view is meant to inspect a single file, so this can not be configured in a
+    /// [Config] and this always returns [None].
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Stat;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Stat::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--stat"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Stat(true)), Stat::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(None, Stat::from_config(&Config::with_none()));
+    }
+}
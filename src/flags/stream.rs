@@ -0,0 +1,61 @@
+//! This module defines the [Stream] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to print the entries as soon as they are read, instead of collecting
+/// them all to sort and align them.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Stream(pub bool);
+
+impl Configurable<Self> for Stream {
+    /// Get a potential `Stream` value from [ArgMatches].
+    ///
+    /// If the "stream" argument is passed, this returns a `Stream` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("stream") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Streaming is meant for a single listing of a huge directory, so it can not be configured
+    /// in a [Config] and this always returns [None].
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Stream;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Stream::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--stream"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Stream(true)), Stream::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(None, Stream::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,77 @@
+//! This module defines the [Summary] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to print how many entries were left out of the listing.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Summary(pub bool);
+
+impl Configurable<Self> for Summary {
+    /// Get a potential `Summary` value from [ArgMatches].
+    ///
+    /// If the "summary" argument is passed, this returns a `Summary` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("summary") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Summary` value from a [Config].
+    ///
+    /// If the `Config::summary` has value,
+    /// this returns it as the value of the `Summary`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.summary.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Summary;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Summary::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--summary"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Summary(true)), Summary::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Summary::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.summary = Some(true);
+        assert_eq!(Some(Summary(true)), Summary::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.summary = Some(false);
+        assert_eq!(Some(Summary(false)), Summary::from_config(&c));
+    }
+}
@@ -0,0 +1,152 @@
+//! This module defines the [SymlinkDisplay] flag. To set it up from [ArgMatches], a [Config] and
+//! its [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing how to display the name of a symbolic link.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SymlinkDisplay {
+    /// The variant to show the link name followed by the arrow and its target.
+    Full,
+    /// The variant to show only the target of the link in place of its name.
+    Target,
+    /// The variant to show only the link name, along with the metadata of its target.
+    Name,
+}
+
+impl SymlinkDisplay {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "full" => Some(Self::Full),
+            "target" => Some(Self::Target),
+            "name" => Some(Self::Name),
+            _ => {
+                panic!(
+                    "Symlink display can only be one of full, target or name, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for SymlinkDisplay {
+    /// Get a potential `SymlinkDisplay` variant from [ArgMatches].
+    ///
+    /// If the "symlink-display" argument is passed, this returns the corresponding
+    /// `SymlinkDisplay` variant in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("symlink-display") > 0 {
+            if let Some(display) = matches.values_of("symlink-display")?.next_back() {
+                return Self::from_str(display);
+            }
+        }
+        None
+    }
+
+    /// Get a potential `SymlinkDisplay` variant from a [Config].
+    ///
+    /// If the `Config::symlink-display` has value and is one of "full", "target" or "name",
+    /// this returns the corresponding `SymlinkDisplay` variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.symlink_display
+    }
+}
+
+/// The default value for `SymlinkDisplay` is [SymlinkDisplay::Full].
+impl Default for SymlinkDisplay {
+    fn default() -> Self {
+        Self::Full
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SymlinkDisplay;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SymlinkDisplay::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_full() {
+        let argv = vec!["lsd", "--symlink-display", "full"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkDisplay::Full),
+            SymlinkDisplay::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_target() {
+        let argv = vec!["lsd", "--symlink-display", "target"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkDisplay::Target),
+            SymlinkDisplay::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_name() {
+        let argv = vec!["lsd", "--symlink-display", "name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkDisplay::Name),
+            SymlinkDisplay::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_multi() {
+        let argv = vec![
+            "lsd",
+            "--symlink-display",
+            "name",
+            "--symlink-display",
+            "target",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkDisplay::Target),
+            SymlinkDisplay::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SymlinkDisplay::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_target() {
+        let mut c = Config::with_none();
+        c.symlink_display = Some(SymlinkDisplay::Target);
+        assert_eq!(
+            Some(SymlinkDisplay::Target),
+            SymlinkDisplay::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_name() {
+        let mut c = Config::with_none();
+        c.symlink_display = Some(SymlinkDisplay::Name);
+        assert_eq!(Some(SymlinkDisplay::Name), SymlinkDisplay::from_config(&c));
+    }
+}
@@ -0,0 +1,116 @@
+//! This module defines the [SymlinkSize] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing which size to display for a symbolic link.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SymlinkSize {
+    /// The variant to show the size of the link itself, that is the length of its target path.
+    Link,
+    /// The variant to show the size of the target of the link, or a dash if it is broken.
+    Target,
+    /// The variant to show a dash.
+    None,
+}
+
+impl SymlinkSize {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "link" => Some(Self::Link),
+            "target" => Some(Self::Target),
+            "none" => Some(Self::None),
+            _ => {
+                panic!(
+                    "Symlink size can only be one of link, target or none, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for SymlinkSize {
+    /// Get a potential `SymlinkSize` variant from [ArgMatches].
+    ///
+    /// If the "symlink-size" argument is passed, this returns the corresponding `SymlinkSize`
+    /// variant in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("symlink-size") > 0 {
+            if let Some(size) = matches.values_of("symlink-size")?.next_back() {
+                return Self::from_str(size);
+            }
+        }
+        None
+    }
+
+    /// Get a potential `SymlinkSize` variant from a [Config].
+    ///
+    /// If the `Config::symlink-size` has value and is one of "link", "target" or "none", this
+    /// returns the corresponding `SymlinkSize` variant in a [Some]. Otherwise this returns
+    /// [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.symlink_size
+    }
+}
+
+/// The default value for `SymlinkSize` is [SymlinkSize::Link].
+impl Default for SymlinkSize {
+    fn default() -> Self {
+        Self::Link
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SymlinkSize;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SymlinkSize::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_target() {
+        let argv = vec!["lsd", "--symlink-size", "target"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkSize::Target),
+            SymlinkSize::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_multi() {
+        let argv = vec!["lsd", "--symlink-size", "none", "--symlink-size", "link"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkSize::Link),
+            SymlinkSize::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SymlinkSize::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_target() {
+        let mut c = Config::with_none();
+        c.symlink_size = Some(SymlinkSize::Target);
+        assert_eq!(Some(SymlinkSize::Target), SymlinkSize::from_config(&c));
+    }
+}
@@ -0,0 +1,91 @@
+//! This module defines the [SymlinkTarget] flag. To set it up from [ArgMatches], a [Config] and
+//! its [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing how to display the target of a symbolic link.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SymlinkTarget {
+    /// The variant to show the target as it is stored in the link, often relative to it.
+    Relative,
+    /// The variant to show the canonical absolute path of the target, or the stored target if
+    /// the link is broken.
+    Absolute,
+}
+
+impl Configurable<Self> for SymlinkTarget {
+    /// Get a potential `SymlinkTarget` variant from [ArgMatches].
+    ///
+    /// If the "symlink-target" argument is passed, this returns the corresponding
+    /// `SymlinkTarget` variant in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("symlink-target") == 0 {
+            return None;
+        }
+        match matches.values_of("symlink-target")?.next_back() {
+            Some("relative") => Some(Self::Relative),
+            Some("absolute") => Some(Self::Absolute),
+            _ => None,
+        }
+    }
+
+    /// Get a potential `SymlinkTarget` variant from a [Config].
+    ///
+    /// If the `Config::symlink-target` has value and is one of "relative" or "absolute", this
+    /// returns the corresponding `SymlinkTarget` variant in a [Some]. Otherwise this returns
+    /// [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.symlink_target
+    }
+}
+
+/// The default value for `SymlinkTarget` is [SymlinkTarget::Relative].
+impl Default for SymlinkTarget {
+    fn default() -> Self {
+        Self::Relative
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SymlinkTarget;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SymlinkTarget::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_absolute() {
+        let argv = vec!["lsd", "--symlink-target", "absolute"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkTarget::Absolute),
+            SymlinkTarget::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(None, SymlinkTarget::from_config(&Config::with_none()));
+
+        let mut c = Config::with_none();
+        c.symlink_target = Some(SymlinkTarget::Absolute);
+        assert_eq!(
+            Some(SymlinkTarget::Absolute),
+            SymlinkTarget::from_config(&c)
+        );
+    }
+}
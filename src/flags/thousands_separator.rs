@@ -0,0 +1,178 @@
+//! This module defines the [ThousandsSeparator] flag. To set it up from [ArgMatches], a [Config]
+//! and its [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The languages grouping the digits of the numbers with a dot, eg: `12.345`.
+const DOT_LANGUAGES: &[&str] = &[
+    "da", "de", "el", "es", "hr", "id", "it", "nl", "pt", "ro", "sl", "sr", "tr", "vi",
+];
+
+/// The languages grouping the digits of the numbers with a space, eg: `12 345`.
+const SPACE_LANGUAGES: &[&str] = &[
+    "bg", "cs", "et", "fi", "fr", "hu", "lt", "lv", "nb", "no", "pl", "ru", "sk", "sv", "uk",
+];
+
+/// The flag showing how to group the digits of the byte sizes and of the counts.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum ThousandsSeparator {
+    /// The variant to print the numbers without grouping their digits.
+    None,
+    /// The variant to group the digits with the separator of the locale, once resolved.
+    Locale,
+    /// The variant to group the digits by thousands with the given separator.
+    Char(char),
+}
+
+impl ThousandsSeparator {
+    /// Resolve [ThousandsSeparator::Locale] to the separator of the language of the first set
+    /// variable among `LC_ALL`, `LC_NUMERIC` and `LANG`, read with `env`, or to a comma.
+    pub fn resolve<F>(self, env: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if self != Self::Locale {
+            return self;
+        }
+
+        let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|name| env(name))
+            .find(|locale| !locale.is_empty())
+            .unwrap_or_default();
+        let language = locale.split(&['_', '.'][..]).next().unwrap_or("");
+        if DOT_LANGUAGES.contains(&language) {
+            Self::Char('.')
+        } else if SPACE_LANGUAGES.contains(&language) {
+            Self::Char(' ')
+        } else {
+            Self::Char(',')
+        }
+    }
+
+    /// Format a number, grouping its digits by thousands when a separator is set.
+    pub fn format(self, number: u64) -> String {
+        let separator = match self {
+            Self::Char(separator) => separator,
+            _ => return number.to_string(),
+        };
+
+        let digits = number.to_string();
+        let groups: Vec<_> = digits
+            .as_bytes()
+            .rchunks(3)
+            .rev()
+            .map(String::from_utf8_lossy)
+            .collect();
+        groups.join(separator.encode_utf8(&mut [0; 4]))
+    }
+}
+
+impl Configurable<Self> for ThousandsSeparator {
+    /// Get a potential `ThousandsSeparator` variant from [ArgMatches].
+    ///
+    /// If the "thousands-separator" argument is passed, this returns
+    /// [ThousandsSeparator::Locale] in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("thousands-separator") {
+            Some(Self::Locale)
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `ThousandsSeparator` variant from a [Config].
+    ///
+    /// If the `Config::thousands-separator` has value, this returns
+    /// [ThousandsSeparator::Locale] for `true` and [ThousandsSeparator::None] for `false`, in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .thousands_separator
+            .map(|separate| if separate { Self::Locale } else { Self::None })
+    }
+}
+
+/// The default value for `ThousandsSeparator` is [ThousandsSeparator::None].
+impl Default for ThousandsSeparator {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ThousandsSeparator;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ThousandsSeparator::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_locale() {
+        let argv = vec!["lsd", "--thousands-separator"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ThousandsSeparator::Locale),
+            ThousandsSeparator::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ThousandsSeparator::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let c: Config = serde_yaml::from_str("thousands-separator: true").unwrap();
+        assert_eq!(
+            Some(ThousandsSeparator::Locale),
+            ThousandsSeparator::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        for (locale, expected) in &[
+            (None, ','),
+            (Some("C"), ','),
+            (Some("en_US.UTF-8"), ','),
+            (Some("de_DE.UTF-8"), '.'),
+            (Some("fr_FR"), ' '),
+        ] {
+            let resolved = ThousandsSeparator::Locale.resolve(|name| match name {
+                "LANG" => locale.map(String::from),
+                _ => None,
+            });
+            assert_eq!(ThousandsSeparator::Char(*expected), resolved);
+        }
+
+        assert_eq!(
+            ThousandsSeparator::None,
+            ThousandsSeparator::None.resolve(|_| Some(String::from("de_DE")))
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let separator = ThousandsSeparator::Char(',');
+        assert_eq!("0", separator.format(0));
+        assert_eq!("999", separator.format(999));
+        assert_eq!("1,000", separator.format(1000));
+        assert_eq!("12,345", separator.format(12345));
+        assert_eq!("1,234,567", separator.format(1_234_567));
+        assert_eq!("12345", ThousandsSeparator::None.format(12345));
+    }
+}
@@ -0,0 +1,169 @@
+//! This module defines the [TimeRange]. To set it up from [ArgMatches], use the
+//! [configure_from](TimeRange::configure_from) method.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
+use clap::{ArgMatches, Error, ErrorKind};
+
+/// The bounds of the modification time of the listed entries.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct TimeRange {
+    pub since: Option<DateTime<Local>>,
+    pub until: Option<DateTime<Local>>,
+}
+
+impl TimeRange {
+    /// Get a value from the "since" and "until" arguments of [ArgMatches]. The bounds can not be
+    /// configured in a [Config](crate::config_file::Config), as they are only relevant to a
+    /// single listing.
+    ///
+    /// # Errors
+    ///
+    /// If the value of either argument is neither a duration nor a date.
+    pub fn configure_from(matches: &ArgMatches) -> Result<Self, Error> {
+        let now = Local::now();
+        // The last value wins when an argument is passed several times.
+        let parse = |name| match matches.values_of(name).and_then(Iterator::last) {
+            Some(value) => Self::parse_time(value, now).map(Some),
+            None => Ok(None),
+        };
+
+        Ok(Self {
+            since: parse("since")?,
+            until: parse("until")?,
+        })
+    }
+
+    /// Whether the time is within both bounds, which are inclusive.
+    pub fn contains(&self, time: &DateTime<Local>) -> bool {
+        self.since.iter().all(|since| time >= since) && self.until.iter().all(|until| time <= until)
+    }
+
+    /// Parse either a duration before now, like "2 hours ago" or "2h", or a date in the local
+    /// timezone, like "2023-01-01" or "2023-01-01 12:00:00", or an RFC 3339 date.
+    fn parse_time(value: &str, now: DateTime<Local>) -> Result<DateTime<Local>, Error> {
+        let value = value.trim();
+        let duration = value.trim_end_matches("ago").trim_end();
+        if let Ok(duration) = humantime::parse_duration(duration) {
+            if let Ok(duration) = Duration::from_std(duration) {
+                return Ok(now - duration);
+            }
+        }
+
+        if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+            return Ok(date.with_timezone(&Local));
+        }
+
+        let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .ok()
+                    .map(|date| date.and_hms(0, 0, 0))
+            });
+        if let Some(date) = naive.and_then(|naive| Local.from_local_datetime(&naive).earliest()) {
+            return Ok(date);
+        }
+
+        Err(Error::with_description(
+            &format!(
+                "invalid time '{}', expected a duration like '2 hours ago' or a date like '2023-01-01'",
+                value
+            ),
+            ErrorKind::ValueValidation,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimeRange;
+
+    use crate::app;
+    use chrono::{Duration, Local, TimeZone};
+
+    #[test]
+    fn test_configure_from_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            TimeRange::default(),
+            TimeRange::configure_from(&matches).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_configure_from_dates() {
+        let argv = vec![
+            "lsd",
+            "--since",
+            "2023-01-01",
+            "--until",
+            "2023-02-01 12:30:00",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            TimeRange {
+                since: Some(Local.ymd(2023, 1, 1).and_hms(0, 0, 0)),
+                until: Some(Local.ymd(2023, 2, 1).and_hms(12, 30, 0)),
+            },
+            TimeRange::configure_from(&matches).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_configure_from_multiple() {
+        let argv = vec!["lsd", "--since", "2023-01-01", "--since", "2023-03-01"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            TimeRange {
+                since: Some(Local.ymd(2023, 3, 1).and_hms(0, 0, 0)),
+                until: None,
+            },
+            TimeRange::configure_from(&matches).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_configure_from_invalid() {
+        let argv = vec!["lsd", "--since", "last tuesday"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(TimeRange::configure_from(&matches).is_err());
+    }
+
+    #[test]
+    fn test_parse_relative() {
+        let now = Local::now();
+        for value in &["2 hours ago", "2h ago", "2hours", "120min"] {
+            assert_eq!(
+                now - Duration::hours(2),
+                TimeRange::parse_time(value, now).unwrap(),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_rfc3339() {
+        let now = Local::now();
+        assert_eq!(
+            Local.timestamp(1_672_531_200, 0),
+            TimeRange::parse_time("2023-01-01T00:00:00Z", now).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        let now = Local::now();
+        let range = TimeRange {
+            since: Some(now - Duration::hours(2)),
+            until: Some(now - Duration::hours(1)),
+        };
+        assert!(!range.contains(&(now - Duration::hours(3))));
+        assert!(range.contains(&(now - Duration::hours(2))));
+        assert!(range.contains(&(now - Duration::minutes(90))));
+        assert!(!range.contains(&now));
+        assert!(TimeRange::default().contains(&now));
+    }
+}
@@ -0,0 +1,92 @@
+//! This module defines the [Truncate] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+use crate::print_error;
+
+use clap::ArgMatches;
+
+/// The flag showing the width, in terminal cells, above which the names are truncated.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Truncate(pub Option<usize>);
+
+impl Configurable<Self> for Truncate {
+    /// Get a potential `Truncate` value from [ArgMatches].
+    ///
+    /// If the "truncate" argument is passed, this returns a `Truncate` with its value in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        let width = matches.values_of("truncate")?.next_back()?;
+        width.parse().ok().map(|width| Self(Some(width)))
+    }
+
+    /// Get a potential `Truncate` value from a [Config].
+    ///
+    /// If the `Config::truncate` has a positive value, this returns it as the value of the
+    /// `Truncate`, in a [Some]. Otherwise this returns [None], printing an error for a zero
+    /// width.
+    fn from_config(config: &Config) -> Option<Self> {
+        match config.truncate? {
+            0 => {
+                print_error!("The truncate width of the configuration must be a positive number, it is ignored.");
+                None
+            }
+            width => Some(Self(Some(width))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Truncate;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Truncate::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_value() {
+        let argv = vec!["lsd", "--truncate", "10"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(Truncate(Some(10))),
+            Truncate::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        for width in &["0", "wide"] {
+            let argv = vec!["lsd", "--truncate", width];
+            assert!(app::build().get_matches_from_safe(argv).is_err());
+        }
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Truncate::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_value() {
+        let mut c = Config::with_none();
+        c.truncate = Some(20);
+        assert_eq!(Some(Truncate(Some(20))), Truncate::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_zero() {
+        let mut c = Config::with_none();
+        c.truncate = Some(0);
+        assert_eq!(None, Truncate::from_config(&c));
+    }
+}
@@ -0,0 +1,107 @@
+//! This module defines the [TtyOption] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing whether the output is laid out and decorated as on a terminal.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TtyOption {
+    /// The variant to check whether stdout is a terminal: when it is not, the entries are printed
+    /// one per line, without colors nor icons.
+    Auto,
+    /// The variant to behave as on a terminal even when stdout is redirected.
+    Force,
+}
+
+impl TtyOption {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "force" => Some(Self::Force),
+            _ => {
+                panic!("Tty can only be one of auto or force, but got {}.", value);
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for TtyOption {
+    /// Get a potential `TtyOption` variant from [ArgMatches].
+    ///
+    /// If the "tty" argument is passed, this returns the corresponding `TtyOption` variant in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("tty") > 0 {
+            if let Some(output) = matches.values_of("tty")?.next_back() {
+                return Self::from_str(output);
+            }
+        }
+        None
+    }
+
+    /// Get a potential `TtyOption` variant from a [Config].
+    ///
+    /// If the `Config::tty` has value and is one of "auto" or "force", this returns the
+    /// corresponding `TtyOption` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.tty
+    }
+}
+
+/// The default value for `TtyOption` is [TtyOption::Auto].
+impl Default for TtyOption {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TtyOption;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, TtyOption::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_force() {
+        let argv = vec!["lsd", "--tty=force"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TtyOption::Force),
+            TtyOption::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_multi() {
+        let argv = vec!["lsd", "--tty", "force", "--tty", "auto"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(TtyOption::Auto), TtyOption::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, TtyOption::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_force() {
+        let mut c = Config::with_none();
+        c.tty = Some(TtyOption::Force);
+        assert_eq!(Some(TtyOption::Force), TtyOption::from_config(&c));
+    }
+}
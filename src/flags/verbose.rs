@@ -0,0 +1,61 @@
+//! This module defines the [Verbose] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to print the diagnostic notes, like the options overridden by the
+/// classic mode and the resolved settings.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Verbose(pub bool);
+
+impl Configurable<Self> for Verbose {
+    /// Get a potential `Verbose` value from [ArgMatches].
+    ///
+    /// If the "verbose" argument is passed, this returns a `Verbose` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("verbose") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// The diagnostic notes are meant to explain a single listing, so this can not be configured
+    /// in a [Config] and this always returns [None].
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Verbose;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Verbose::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--verbose"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Verbose(true)), Verbose::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(None, Verbose::from_config(&Config::with_none()));
+    }
+}
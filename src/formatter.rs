@@ -0,0 +1,95 @@
+use crate::print_error;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// The size, in bytes, of the JSON document above which a note tells that it may take the
+/// external formatter a while to read it.
+pub const LARGE_INPUT: usize = 16 * 1024 * 1024;
+
+/// The command of the external formatter.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FormatterCommand {
+    program: String,
+    args: Vec<String>,
+}
+
+/// Build the command of the external formatter, splitting it on whitespace like `$PAGER`.
+pub fn command(formatter: &str) -> FormatterCommand {
+    let mut words = formatter.split_whitespace().map(String::from);
+    FormatterCommand {
+        program: words.next().unwrap_or_default(),
+        args: words.collect(),
+    }
+}
+
+/// Run the external formatter with the input written to its standard input, and return what it
+/// wrote to its standard output, or the error to report.
+///
+/// The input is written from another thread, so that a formatter writing its output before it
+/// has read all its input, like `cat`, does not block on a full pipe.
+pub fn run(command: FormatterCommand, input: String) -> Result<String, String> {
+    if input.len() > LARGE_INPUT {
+        print_error!(
+            "note: writing {} MiB of JSON to the external formatter.",
+            input.len() / 1024 / 1024
+        );
+    }
+
+    let mut child = Command::new(command.program)
+        .args(command.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|err| err.to_string())?;
+    let writer = child.stdin.take().map(|mut stdin| {
+        // The formatter may exit before reading the whole input, this is not an error.
+        thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        })
+    });
+
+    let output = child.wait_with_output().map_err(|err| err.to_string())?;
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    if !output.status.success() {
+        return Err(format!("it exited with {}", output.status));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{command, run, FormatterCommand};
+
+    #[test]
+    fn test_command() {
+        assert_eq!(
+            FormatterCommand {
+                program: "jq".into(),
+                args: vec!["-r".into(), ".entries".into()],
+            },
+            command("jq  -r .entries")
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_round_trips_through_cat() {
+        let input = "{\"version\": 1}\n".repeat(100_000);
+
+        assert_eq!(input, run(command("cat"), input.clone()).unwrap());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_fails_on_error_status() {
+        assert!(run(command("false"), String::new()).is_err());
+    }
+
+    #[test]
+    fn test_run_fails_on_missing_command() {
+        assert!(run(command("lsd-missing-formatter"), String::new()).is_err());
+    }
+}
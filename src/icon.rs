@@ -1,3 +1,5 @@
+use crate::flags::icons::{IconHideFor, IconLookup, IconLookupStep};
+use crate::flags::{IconOverrides, IconTheme, NerdFontVersion};
 use crate::meta::{FileType, Name};
 use std::collections::HashMap;
 
@@ -5,11 +7,64 @@ pub struct Icons {
     display_icons: bool,
     icons_by_name: HashMap<&'static str, &'static str>,
     icons_by_extension: HashMap<&'static str, &'static str>,
+    icons_by_category: HashMap<&'static str, &'static str>,
     default_folder_icon: &'static str,
+    empty_folder_icon: &'static str,
     default_file_icon: &'static str,
     icon_separator: String,
+    nerd_font_version: NerdFontVersion,
+    overrides: IconOverrides,
+    compound_extensions: bool,
+    lookup: IconLookup,
+    hide_for: IconHideFor,
 }
 
+/// The Material Design icons which moved in the version 3 of the Nerd Fonts, with their
+/// codepoints in the previous versions.
+const NERD_FONT_V2_CODEPOINTS: &[(&str, &str)] = &[
+    ("\u{f0427}", "\u{f6a7}"), // socket
+    ("\u{f0232}", "\u{f731}"), // pipe
+    ("\u{f072b}", "\u{fc29}"), // block device
+    ("\u{f0cb9}", "\u{f910}"), // playlist
+    ("\u{f031b}", "\u{f81a}"), // C#
+    ("\u{f0844}", "\u{fd42}"), // Vue
+];
+
+/// The categories of the extensions, to give the files of a known kind an icon even when their
+/// extension has none of its own. The extensions are lower-case.
+const EXTENSION_CATEGORIES: &[(&str, &[&str])] = &[
+    (
+        "image",
+        &[
+            "apng", "arw", "avif", "bmp", "cr2", "dds", "dng", "exr", "gif", "heic", "heif", "ico",
+            "jfif", "jp2", "jpeg", "jpg", "jxl", "nef", "pbm", "pcx", "pgm", "png", "ppm", "psd",
+            "qoi", "svg", "tga", "tif", "tiff", "webp", "xcf",
+        ],
+    ),
+    (
+        "video",
+        &[
+            "3gp", "avi", "flv", "m2ts", "m4v", "mkv", "mov", "mp4", "mpeg", "mpg", "ogv", "vob",
+            "webm", "wmv",
+        ],
+    ),
+    (
+        "audio",
+        &[
+            "aac", "aif", "aiff", "ape", "flac", "m4a", "mid", "midi", "mp3", "oga", "ogg", "opus",
+            "wav", "wma",
+        ],
+    ),
+    (
+        "archive",
+        &[
+            "7z", "bz2", "cab", "gz", "lz", "lz4", "lzma", "rar", "tar", "tgz", "txz", "xz", "z",
+            "zip", "zst",
+        ],
+    ),
+    ("font", &["eot", "otf", "ttf", "woff", "woff2"]),
+];
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Theme {
     NoIcon,
@@ -17,81 +72,211 @@ pub enum Theme {
     Unicode,
 }
 
+impl Theme {
+    /// Get the theme to use on a tty when icons are enabled in `auto` mode, looking up the
+    /// environment variables with `env`.
+    ///
+    /// Icons are disabled when the locale is explicitly set to a non UTF-8 one, as the glyphs
+    /// would render as boxes. Otherwise the `LSD_ICONS` hint, one of "fancy", "unicode" or
+    /// "none", tells what the terminal can render, eg: "unicode" without a Nerd Font. Without
+    /// the hint, the configured theme is used.
+    pub fn auto<F>(theme: IconTheme, env: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .find_map(|name| env(name).filter(|value| !value.is_empty()));
+        if let Some(locale) = locale {
+            let locale = locale.to_lowercase();
+            if !locale.contains("utf-8") && !locale.contains("utf8") {
+                return Self::NoIcon;
+            }
+        }
+
+        match env("LSD_ICONS").as_deref() {
+            Some("none") => Self::NoIcon,
+            Some("unicode") => Self::Unicode,
+            Some("fancy") => Self::Fancy,
+            _ => match theme {
+                IconTheme::Fancy => Self::Fancy,
+                IconTheme::Unicode => Self::Unicode,
+            },
+        }
+    }
+}
+
 // In order to add a new icon, write the unicode value like "\ue5fb" then
 // run the command below in vim:
 //
 // s#\\u[0-9a-f]*#\=eval('"'.submatch(0).'"')#
 impl Icons {
     pub fn new(theme: Theme, icon_separator: String) -> Self {
+        Self::with_nerd_font_version(theme, icon_separator, NerdFontVersion::default())
+    }
+
+    /// Build the icons of the theme, the fancy ones being taken from the given version of the
+    /// Nerd Fonts.
+    pub fn with_nerd_font_version(
+        theme: Theme,
+        icon_separator: String,
+        nerd_font_version: NerdFontVersion,
+    ) -> Self {
         let display_icons = theme == Theme::Fancy || theme == Theme::Unicode;
-        let (icons_by_name, icons_by_extension, default_file_icon, default_folder_icon) =
-            if theme == Theme::Fancy {
-                (
-                    Self::get_default_icons_by_name(),
-                    Self::get_default_icons_by_extension(),
-                    "\u{f016}", // 
-                    "\u{f115}", // 
-                )
-            } else {
-                (
-                    HashMap::new(),
-                    HashMap::new(),
-                    "\u{1f5cb}", // 🗋
-                    "\u{1f5c1}", // 🗁
-                )
-            };
+        let (
+            icons_by_name,
+            icons_by_extension,
+            icons_by_category,
+            default_file_icon,
+            default_folder_icon,
+            empty_folder_icon,
+        ) = if theme == Theme::Fancy {
+            (
+                Self::get_default_icons_by_name(),
+                Self::get_default_icons_by_extension(),
+                Self::get_default_icons_by_category(),
+                "\u{f016}", // 
+                "\u{f115}", // 
+                "\u{f114}", // 
+            )
+        } else {
+            (
+                HashMap::new(),
+                HashMap::new(),
+                HashMap::new(),
+                "\u{1f5cb}", // 🗋
+                "\u{1f5c1}", // 🗁
+                "\u{1f5c0}", // 🗀
+            )
+        };
 
         Self {
             display_icons,
             icons_by_name,
             icons_by_extension,
+            icons_by_category,
             default_file_icon,
             default_folder_icon,
+            empty_folder_icon,
             icon_separator,
+            nerd_font_version,
+            overrides: IconOverrides::default(),
+            compound_extensions: false,
+            lookup: IconLookup::default(),
+            hide_for: IconHideFor::default(),
         }
     }
 
+    /// Replace the icons of the names and extensions of the overrides, whatever the theme and
+    /// the version of the Nerd Fonts.
+    pub fn with_overrides(mut self, overrides: IconOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Look up the icons of the known compound extensions, like `tar.gz`, as a whole.
+    pub fn with_compound_extensions(mut self, compound_extensions: bool) -> Self {
+        self.compound_extensions = compound_extensions;
+        self
+    }
+
+    /// Look up the icons of the files by their name, extension and category in the given order.
+    pub fn with_lookup(mut self, lookup: IconLookup) -> Self {
+        self.lookup = lookup;
+        self
+    }
+
+    /// Print no icon for the files of the given types.
+    pub fn with_hide_for(mut self, hide_for: IconHideFor) -> Self {
+        self.hide_for = hide_for;
+        self
+    }
+
+    /// The icon found by the first step of the lookup which knows the file.
+    fn lookup(&self, file_name: &str, extension: Option<&str>) -> Option<&'static str> {
+        self.lookup.0.iter().find_map(|step| match step {
+            IconLookupStep::Name => self.icons_by_name.get(file_name).copied(),
+            IconLookupStep::Extension => {
+                extension.and_then(|extension| self.icons_by_extension.get(extension).copied())
+            }
+            IconLookupStep::Category => extension
+                .and_then(|extension| {
+                    EXTENSION_CATEGORIES
+                        .iter()
+                        .find(|(_, extensions)| extensions.contains(&extension))
+                })
+                .and_then(|(category, _)| self.icons_by_category.get(category).copied()),
+        })
+    }
+
     pub fn get(&self, name: &Name) -> String {
-        if !self.display_icons {
+        if !self.display_icons || self.hide_for.hides(name.file_type()) {
             return String::new();
         }
 
+        let file_name = name.file_name().to_lowercase();
+        let extension = if self.compound_extensions {
+            name.compound_extension()
+        } else {
+            name.extension()
+        }
+        .map(str::to_lowercase);
+        let extension = match extension {
+            None => name.magic_extension().map(str::to_string),
+            extension => extension,
+        };
+        if let Some(icon) = self.overrides.by_name.get(&file_name) {
+            return format!("{}{}", icon, self.icon_separator);
+        }
+
         // Check file types
         let file_type: FileType = name.file_type();
 
         let icon = if let FileType::Directory { .. } = file_type {
-            self.default_folder_icon
+            if name.empty_dir {
+                self.empty_folder_icon
+            } else {
+                self.default_folder_icon
+            }
         } else if let FileType::SymLink { is_dir: true } = file_type {
             "\u{f482}" // ""
         } else if let FileType::SymLink { is_dir: false } = file_type {
             "\u{f481}" // ""
         } else if let FileType::Socket = file_type {
-            "\u{f6a7}" // ""
+            "\u{f0427}" // "󰐧"
         } else if let FileType::Pipe = file_type {
-            "\u{f731}" // ""
+            "\u{f0232}" // "󰈲"
         } else if let FileType::CharDevice = file_type {
             "\u{e601}" // ""
         } else if let FileType::BlockDevice = file_type {
-            "\u{fc29}" // "ﰩ"
+            "\u{f072b}" // "󰜫"
         } else if let FileType::Special = file_type {
             "\u{f2dc}" // ""
-        } else if let Some(icon) = self
-            .icons_by_name
-            .get(name.file_name().to_lowercase().as_str())
+        } else if let Some(icon) = extension
+            .as_ref()
+            .and_then(|extension| self.overrides.by_extension.get(extension))
         {
-            // Use the known names.
-            icon
-        } else if let Some(icon) = name.extension().and_then(|extension| {
-            self.icons_by_extension
-                .get(extension.to_lowercase().as_str())
-        }) {
-            // Use the known extensions.
+            // Use the overridden extensions, as they are.
+            return format!("{}{}", icon, self.icon_separator);
+        } else if let Some(icon) = self.lookup(&file_name, extension.as_deref()) {
+            // Use the known names, extensions and categories.
             icon
+        } else if cfg!(windows) && matches!(file_type, FileType::File { exec: true, .. }) {
+            // The executables only get their own icon on Windows, told by their extension.
+            "\u{f489}" // ""
         } else {
             // Use the default icons.
             self.default_file_icon
         };
 
+        let icon = match self.nerd_font_version {
+            NerdFontVersion::V2 => NERD_FONT_V2_CODEPOINTS
+                .iter()
+                .find(|(v3, _)| *v3 == icon)
+                .map_or(icon, |(_, v2)| v2),
+            NerdFontVersion::V3 => icon,
+        };
+
         format!("{}{}", icon, self.icon_separator)
     }
 
@@ -138,6 +323,18 @@ impl Icons {
         m
     }
 
+    fn get_default_icons_by_category() -> HashMap<&'static str, &'static str> {
+        let mut m = HashMap::new();
+
+        m.insert("image", "\u{f1c5}"); // ""
+        m.insert("video", "\u{f03d}"); // ""
+        m.insert("audio", "\u{f001}"); // ""
+        m.insert("archive", "\u{f410}"); // ""
+        m.insert("font", "\u{f031}"); // ""
+
+        m
+    }
+
     fn get_default_icons_by_extension() -> HashMap<&'static str, &'static str> {
         let mut m = HashMap::new();
 
@@ -153,7 +350,7 @@ impl Icons {
         m.insert("bash_profile", "\u{f489}"); // ""
         m.insert("bashrc", "\u{f489}"); // ""
         m.insert("bat", "\u{f17a}"); // ""
-        m.insert("bio", "\u{f910}"); // "蘿"
+        m.insert("bio", "\u{f0cb9}"); // "󰲹"
         m.insert("bmp", "\u{f1c5}"); // ""
         m.insert("bz2", "\u{f410}"); // ""
         m.insert("c", "\u{e61e}"); // ""
@@ -167,10 +364,10 @@ impl Icons {
         m.insert("conf", "\u{e615}"); // ""
         m.insert("cp", "\u{e61d}"); // ""
         m.insert("cpp", "\u{e61d}"); // ""
-        m.insert("cs", "\u{f81a}"); // ""
+        m.insert("cs", "\u{f031b}"); // "󰌛"
         m.insert("cshtml", "\u{f1fa}"); // ""
-        m.insert("csproj", "\u{f81a}"); // ""
-        m.insert("csx", "\u{f81a}"); // ""
+        m.insert("csproj", "\u{f031b}"); // "󰌛"
+        m.insert("csx", "\u{f031b}"); // "󰌛"
         m.insert("csh", "\u{f489}"); // ""
         m.insert("css", "\u{e749}"); // ""
         m.insert("csv", "\u{f1c3}"); // ""
@@ -199,7 +396,7 @@ impl Icons {
         m.insert("flac", "\u{f001}"); // ""
         m.insert("flv", "\u{f03d}"); // ""
         m.insert("font", "\u{f031}"); // ""
-        m.insert("fpl", "\u{f910}"); // "蘿"
+        m.insert("fpl", "\u{f0cb9}"); // "󰲹"
         m.insert("fs", "\u{e7a7}"); // ""
         m.insert("fsx", "\u{e7a7}"); // ""
         m.insert("fsi", "\u{e7a7}"); // ""
@@ -244,8 +441,8 @@ impl Icons {
         m.insert("log", "\u{f18d}"); // ""
         m.insert("lua", "\u{e620}"); // ""
         m.insert("lz", "\u{f410}"); // ""
-        m.insert("m3u", "\u{f910}"); // "蘿"
-        m.insert("m3u8", "\u{f910}"); // "蘿"
+        m.insert("m3u", "\u{f0cb9}"); // "󰲹"
+        m.insert("m3u8", "\u{f0cb9}"); // "󰲹"
         m.insert("m4a", "\u{f001}"); // ""
         m.insert("markdown", "\u{f48a}"); // ""
         m.insert("md", "\u{f48a}"); // ""
@@ -265,7 +462,7 @@ impl Icons {
         m.insert("pdf", "\u{f1c1}"); // ""
         m.insert("php", "\u{e73d}"); // ""
         m.insert("pl", "\u{e769}"); // ""
-        m.insert("pls", "\u{f910}"); // "蘿"
+        m.insert("pls", "\u{f0cb9}"); // "󰲹"
         m.insert("pm", "\u{e769}"); // ""
         m.insert("png", "\u{f1c5}"); // ""
         m.insert("ppt", "\u{f1c4}"); // ""
@@ -311,6 +508,13 @@ impl Icons {
         m.insert("swift", "\u{e755}"); // ""
         m.insert("t", "\u{e769}"); // ""
         m.insert("tar", "\u{f410}"); // ""
+        m.insert("tar.bz2", "\u{f410}"); // ""
+        m.insert("tar.gz", "\u{f410}"); // ""
+        m.insert("tar.lz", "\u{f410}"); // ""
+        m.insert("tar.lzma", "\u{f410}"); // ""
+        m.insert("tar.xz", "\u{f410}"); // ""
+        m.insert("tar.z", "\u{f410}"); // ""
+        m.insert("tar.zst", "\u{f410}"); // ""
         m.insert("tex", "\u{e600}"); // ""
         m.insert("tiff", "\u{f1c5}"); // ""
         m.insert("ts", "\u{e628}"); // ""
@@ -321,15 +525,15 @@ impl Icons {
         m.insert("txt", "\u{f15c}"); // ""
         m.insert("video", "\u{f03d}"); // ""
         m.insert("vim", "\u{e62b}"); // ""
-        m.insert("vlc", "\u{f910}"); // "蘿"
-        m.insert("vue", "\u{fd42}"); // "﵂"
+        m.insert("vlc", "\u{f0cb9}"); // "󰲹"
+        m.insert("vue", "\u{f0844}"); // "󰡄"
         m.insert("wav", "\u{f001}"); // ""
         m.insert("webm", "\u{f03d}"); // ""
         m.insert("webp", "\u{f1c5}"); // ""
         m.insert("windows", "\u{f17a}"); // ""
         m.insert("wma", "\u{f001}"); // ""
         m.insert("wmv", "\u{f03d}"); // ""
-        m.insert("wpl", "\u{f910}"); // "蘿"
+        m.insert("wpl", "\u{f0cb9}"); // "󰲹"
         m.insert("woff", "\u{f031}"); // ""
         m.insert("woff2", "\u{f031}"); // ""
         m.insert("xls", "\u{f1c3}"); // ""
@@ -351,8 +555,14 @@ impl Icons {
 #[cfg(test)]
 mod test {
     use super::{Icons, Theme};
+    use crate::flags::icons::{IconFileType, IconHideFor, IconLookup, IconLookupStep};
+    use crate::flags::{
+        Display, Flags, IconOption, IconOverrides, IconTheme, IgnoreGlobs, NerdFontVersion,
+    };
     use crate::meta::Meta;
+    use globset::{Glob, GlobSetBuilder};
     use std::fs::File;
+    use std::path::Path;
     use tempfile::tempdir;
 
     #[test]
@@ -397,6 +607,7 @@ mod test {
     #[test]
     fn get_directory_icon() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
+        File::create(tmp_dir.path().join("file")).expect("failed to create file");
         let file_path = tmp_dir.path();
         let meta = Meta::from_path(&file_path.to_path_buf(), false).unwrap();
 
@@ -409,6 +620,7 @@ mod test {
     #[test]
     fn get_directory_icon_unicode() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
+        File::create(tmp_dir.path().join("file")).expect("failed to create file");
         let file_path = tmp_dir.path();
         let meta = Meta::from_path(&file_path.to_path_buf(), false).unwrap();
 
@@ -421,6 +633,7 @@ mod test {
     #[test]
     fn get_directory_icon_with_ext() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
+        File::create(tmp_dir.path().join("file")).expect("failed to create file");
         let file_path = tmp_dir.path();
         let meta = Meta::from_path(&file_path.to_path_buf(), false).unwrap();
 
@@ -430,6 +643,102 @@ mod test {
         assert_eq!(icon_str, format!("{}{}", "\u{f115}", icon.icon_separator)); // 
     }
 
+    #[test]
+    fn get_empty_directory_icon() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir_path = tmp_dir.path().join("dir");
+        std::fs::create_dir(&dir_path).expect("failed to create dir");
+
+        let probed = |path: &Path, flags: &Flags| {
+            let mut meta = Meta::from_path_with(path, flags).unwrap();
+            meta.probe_empty_dir(flags);
+            meta
+        };
+        let flags = Flags::default();
+        let icon = Icons::new(Theme::Fancy, " ".to_string());
+        let meta = probed(&dir_path, &flags);
+        assert_eq!(icon.get(&meta.name), "\u{f114} "); // 
+
+        // A hidden entry only counts when the hidden entries are listed.
+        File::create(dir_path.join(".hidden")).expect("failed to create file");
+        let meta = probed(&dir_path, &flags);
+        assert_eq!(icon.get(&meta.name), "\u{f114} "); // 
+        let all = Flags {
+            display: Display::AlmostAll,
+            ..Flags::default()
+        };
+        let meta = probed(&dir_path, &all);
+        assert_eq!(icon.get(&meta.name), "\u{f115} "); // 
+
+        // Neither does an ignored entry.
+        File::create(dir_path.join("file")).expect("failed to create file");
+        let mut globs = GlobSetBuilder::new();
+        globs.add(Glob::new("file").unwrap());
+        let ignored = Flags {
+            ignore_globs: IgnoreGlobs(globs.build().unwrap()),
+            ..Flags::default()
+        };
+        let meta = probed(&dir_path, &ignored);
+        assert_eq!(icon.get(&meta.name), "\u{f114} "); // 
+
+        let icon = Icons::new(Theme::Unicode, " ".to_string());
+        let meta = probed(&dir_path, &flags);
+        assert_eq!(icon.get(&meta.name), "\u{1f5c1} ");
+
+        // The directories are only read when the icons are shown.
+        let mut never = Flags::default();
+        never.icons.when = IconOption::Never;
+        let empty_path = tmp_dir.path().join("empty");
+        std::fs::create_dir(&empty_path).expect("failed to create dir");
+        let meta = probed(&empty_path, &never);
+        assert!(!meta.name.empty_dir);
+        let mut hidden = Flags::default();
+        hidden.icons.hide_for = IconHideFor(vec![IconFileType::Directory]);
+        let meta = probed(&empty_path, &hidden);
+        assert!(!meta.name.empty_dir);
+
+        // The content read when recursing tells it as well.
+        let mut meta = Meta::from_path_with(&empty_path, &never).unwrap();
+        meta.content = meta.recurse_into(1, &never).unwrap();
+        assert!(meta.name.empty_dir);
+    }
+
+    #[test]
+    fn get_no_icon_for_hidden_types() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir_path = tmp_dir.path().join("dir");
+        std::fs::create_dir(&dir_path).expect("failed to create dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+
+        let icon = Icons::new(Theme::Fancy, " ".to_string())
+            .with_hide_for(IconHideFor(vec![IconFileType::Directory]));
+        let meta = Meta::from_path(&dir_path, false).unwrap();
+        assert_eq!(icon.get(&meta.name), "");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+        assert_eq!(icon.get(&meta.name), "\u{f016} "); // 
+    }
+
+    #[test]
+    fn get_icon_by_nerd_font_version() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file.cs");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+
+        let icon =
+            Icons::with_nerd_font_version(Theme::Fancy, " ".to_string(), NerdFontVersion::V3);
+        assert_eq!(icon.get(&meta.name), "\u{f031b} "); // 󰌛
+
+        let icon =
+            Icons::with_nerd_font_version(Theme::Fancy, " ".to_string(), NerdFontVersion::V2);
+        assert_eq!(icon.get(&meta.name), "\u{f81a} "); //
+
+        // The icons which did not move are the same in both versions
+        let meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+        assert_eq!(icon.get(&meta.name), "\u{f115} "); //
+    }
+
     #[test]
     fn get_icon_by_name() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -455,10 +764,161 @@ mod test {
             File::create(&file_path).expect("failed to create file");
             let meta = Meta::from_path(&file_path, false).unwrap();
 
-            let icon = Icons::new(Theme::Fancy, " ".to_string());
+            // The compound extensions are only looked up as a whole when enabled.
+            let icon = Icons::new(Theme::Fancy, " ".to_string()).with_compound_extensions(true);
             let icon_str = icon.get(&meta.name);
 
             assert_eq!(icon_str, format!("{}{}", file_icon, icon.icon_separator));
         }
     }
+
+    #[test]
+    fn get_icon_by_category() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("picture.qoi");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+
+        let icon = Icons::new(Theme::Fancy, " ".to_string());
+        assert_eq!("\u{f1c5} ", icon.get(&meta.name)); // ""
+
+        // Without the category step, the default icon is used.
+        let icon = Icons::new(Theme::Fancy, " ".to_string()).with_lookup(IconLookup(vec![
+            IconLookupStep::Name,
+            IconLookupStep::Extension,
+        ]));
+        assert_eq!("\u{f016} ", icon.get(&meta.name)); // ""
+    }
+
+    #[test]
+    fn get_icon_by_compound_extension() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let mut overrides = IconOverrides::default();
+        overrides
+            .by_extension
+            .insert("gz".to_string(), "G".to_string());
+
+        let file_path = tmp_dir.path().join("archive.tar.gz");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+
+        let icon = Icons::new(Theme::Fancy, " ".to_string()).with_overrides(overrides);
+        assert_eq!("G ", icon.get(&meta.name));
+        let icon = icon.with_compound_extensions(true);
+        assert_eq!("\u{f410} ", icon.get(&meta.name));
+    }
+
+    #[test]
+    fn get_icon_by_magic() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icon = Icons::new(Theme::Fancy, " ".to_string());
+
+        for (name, content, expected) in &[
+            ("image", &b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"[..], "\u{f1c5} "),
+            ("document", b"%PDF-1.7\n", "\u{f1c1} "),
+            ("script", b"#!/bin/sh\necho hi\n", "\u{f489} "),
+            ("text", b"just some text", "\u{f016} "),
+            ("empty", b"", "\u{f016} "),
+            ("named.txt", b"\x89PNG\r\n\x1a\n", "\u{f15c} "),
+        ] {
+            let file_path = tmp_dir.path().join(name);
+            std::fs::write(&file_path, content).expect("failed to write file");
+            let mut meta = Meta::from_path(&file_path, false).unwrap();
+            meta.name.read_magic();
+            assert_eq!(*expected, icon.get(&meta.name), "{}", name);
+        }
+
+        // The content is not read by default.
+        let meta = Meta::from_path(&tmp_dir.path().join("image"), false).unwrap();
+        assert_eq!("\u{f016} ", icon.get(&meta.name));
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn get_executable_icon_by_extension() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icon = Icons::new(Theme::Fancy, " ".to_string());
+
+        for (name, expected) in &[
+            ("setup.exe", "\u{f17a} "),
+            ("build.cmd", "\u{f489} "),
+            ("notes.txt", "\u{f15c} "),
+            ("data", "\u{f016} "),
+        ] {
+            let file_path = tmp_dir.path().join(name);
+            File::create(&file_path).expect("failed to create file");
+            let meta = Meta::from_path(&file_path, false).unwrap();
+            assert_eq!(*expected, icon.get(&meta.name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn get_overridden_icon() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let mut overrides = IconOverrides::default();
+        overrides
+            .by_extension
+            .insert("rs".to_string(), "\u{e7a8}".to_string());
+        overrides
+            .by_name
+            .insert("cargo.lock".to_string(), "L".to_string());
+        let icon = Icons::new(Theme::Fancy, " ".to_string()).with_overrides(overrides);
+
+        for (name, expected) in &[
+            ("main.RS", "\u{e7a8} "),
+            ("Cargo.lock", "L "),
+            ("file.md", "\u{f48a} "),
+        ] {
+            let file_path = tmp_dir.path().join(name);
+            File::create(&file_path).expect("failed to create file");
+            let meta = Meta::from_path(&file_path, false).unwrap();
+
+            assert_eq!(icon.get(&meta.name), *expected);
+        }
+    }
+
+    fn env(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn auto_without_environment() {
+        assert_eq!(Theme::Fancy, Theme::auto(IconTheme::Fancy, env(&[])));
+        assert_eq!(Theme::Unicode, Theme::auto(IconTheme::Unicode, env(&[])));
+    }
+
+    #[test]
+    fn auto_with_utf8_locale() {
+        let vars = &[("LANG", "en_US.UTF-8"), ("LC_CTYPE", "en_US.utf8")];
+        assert_eq!(Theme::Fancy, Theme::auto(IconTheme::Fancy, env(vars)));
+    }
+
+    #[test]
+    fn auto_with_non_utf8_lc_ctype() {
+        let vars = &[("LANG", "en_US.UTF-8"), ("LC_CTYPE", "C")];
+        assert_eq!(Theme::NoIcon, Theme::auto(IconTheme::Fancy, env(vars)));
+        assert_eq!(Theme::NoIcon, Theme::auto(IconTheme::Unicode, env(vars)));
+    }
+
+    #[test]
+    fn auto_with_lc_all_overriding_lc_ctype() {
+        let vars = &[("LC_ALL", "en_US.UTF-8"), ("LC_CTYPE", "POSIX")];
+        assert_eq!(Theme::Fancy, Theme::auto(IconTheme::Fancy, env(vars)));
+    }
+
+    #[test]
+    fn auto_with_hint() {
+        let vars = &[("LANG", "C.UTF-8"), ("LSD_ICONS", "unicode")];
+        assert_eq!(Theme::Unicode, Theme::auto(IconTheme::Fancy, env(vars)));
+
+        let vars = &[("LSD_ICONS", "none")];
+        assert_eq!(Theme::NoIcon, Theme::auto(IconTheme::Fancy, env(vars)));
+
+        let vars = &[("LSD_ICONS", "fancy")];
+        assert_eq!(Theme::Fancy, Theme::auto(IconTheme::Unicode, env(vars)));
+    }
 }
@@ -33,10 +33,14 @@ mod color;
 mod config_file;
 mod core;
 mod display;
+mod exit_code;
 mod flags;
+mod formatter;
 mod icon;
 mod meta;
+mod pager;
 mod sort;
+mod terminal;
 
 use crate::config_file::Config;
 use crate::core::Core;
@@ -92,6 +96,15 @@ macro_rules! print_output {
 fn main() {
     let matches = app::build().get_matches_from(wild::args_os());
 
+    if matches.is_present("list-blocks") {
+        print_output!("{}", flags::Block::list());
+        return;
+    }
+    if matches.is_present("list-sorts") {
+        print_output!("{}", flags::SortColumn::list());
+        return;
+    }
+
     // input translate glob FILE without single quote into real names
     // for example:
     // * to all files matched
@@ -114,8 +127,21 @@ fn main() {
     } else {
         Config::default()
     };
+    let config = match matches.value_of("profile") {
+        Some(profile) => config.with_profile(profile).unwrap_or_else(|err| {
+            clap::Error::with_description(&err, clap::ErrorKind::InvalidValue).exit()
+        }),
+        None => config,
+    };
     let flags = Flags::configure_from(&matches, &config).unwrap_or_else(|err| err.exit());
+    if flags.verbose.0 {
+        for note in &flags.classic_overrides.0 {
+            print_error!("note: {}.", note);
+        }
+    }
     let core = Core::new(flags);
 
     core.run(inputs);
+
+    std::process::exit(exit_code::get());
 }
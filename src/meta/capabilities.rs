@@ -0,0 +1,257 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::fmt;
+use std::path::Path;
+
+/// The names of the capabilities, indexed by their number.
+const NAMES: [&str; 41] = [
+    "chown",
+    "dac_override",
+    "dac_read_search",
+    "fowner",
+    "fsetid",
+    "kill",
+    "setgid",
+    "setuid",
+    "setpcap",
+    "linux_immutable",
+    "net_bind_service",
+    "net_broadcast",
+    "net_admin",
+    "net_raw",
+    "ipc_lock",
+    "ipc_owner",
+    "sys_module",
+    "sys_rawio",
+    "sys_chroot",
+    "sys_ptrace",
+    "sys_pacct",
+    "sys_admin",
+    "sys_boot",
+    "sys_nice",
+    "sys_resource",
+    "sys_time",
+    "sys_tty_config",
+    "mknod",
+    "lease",
+    "audit_write",
+    "audit_control",
+    "setfcap",
+    "mac_override",
+    "mac_admin",
+    "syslog",
+    "wake_alarm",
+    "block_suspend",
+    "audit_read",
+    "perfmon",
+    "bpf",
+    "checkpoint_restore",
+];
+
+const REVISION_MASK: u32 = 0xFF00_0000;
+const REVISION_1: u32 = 0x0100_0000;
+const REVISION_2: u32 = 0x0200_0000;
+const REVISION_3: u32 = 0x0300_0000;
+const EFFECTIVE: u32 = 0x0000_0001;
+
+/// The file capabilities, stored in the `security.capability` extended attribute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    permitted: u64,
+    inheritable: u64,
+    effective: bool,
+}
+
+impl Capabilities {
+    /// Read the capabilities of a file, without following symbolic links. This returns [None]
+    /// if the file has no capabilities or if they can not be read.
+    #[cfg(target_os = "linux")]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let name = b"security.capability\0";
+        let mut value = [0_u8; 24];
+        let len = unsafe {
+            libc::lgetxattr(
+                path.as_ptr(),
+                name.as_ptr() as *const libc::c_char,
+                value.as_mut_ptr() as *mut libc::c_void,
+                value.len(),
+            )
+        };
+        if len <= 0 {
+            return None;
+        }
+
+        Self::from_bytes(&value[..len as usize])
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn from_path(_: &Path) -> Option<Self> {
+        None
+    }
+
+    /// Decode a `vfs_cap_data` structure, whose fields are little endian 32 bits words: the
+    /// revision and flags, then the permitted and inheritable sets, split in two words since
+    /// the second revision.
+    fn from_bytes(value: &[u8]) -> Option<Self> {
+        let word = |index: usize| -> Option<u64> {
+            let bytes = value.get(index * 4..index * 4 + 4)?;
+            Some(u64::from(u32::from_le_bytes([
+                bytes[0], bytes[1], bytes[2], bytes[3],
+            ])))
+        };
+
+        let magic = word(0)? as u32;
+        let (permitted, inheritable) = match magic & REVISION_MASK {
+            REVISION_1 => (word(1)?, word(2)?),
+            REVISION_2 | REVISION_3 => (word(1)? | word(3)? << 32, word(2)? | word(4)? << 32),
+            _ => return None,
+        };
+
+        if permitted == 0 && inheritable == 0 {
+            return None;
+        }
+
+        Some(Self {
+            permitted,
+            inheritable,
+            effective: magic & EFFECTIVE != 0,
+        })
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString<'static> {
+        colors.colorize(self.to_string(), &Elem::Capability)
+    }
+}
+
+/// Formats the capabilities like `getcap`, grouping the ones sharing the same flags, eg:
+/// `cap_net_admin,cap_net_raw=ep cap_kill=i`.
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut groups: Vec<(String, Vec<String>)> = vec![];
+        for bit in 0..64 {
+            let permitted = self.permitted & 1 << bit != 0;
+            let inheritable = self.inheritable & 1 << bit != 0;
+            if !permitted && !inheritable {
+                continue;
+            }
+
+            let mut flags = String::new();
+            if self.effective {
+                flags.push('e');
+            }
+            if inheritable {
+                flags.push('i');
+            }
+            if permitted {
+                flags.push('p');
+            }
+            let name = match NAMES.get(bit) {
+                Some(name) => format!("cap_{}", name),
+                None => format!("cap_{}", bit),
+            };
+
+            match groups
+                .iter_mut()
+                .find(|(group_flags, _)| *group_flags == flags)
+            {
+                Some((_, names)) => names.push(name),
+                None => groups.push((flags, vec![name])),
+            }
+        }
+
+        let groups: Vec<String> = groups
+            .iter()
+            .map(|(flags, names)| format!("{}={}", names.join(","), flags))
+            .collect();
+        write!(f, "{}", groups.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Capabilities;
+
+    /// Encode a third revision `vfs_cap_data`, as written by `setcap`.
+    pub fn encode(permitted: u64, inheritable: u64, effective: bool) -> Vec<u8> {
+        let magic = 0x0300_0000 | effective as u32;
+        let words = [
+            magic,
+            permitted as u32,
+            inheritable as u32,
+            (permitted >> 32) as u32,
+            (inheritable >> 32) as u32,
+            0,
+        ];
+        words
+            .iter()
+            .flat_map(|word| word.to_le_bytes().to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn test_from_bytes_net_raw() {
+        let caps = Capabilities::from_bytes(&encode(1 << 13, 0, true)).unwrap();
+        assert_eq!("cap_net_raw=ep", caps.to_string());
+    }
+
+    #[test]
+    fn test_from_bytes_groups() {
+        let caps =
+            Capabilities::from_bytes(&encode(1 << 12 | 1 << 13 | 1 << 38, 1 << 5, false)).unwrap();
+        assert_eq!(
+            "cap_kill=i cap_net_admin,cap_net_raw,cap_perfmon=p",
+            caps.to_string()
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_revision_1() {
+        let bytes: Vec<u8> = [0x0100_0000_u32, 1, 0]
+            .iter()
+            .flat_map(|word| word.to_le_bytes().to_vec())
+            .collect();
+        let caps = Capabilities::from_bytes(&bytes).unwrap();
+        assert_eq!("cap_chown=p", caps.to_string());
+    }
+
+    #[test]
+    fn test_from_bytes_invalid() {
+        assert_eq!(None, Capabilities::from_bytes(&[]));
+        assert_eq!(None, Capabilities::from_bytes(&encode(0, 0, true)));
+        assert_eq!(None, Capabilities::from_bytes(&[0, 0, 0, 0x05, 0, 0, 0, 0]));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_from_path() {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("ping");
+        std::fs::File::create(&file_path).expect("failed to create file");
+        assert_eq!(None, Capabilities::from_path(&file_path));
+
+        // Setting capabilities needs CAP_SETFCAP and a filesystem supporting them
+        let value = encode(1 << 13, 0, true);
+        let path = CString::new(file_path.as_os_str().as_bytes()).unwrap();
+        let res = unsafe {
+            libc::setxattr(
+                path.as_ptr(),
+                b"security.capability\0".as_ptr() as *const libc::c_char,
+                value.as_ptr() as *const libc::c_void,
+                value.len(),
+                0,
+            )
+        };
+        if res != 0 {
+            return;
+        }
+
+        let caps = Capabilities::from_path(&file_path).unwrap();
+        assert_eq!("cap_net_raw=ep", caps.to_string());
+    }
+}
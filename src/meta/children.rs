@@ -0,0 +1,120 @@
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::{Display, Flags};
+use crate::meta::FileType;
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Children {
+    /// The file is not a directory.
+    NotDirectory,
+    /// The number of entries listed in the directory.
+    Count(usize),
+    /// The directory could not be read, eg: because of its permissions.
+    Unreadable,
+}
+
+impl Children {
+    /// Count the immediate entries of a directory, skipping the ones which would not be listed
+    /// with the given flags (hidden files without `--all` and ignored globs).
+    pub fn new(path: &Path, file_type: FileType, flags: &Flags) -> Self {
+        if !matches!(file_type, FileType::Directory { .. }) {
+            return Self::NotDirectory;
+        }
+
+        let entries = match path.read_dir() {
+            Ok(entries) => entries,
+            Err(_) => return Self::Unreadable,
+        };
+
+        let show_hidden = matches!(flags.display, Display::All | Display::AlmostAll);
+        let count = entries
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name();
+                (show_hidden || !name.to_string_lossy().starts_with('.'))
+                    && !flags.ignore_globs.0.is_match(&name)
+            })
+            .count();
+
+        Self::Count(count)
+    }
+
+    pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString<'static> {
+        match self {
+            Self::Count(count) => colors.colorize(
+                flags.thousands_separator.format(*count as u64),
+                &Elem::Links { valid: true },
+            ),
+            Self::NotDirectory => colors.colorize(String::from("-"), &Elem::NonFile),
+            Self::Unreadable => colors.colorize(String::from("?"), &Elem::NoAccess),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Children;
+    use crate::color::{Colors, Theme};
+    use crate::flags::{Display, Flags, ThousandsSeparator};
+    use crate::meta::Meta;
+    use std::fs::{create_dir, File};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_children_count() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        for name in &["a", "b", "c", "d", ".hidden"] {
+            File::create(tmp_dir.path().join(name)).expect("failed to create file");
+        }
+        create_dir(tmp_dir.path().join("e")).expect("failed to create dir");
+        let meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+
+        let flags = Flags::default();
+        assert_eq!(
+            Children::Count(5),
+            Children::new(&meta.path, meta.file_type, &flags)
+        );
+
+        let flags = Flags {
+            display: Display::AlmostAll,
+            ..Flags::default()
+        };
+        assert_eq!(
+            Children::Count(6),
+            Children::new(&meta.path, meta.file_type, &flags)
+        );
+    }
+
+    #[test]
+    fn test_children_not_directory() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("file");
+        File::create(&path).expect("failed to create file");
+        let meta = Meta::from_path(&path, false).unwrap();
+
+        assert_eq!(
+            Children::NotDirectory,
+            Children::new(&meta.path, meta.file_type, &Flags::default())
+        );
+    }
+
+    #[test]
+    fn test_children_thousands_separator() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        for index in 0..12345 {
+            File::create(tmp_dir.path().join(index.to_string())).expect("failed to create file");
+        }
+        let meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+        let colors = Colors::new(Theme::NoColor);
+
+        let flags = Flags::default();
+        let children = Children::new(&meta.path, meta.file_type, &flags);
+        assert_eq!("12345", children.render(&colors, &flags).to_string());
+
+        let flags = Flags {
+            thousands_separator: ThousandsSeparator::Char(','),
+            ..Flags::default()
+        };
+        assert_eq!("12,345", children.render(&colors, &flags).to_string());
+    }
+}
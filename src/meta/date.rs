@@ -1,9 +1,21 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::{DateFlag, Flags};
+use crate::flags::{DateFlag, Flags, RelativeDateFlag, RelativeUnit, TimeRange};
 use chrono::{DateTime, Duration, Local};
 use chrono_humanize::HumanTime;
 use std::fs::Metadata;
 
+/// The units of the relative dates, with their length in seconds, their name and their
+/// abbreviation in the compound dates. The months are 30 days long and the years 365 days long.
+const UNITS: &[(RelativeUnit, i64, &str, &str)] = &[
+    (RelativeUnit::Second, 1, "second", "s"),
+    (RelativeUnit::Minute, 60, "minute", "m"),
+    (RelativeUnit::Hour, 3_600, "hour", "h"),
+    (RelativeUnit::Day, 86_400, "day", "d"),
+    (RelativeUnit::Week, 604_800, "week", "w"),
+    (RelativeUnit::Month, 2_592_000, "month", "mo"),
+    (RelativeUnit::Year, 31_536_000, "year", "y"),
+];
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Date(DateTime<Local>);
 
@@ -18,7 +30,50 @@ impl<'a> From<&'a Metadata> for Date {
 }
 
 impl Date {
+    /// Whether the date is within the bounds of `--since` and `--until`.
+    pub fn is_within(&self, range: &TimeRange) -> bool {
+        range.contains(&self.0)
+    }
+
+    /// Get the date of the last access, or of the last modification if it is not available.
+    pub fn accessed(meta: &Metadata) -> Self {
+        match meta.accessed() {
+            Ok(accessed_time) => Date(accessed_time.into()),
+            Err(_) => Date::from(meta),
+        }
+    }
+
+    /// Get the date of the last status change, or of the last modification if it is not
+    /// available, as on Windows.
+    pub fn changed(meta: &Metadata) -> Self {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            use std::time::{Duration as StdDuration, UNIX_EPOCH};
+
+            if meta.ctime() >= 0 {
+                let since_epoch = StdDuration::new(meta.ctime() as u64, meta.ctime_nsec() as u32);
+                return Date((UNIX_EPOCH + since_epoch).into());
+            }
+        }
+        Date::from(meta)
+    }
+
+    /// The date in the RFC 3339 format, eg: `2021-06-15T12:00:00+02:00`.
+    pub fn to_rfc3339(&self) -> String {
+        self.0.to_rfc3339()
+    }
+
     pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+        self.render_with(colors, &flags.date, &flags.date_relative)
+    }
+
+    pub fn render_with(
+        &self,
+        colors: &Colors,
+        date_flag: &DateFlag,
+        relative: &RelativeDateFlag,
+    ) -> ColoredString<'static> {
         let now = Local::now();
 
         let elem = if self.0 > now - Duration::hours(1) {
@@ -29,13 +84,14 @@ impl Date {
             Elem::Older
         };
 
-        colors.colorize(self.date_string(&flags), &elem)
+        colors.colorize(self.date_string(date_flag, relative), &elem)
     }
 
-    pub fn date_string(&self, flags: &Flags) -> String {
-        match &flags.date {
+    pub fn date_string(&self, date_flag: &DateFlag, relative: &RelativeDateFlag) -> String {
+        match date_flag {
             DateFlag::Date => self.0.format("%c").to_string(),
-            DateFlag::Relative => format!("{}", HumanTime::from(self.0 - Local::now())),
+            DateFlag::Relative => self.relative(Local::now(), relative),
+            DateFlag::RelativeSmart => self.relative_smart(Local::now(), relative),
             DateFlag::ISO => {
                 // 365.2425 * 24 * 60 * 60 = 31556952 seconds per year
                 // 15778476 seconds are 6 months
@@ -48,15 +104,99 @@ impl Date {
             DateFlag::Formatted(format) => self.0.format(&format).to_string(),
         }
     }
+
+    /// The age group of the date, under which `--date-groups` lists it.
+    pub fn age_group(&self) -> &'static str {
+        self.age_group_at(Local::now())
+    }
+
+    /// The age group of the date compared to `now`: "Today" for the calendar day of `now` and the
+    /// future, then "This Week" and "This Month" within the lengths of the relative date units.
+    fn age_group_at(&self, now: DateTime<Local>) -> &'static str {
+        let length = |unit| {
+            UNITS
+                .iter()
+                .find(|(u, ..)| *u == unit)
+                .map_or(0, |(_, length, ..)| *length)
+        };
+        let seconds = (now - self.0).num_seconds();
+
+        if self.0.date() >= now.date() {
+            "Today"
+        } else if seconds < length(RelativeUnit::Week) {
+            "This Week"
+        } else if seconds < length(RelativeUnit::Month) {
+            "This Month"
+        } else {
+            "Older"
+        }
+    }
+
+    /// The calendar day of the date compared to the day of `now`, or the relative date beyond
+    /// yesterday.
+    fn relative_smart(&self, now: DateTime<Local>, relative: &RelativeDateFlag) -> String {
+        let day = self.0.date();
+        if day == now.date() {
+            String::from("today")
+        } else if Some(day) == now.date().pred_opt() {
+            String::from("yesterday")
+        } else {
+            self.relative(now, relative)
+        }
+    }
+
+    /// The duration between the date and `now`, counted in the units of the relative flag.
+    fn relative(&self, now: DateTime<Local>, relative: &RelativeDateFlag) -> String {
+        if relative.is_default() {
+            return format!("{}", HumanTime::from(self.0 - now));
+        }
+
+        let seconds = (now - self.0).num_seconds();
+        let max_unit = relative.max_unit.unwrap_or(RelativeUnit::Year);
+        // The units from the largest to the smallest one, with their length in seconds
+        let units = UNITS
+            .iter()
+            .rev()
+            .filter(|(unit, ..)| *unit <= max_unit)
+            .collect::<Vec<_>>();
+
+        let mut remainder = seconds.abs();
+        let mut parts = vec![];
+        for (_, length, name, short) in units {
+            let count = remainder / length;
+            remainder %= length;
+            if parts.is_empty() && count == 0 {
+                continue;
+            }
+            if relative.compound {
+                if count > 0 {
+                    parts.push(format!("{}{}", count, short));
+                }
+            } else {
+                let plural = if count == 1 { "" } else { "s" };
+                parts.push(format!("{} {}{}", count, name, plural));
+            }
+            // The count is rounded down to a single unit, or to the unit below in compound
+            if !relative.compound || parts.len() == 2 || count == 0 {
+                break;
+            }
+        }
+
+        match (parts.join(" "), seconds) {
+            (duration, _) if duration.is_empty() => String::from("now"),
+            (duration, seconds) if seconds < 0 => format!("in {}", duration),
+            (duration, _) => format!("{} ago", duration),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::Date;
     use crate::color::{Colors, Theme};
-    use crate::flags::{DateFlag, Flags};
+    use crate::flags::{DateFlag, Flags, RelativeDateFlag, RelativeUnit};
     use ansi_term::Colour;
-    use chrono::{DateTime, Duration, Local};
+    use chrono::{DateTime, Duration, Local, TimeZone};
     use std::io;
     use std::path::Path;
     use std::process::{Command, ExitStatus};
@@ -216,6 +356,120 @@ mod test {
         fs::remove_file(file_path).unwrap();
     }
 
+    #[test]
+    fn test_with_relative_smart_date() {
+        let now = Local.ymd(2021, 6, 15).and_hms(12, 0, 0);
+        for (name, creation_date, label) in &[
+            ("today", now - Duration::hours(11), "today"),
+            ("yesterday", now - Duration::hours(13), "yesterday"),
+            ("last_week", now - Duration::days(7), "a week ago"),
+        ] {
+            let mut file_path = env::temp_dir();
+            file_path.push(format!("test_with_relative_smart_date_{}.tmp", name));
+
+            let success = cross_platform_touch(&file_path, creation_date)
+                .unwrap()
+                .success();
+            assert!(success, "failed to exec touch");
+
+            let date = Date::from(&file_path.metadata().unwrap());
+            assert_eq!(
+                *label,
+                date.relative_smart(now, &RelativeDateFlag::default())
+            );
+
+            fs::remove_file(file_path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_relative_single_unit() {
+        let now = Local.ymd(2021, 6, 15).and_hms(12, 0, 0);
+        let single = RelativeDateFlag {
+            max_unit: Some(RelativeUnit::Year),
+            compound: false,
+        };
+        let days = RelativeDateFlag {
+            max_unit: Some(RelativeUnit::Day),
+            compound: false,
+        };
+
+        for (date, relative, label) in &[
+            (now, &single, "now"),
+            (now - Duration::minutes(65), &single, "1 hour ago"),
+            (now - Duration::days(400), &single, "1 year ago"),
+            (now - Duration::days(400), &days, "400 days ago"),
+            (now + Duration::days(2), &days, "in 2 days"),
+        ] {
+            assert_eq!(*label, Date(*date).relative(now, relative));
+        }
+    }
+
+    #[test]
+    fn test_relative_compound() {
+        let now = Local.ymd(2021, 6, 15).and_hms(12, 0, 0);
+        let compound = RelativeDateFlag {
+            max_unit: None,
+            compound: true,
+        };
+        let hours = RelativeDateFlag {
+            max_unit: Some(RelativeUnit::Hour),
+            compound: true,
+        };
+
+        for (date, relative, label) in &[
+            (now - Duration::seconds(42), &compound, "42s ago"),
+            (now - Duration::minutes(65), &compound, "1h 5m ago"),
+            (now - Duration::seconds(3_630), &compound, "1h ago"),
+            (now - Duration::days(9), &compound, "1w 2d ago"),
+            (now - Duration::hours(50), &hours, "50h ago"),
+        ] {
+            assert_eq!(*label, Date(*date).relative(now, relative));
+        }
+    }
+
+    #[test]
+    fn test_age_group() {
+        let now = Local.ymd(2021, 6, 15).and_hms(12, 0, 0);
+
+        for (date, group) in &[
+            (now + Duration::days(2), "Today"),
+            (now - Duration::hours(11), "Today"),
+            (now - Duration::hours(13), "This Week"),
+            (now - Duration::days(6), "This Week"),
+            (now - Duration::days(8), "This Month"),
+            (now - Duration::days(31), "Older"),
+        ] {
+            assert_eq!(*group, Date(*date).age_group_at(now));
+        }
+    }
+
+    #[test]
+    fn test_with_relative_smart_date_now() {
+        let mut file_path = env::temp_dir();
+        file_path.push("test_with_relative_smart_date_now.tmp");
+
+        let success = cross_platform_touch(&file_path, &Local::now())
+            .unwrap()
+            .success();
+        assert!(success, "failed to exec touch");
+
+        let colors = Colors::new(Theme::Default);
+        let date = Date::from(&file_path.metadata().unwrap());
+
+        let flags = Flags {
+            date: DateFlag::RelativeSmart,
+            ..Flags::default()
+        };
+
+        assert_eq!(
+            Colour::Fixed(40).paint("today"),
+            date.render(&colors, &flags)
+        );
+
+        fs::remove_file(file_path).unwrap();
+    }
+
     #[test]
     fn test_iso_format_now() {
         let mut file_path = env::temp_dir();
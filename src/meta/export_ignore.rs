@@ -0,0 +1,142 @@
+use globset::{GlobBuilder, GlobMatcher};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A line of a `.gitattributes` file setting or unsetting the `export-ignore` attribute.
+#[derive(Clone, Debug)]
+struct Rule {
+    glob: GlobMatcher,
+    /// The path of the listed directory from the directory of the `.gitattributes` file.
+    prefix: PathBuf,
+    /// Whether the pattern has no slash, and then matches the file names at any depth.
+    file_name_only: bool,
+    ignore: bool,
+}
+
+/// The files of a directory which `git archive` leaves out, as they have the `export-ignore`
+/// attribute in a `.gitattributes` file of the directory or of its parents in the repository.
+#[derive(Clone, Debug, Default)]
+pub struct ExportIgnore {
+    rules: Vec<Rule>,
+}
+
+impl ExportIgnore {
+    /// Read the `.gitattributes` files from the root of the repository of `dir` down to `dir`.
+    /// Outside of a repository, only the `.gitattributes` file of `dir` is read.
+    pub fn for_dir(dir: &Path) -> Self {
+        let dir = match dir.canonicalize() {
+            Ok(dir) => dir,
+            Err(_) => return Self::default(),
+        };
+        let root = dir
+            .ancestors()
+            .find(|ancestor| ancestor.join(".git").exists())
+            .unwrap_or(&dir);
+        let dirs: Vec<&Path> = dir
+            .ancestors()
+            .take_while(|ancestor| ancestor.starts_with(root))
+            .collect();
+
+        let mut rules = vec![];
+        // The deeper files come last, so that their rules take precedence.
+        for attributes_dir in dirs.into_iter().rev() {
+            let content = match fs::read_to_string(attributes_dir.join(".gitattributes")) {
+                Ok(content) => content,
+                Err(_) => continue,
+            };
+            let prefix = dir.strip_prefix(attributes_dir).unwrap_or(&dir);
+            rules.extend(content.lines().filter_map(|line| Rule::parse(line, prefix)));
+        }
+
+        Self { rules }
+    }
+
+    /// Whether the file of the listed directory named `name` is export-ignored, according to the
+    /// last rule matching it.
+    pub fn is_ignored(&self, name: &OsStr) -> bool {
+        let rule = self.rules.iter().rev().find(|rule| {
+            if rule.file_name_only {
+                rule.glob.is_match(name)
+            } else {
+                rule.glob.is_match(rule.prefix.join(name))
+            }
+        });
+        matches!(rule, Some(rule) if rule.ignore)
+    }
+}
+
+impl Rule {
+    /// Parse a line like `docs/*.md export-ignore`, the attribute being unset with a leading `-`
+    /// or `!`. Comments, macros and the lines without the attribute give [None].
+    fn parse(line: &str, prefix: &Path) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let pattern = fields.next().filter(|pattern| {
+            !pattern.starts_with('#') && !pattern.starts_with('[') && !pattern.ends_with('/')
+        })?;
+        let ignore = fields.fold(None, |ignore, attribute| match attribute {
+            "export-ignore" => Some(true),
+            "-export-ignore" | "!export-ignore" => Some(false),
+            _ => ignore,
+        })?;
+
+        let glob = GlobBuilder::new(pattern.trim_start_matches('/'))
+            .literal_separator(true)
+            .build()
+            .ok()?
+            .compile_matcher();
+
+        Some(Self {
+            glob,
+            prefix: prefix.to_path_buf(),
+            file_name_only: !pattern.contains('/'),
+            ignore,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ExportIgnore;
+    use std::ffi::OsStr;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_is_ignored() {
+        let repo = tempdir().expect("failed to create temp dir");
+        fs::create_dir_all(repo.path().join(".git")).unwrap();
+        fs::create_dir_all(repo.path().join("docs/internal")).unwrap();
+        fs::write(
+            repo.path().join(".gitattributes"),
+            "# tooling\n*.md export-ignore\n/docs/internal export-ignore\ntests/ export-ignore\n",
+        )
+        .unwrap();
+        fs::write(
+            repo.path().join("docs/.gitattributes"),
+            "README.md -export-ignore\n*.txt text\n",
+        )
+        .unwrap();
+
+        let root = ExportIgnore::for_dir(repo.path());
+        assert!(root.is_ignored(OsStr::new("CHANGELOG.md")));
+        assert!(!root.is_ignored(OsStr::new("internal")));
+        assert!(!root.is_ignored(OsStr::new("tests")));
+
+        let docs = ExportIgnore::for_dir(&repo.path().join("docs"));
+        assert!(docs.is_ignored(OsStr::new("guide.md")));
+        assert!(docs.is_ignored(OsStr::new("internal")));
+        assert!(!docs.is_ignored(OsStr::new("README.md")));
+        assert!(!docs.is_ignored(OsStr::new("notes.txt")));
+    }
+
+    #[test]
+    fn test_is_ignored_outside_repository() {
+        let dir = tempdir().expect("failed to create temp dir");
+        fs::write(dir.path().join(".gitattributes"), "build export-ignore\n").unwrap();
+
+        let export_ignore = ExportIgnore::for_dir(dir.path());
+        assert!(export_ignore.is_ignored(OsStr::new("build")));
+        assert!(!export_ignore.is_ignored(OsStr::new("src")));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::fmt;
+use std::fs::Metadata;
+
+/// The names of the file flags, as printed by `ls -lo`, with their bits in `st_flags`.
+const NAMES: [(u32, &str); 8] = [
+    (0x0000_0001, "nodump"),
+    (0x0000_0002, "uchg"),
+    (0x0000_0004, "uappnd"),
+    (0x0000_0008, "opaque"),
+    (0x0000_8000, "hidden"),
+    (0x0001_0000, "arch"),
+    (0x0002_0000, "schg"),
+    (0x0004_0000, "sappnd"),
+];
+
+/// The flags of a file, set with `chflags` on FreeBSD and macOS, eg: whether it is immutable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileFlags(u32);
+
+impl From<&Metadata> for FileFlags {
+    #[cfg(target_os = "macos")]
+    fn from(meta: &Metadata) -> Self {
+        use std::os::macos::fs::MetadataExt;
+
+        Self(meta.st_flags())
+    }
+
+    #[cfg(target_os = "freebsd")]
+    fn from(meta: &Metadata) -> Self {
+        use std::os::freebsd::fs::MetadataExt;
+
+        Self(meta.st_flags())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
+    fn from(_: &Metadata) -> Self {
+        Self(0)
+    }
+}
+
+impl FileFlags {
+    pub fn render(&self, colors: &Colors) -> ColoredString<'static> {
+        if NAMES.iter().any(|(bit, _)| self.0 & bit != 0) {
+            colors.colorize(self.to_string(), &Elem::Special)
+        } else {
+            colors.colorize(String::from("-"), &Elem::NonFile)
+        }
+    }
+}
+
+/// Formats the known flags like `ls -lo`, separated by commas, eg: `uchg,hidden`.
+impl fmt::Display for FileFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<&str> = NAMES
+            .iter()
+            .filter(|(bit, _)| self.0 & bit != 0)
+            .map(|(_, name)| *name)
+            .collect();
+        write!(f, "{}", names.join(","))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FileFlags;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("", FileFlags(0).to_string());
+        assert_eq!("nodump,uchg,hidden", FileFlags(0x8003).to_string());
+        assert_eq!("schg", FileFlags(0x0002_0000 | 0x8000_0000).to_string());
+    }
+
+    #[test]
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    fn test_from_metadata_nodump() {
+        use std::ffi::CString;
+        use std::fs::File;
+        use std::os::unix::ffi::OsStrExt;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("file");
+        File::create(&path).expect("failed to create file");
+
+        let c_path = CString::new(path.as_os_str().as_bytes()).unwrap();
+        let res = unsafe { libc::chflags(c_path.as_ptr(), 0x1) };
+        assert_eq!(0, res, "failed to set the nodump flag");
+
+        let flags = FileFlags::from(&path.metadata().unwrap());
+        assert_eq!("nodump", flags.to_string());
+    }
+}
@@ -80,6 +80,20 @@ impl FileType {
         }
     }
 
+    /// The name of the file type, as written in the JSON output.
+    pub fn name(self) -> &'static str {
+        match self {
+            FileType::BlockDevice => "block-device",
+            FileType::CharDevice => "char-device",
+            FileType::Directory { .. } => "directory",
+            FileType::File { .. } => "file",
+            FileType::SymLink { .. } => "symlink",
+            FileType::Pipe => "pipe",
+            FileType::Socket => "socket",
+            FileType::Special => "special",
+        }
+    }
+
     pub fn is_dirlike(self) -> bool {
         matches!(
             self,
@@ -216,7 +230,7 @@ mod test {
         let colors = Colors::new(Theme::NoLscolors);
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
 
-        assert_eq!(Colour::Fixed(44).paint("|"), file_type.render(&colors));
+        assert_eq!(Colour::Fixed(178).paint("|"), file_type.render(&colors));
     }
 
     #[test]
@@ -257,6 +271,6 @@ mod test {
         let colors = Colors::new(Theme::NoLscolors);
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
 
-        assert_eq!(Colour::Fixed(44).paint("s"), file_type.render(&colors));
+        assert_eq!(Colour::Fixed(170).paint("s"), file_type.render(&colors));
     }
 }
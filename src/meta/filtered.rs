@@ -0,0 +1,82 @@
+use crate::meta::Meta;
+use std::ops::AddAssign;
+
+/// Why an entry of a directory is left out of the listing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unlisted {
+    /// The entry is hidden, without `--all` or `--almost-all`.
+    Hidden,
+    /// The entry matches an ignore glob, or is ignored by the `.gitattributes`.
+    Ignored,
+}
+
+/// The number of entries of a directory left out of the listing, for `--summary`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Filtered {
+    pub hidden: usize,
+    pub ignored: usize,
+}
+
+impl Filtered {
+    pub fn add(&mut self, unlisted: Unlisted) {
+        match unlisted {
+            Unlisted::Hidden => self.hidden += 1,
+            Unlisted::Ignored => self.ignored += 1,
+        }
+    }
+
+    /// The number of entries left out of the listed directories and of their subdirectories.
+    pub fn total(metas: &[Meta]) -> Self {
+        let mut total = Self::default();
+        for meta in metas {
+            total += meta.filtered;
+            if let Some(content) = &meta.content {
+                total += Self::total(content);
+            }
+        }
+        total
+    }
+
+    /// The footer line, eg: `(3 hidden, 2 ignored)`, if any entry was left out.
+    pub fn render(&self) -> Option<String> {
+        let mut counts = vec![];
+        if self.hidden > 0 {
+            counts.push(format!("{} hidden", self.hidden));
+        }
+        if self.ignored > 0 {
+            counts.push(format!("{} ignored", self.ignored));
+        }
+        if counts.is_empty() {
+            None
+        } else {
+            Some(format!("({})", counts.join(", ")))
+        }
+    }
+}
+
+impl AddAssign for Filtered {
+    fn add_assign(&mut self, other: Self) {
+        self.hidden += other.hidden;
+        self.ignored += other.ignored;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Filtered, Unlisted};
+
+    #[test]
+    fn test_render() {
+        let mut filtered = Filtered::default();
+        assert_eq!(None, filtered.render());
+
+        filtered.add(Unlisted::Ignored);
+        assert_eq!(Some("(1 ignored)".to_string()), filtered.render());
+
+        filtered += Filtered {
+            hidden: 3,
+            ignored: 1,
+        };
+        assert_eq!(Some("(3 hidden, 2 ignored)".to_string()), filtered.render());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::color::{ColoredString, Colors, Elem};
+use crate::meta::FileType;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+thread_local! {
+    // Every listed directory is looked up, so the mount table is only read once per run.
+    static MOUNTS: HashMap<PathBuf, String> = read_mounts();
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FsType {
+    /// The file is not a mount point.
+    NotMountPoint,
+    /// The type of the filesystem mounted on the directory, eg: ext4 or nfs.
+    MountPoint(String),
+}
+
+impl FsType {
+    /// Look up a directory in the mount table, to get the type of the filesystem mounted on it.
+    pub fn new(path: &Path, file_type: FileType) -> Self {
+        if !matches!(file_type, FileType::Directory { .. }) {
+            return Self::NotMountPoint;
+        }
+
+        let path = match path.canonicalize() {
+            Ok(path) => path,
+            Err(_) => return Self::NotMountPoint,
+        };
+
+        MOUNTS.with(|mounts| match mounts.get(&path) {
+            Some(fs_type) => Self::MountPoint(fs_type.clone()),
+            None => Self::NotMountPoint,
+        })
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString<'static> {
+        match self {
+            Self::MountPoint(fs_type) => {
+                colors.colorize(fs_type.clone(), &Elem::Dir { uid: false })
+            }
+            Self::NotMountPoint => colors.colorize(String::from("-"), &Elem::NonFile),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_mounts() -> HashMap<PathBuf, String> {
+    std::fs::read_to_string("/proc/mounts")
+        .map(|content| parse_mounts(&content))
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_mounts() -> HashMap<PathBuf, String> {
+    HashMap::new()
+}
+
+/// Parse the lines of `/proc/mounts`, formatted like `fstab`: the device, the mount point and
+/// the filesystem type come first, separated by spaces. When several filesystems are mounted on
+/// the same directory, the last one hides the others so it wins.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_mounts(content: &str) -> HashMap<PathBuf, String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = fields.nth(1)?;
+            let fs_type = fields.next()?;
+            Some((PathBuf::from(unescape(mount_point)), fs_type.to_owned()))
+        })
+        .collect()
+}
+
+/// The spaces, tabs, newlines and backslashes of the mount points are written as octal escapes,
+/// eg: `\040` for a space.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn unescape(field: &str) -> String {
+    let mut bytes = Vec::with_capacity(field.len());
+    let mut rest = field.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let code = tail
+            .get(..3)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        match (byte, code) {
+            (b'\\', Some(code)) => {
+                bytes.push(code);
+                rest = &tail[3..];
+            }
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_mounts, unescape, FsType};
+    use crate::meta::FileType;
+    use std::path::{Path, PathBuf};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_mounts() {
+        let mounts = parse_mounts(
+            "/dev/sda1 / ext4 rw,relatime 0 0\n\
+             server:/export /mnt/nfs nfs rw 0 0\n\
+             tmpfs /mnt/nfs tmpfs rw 0 0\n\
+             /dev/sdb1 /mnt/my\\040disk vfat rw 0 0\n",
+        );
+
+        assert_eq!(Some("ext4"), mounts.get(Path::new("/")).map(String::as_str));
+        assert_eq!(
+            Some("tmpfs"),
+            mounts.get(Path::new("/mnt/nfs")).map(String::as_str)
+        );
+        assert_eq!(
+            Some("vfat"),
+            mounts
+                .get(&PathBuf::from("/mnt/my disk"))
+                .map(String::as_str)
+        );
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!("a b\\c", unescape("a\\040b\\134c"));
+        assert_eq!("trailing\\", unescape("trailing\\"));
+    }
+
+    #[test]
+    fn test_not_mount_point() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir = tmp_dir.path().join("dir");
+        std::fs::create_dir(&dir).expect("failed to create dir");
+
+        assert_eq!(
+            FsType::NotMountPoint,
+            FsType::new(&dir, FileType::Directory { uid: false })
+        );
+    }
+
+    #[test]
+    fn test_file_is_not_mount_point() {
+        assert_eq!(
+            FsType::NotMountPoint,
+            FsType::new(
+                Path::new("/"),
+                FileType::File {
+                    uid: false,
+                    exec: false
+                }
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_proc_mount_point() {
+        // /proc is not mounted in some sandboxes, skip the test then.
+        match FsType::new(Path::new("/proc"), FileType::Directory { uid: false }) {
+            FsType::MountPoint(fs_type) => assert_eq!("proc", fs_type),
+            FsType::NotMountPoint => {}
+        }
+    }
+}
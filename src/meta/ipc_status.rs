@@ -0,0 +1,227 @@
+use crate::color::{ColoredString, Colors, Elem};
+use crate::meta::FileType;
+use std::path::{Path, PathBuf};
+
+thread_local! {
+    // Every listed socket is looked up, so the socket table is only read once per run.
+    static SOCKETS: Vec<BoundSocket> = read_sockets();
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum IpcStatus {
+    /// The file is neither a socket nor a FIFO.
+    NotIpc,
+    /// A process is bound to the socket and accepts connections on it.
+    Listening,
+    /// A process is bound to the socket without accepting connections, eg: a datagram socket.
+    Bound,
+    /// No process is bound to the socket anymore, eg: the server exited without removing it.
+    Stale,
+    /// The status can not be detected without opening the file, which could block.
+    Unknown,
+}
+
+/// A unix socket bound to a path, as listed in `/proc/net/unix`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct BoundSocket {
+    /// The canonical path of the socket, or the path given to bind if it is relative to the
+    /// directory of the process which bound it.
+    path: PathBuf,
+    /// Whether the socket accepts connections.
+    listening: bool,
+}
+
+impl IpcStatus {
+    /// Detect whether a socket is bound by a process. FIFOs are never opened, as opening them
+    /// blocks until a writer comes along, so their status is always unknown.
+    pub fn new(path: &Path, file_type: FileType) -> Self {
+        match file_type {
+            FileType::Socket => SOCKETS.with(|sockets| Self::socket_status(path, sockets)),
+            FileType::Pipe => Self::Unknown,
+            _ => Self::NotIpc,
+        }
+    }
+
+    fn socket_status(path: &Path, sockets: &[BoundSocket]) -> Self {
+        if cfg!(not(target_os = "linux")) {
+            return Self::Unknown;
+        }
+        let path = match path.canonicalize() {
+            Ok(path) => path,
+            Err(_) => return Self::Unknown,
+        };
+
+        match sockets.iter().find(|socket| socket.path == path) {
+            Some(socket) if socket.listening => Self::Listening,
+            Some(_) => Self::Bound,
+            // A socket bound to a relative path may be this one, or another one with the same name.
+            None if sockets
+                .iter()
+                .any(|socket| socket.path.is_relative() && path.ends_with(&socket.path)) =>
+            {
+                Self::Unknown
+            }
+            None => Self::Stale,
+        }
+    }
+    pub fn render(self, colors: &Colors) -> ColoredString<'static> {
+        match self {
+            Self::NotIpc => ColoredString::from(""),
+            Self::Listening => colors.colorize(String::from(" [listening]"), &Elem::Socket),
+            Self::Bound => colors.colorize(String::from(" [bound]"), &Elem::Socket),
+            Self::Stale => colors.colorize(String::from(" [stale]"), &Elem::NoAccess),
+            Self::Unknown => colors.colorize(String::from(" [?]"), &Elem::NoAccess),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_sockets() -> Vec<BoundSocket> {
+    std::fs::read_to_string("/proc/net/unix")
+        .map(|content| parse_sockets(&content))
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_sockets() -> Vec<BoundSocket> {
+    Vec::new()
+}
+
+/// The flag of the sockets accepting connections, `__SO_ACCEPTCON`.
+const ACCEPT_CONNECTIONS: u32 = 0x0001_0000;
+/// The state of the sockets which are not connected, which the listening ones are.
+const UNCONNECTED: &str = "01";
+
+/// Parse the lines of `/proc/net/unix`, whose columns are `Num RefCount Protocol Flags Type St
+/// Inode Path`, keeping the sockets bound to a path. The path is kept as given to bind, so the
+/// absolute ones are made canonical to be compared exactly.
+fn parse_sockets(content: &str) -> Vec<BoundSocket> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            let (flags, state, path) = match columns.as_slice() {
+                [_, _, _, flags, _, state, _, path] => (flags, state, path),
+                _ => return None,
+            };
+            // The abstract sockets are not bound to a file.
+            if path.starts_with('@') {
+                return None;
+            }
+            let path = Path::new(path);
+            let path = match path.canonicalize() {
+                Ok(canonical) if path.is_absolute() => canonical,
+                _ => path.to_path_buf(),
+            };
+            let accepting = u32::from_str_radix(flags, 16)
+                .map(|flags| flags & ACCEPT_CONNECTIONS != 0)
+                .unwrap_or(false);
+
+            Some(BoundSocket {
+                path,
+                listening: accepting && *state == UNCONNECTED,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::{parse_sockets, BoundSocket, IpcStatus};
+    use crate::meta::{FileType, Permissions};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn status(path: &Path) -> IpcStatus {
+        let meta = path.symlink_metadata().expect("failed to get metas");
+        let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
+        IpcStatus::new(path, file_type)
+    }
+
+    #[test]
+    fn test_file_is_not_ipc() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file.txt");
+        std::fs::File::create(&file_path).expect("failed to create file");
+
+        assert_eq!(IpcStatus::NotIpc, status(&file_path));
+    }
+
+    #[test]
+    fn test_fifo_is_unknown() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let pipe_path = tmp_dir.path().join("pipe.tmp");
+        let success = Command::new("mkfifo")
+            .arg(&pipe_path)
+            .status()
+            .expect("failed to exec mkfifo")
+            .success();
+        assert!(success, "failed to exec mkfifo");
+
+        assert_eq!(IpcStatus::Unknown, status(&pipe_path));
+    }
+
+    #[test]
+    fn test_parse_sockets() {
+        let content = "Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 20893 /run/listening.sock
+0000000000000000: 00000002 00000000 00000000 0002 01 20894 /run/datagram.sock
+0000000000000000: 00000003 00000000 00000000 0001 03 20895 /run/listening.sock
+0000000000000000: 00000002 00000000 00010000 0001 01 20896 @abstract
+0000000000000000: 00000002 00000000 00010000 0001 01 20897 relative.sock
+0000000000000000: 00000003 00000000 00000000 0001 03 20898
+";
+        let socket = |path: &str, listening| BoundSocket {
+            path: PathBuf::from(path),
+            listening,
+        };
+
+        assert_eq!(
+            vec![
+                socket("/run/listening.sock", true),
+                socket("/run/datagram.sock", false),
+                socket("/run/listening.sock", false),
+                socket("relative.sock", true),
+            ],
+            parse_sockets(content)
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_socket_status() {
+        use std::os::unix::net::{UnixDatagram, UnixListener};
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let socket_path = tmp_dir.path().join("socket.tmp");
+        let listener = UnixListener::bind(&socket_path).expect("failed to create the socket");
+        let datagram_path = tmp_dir.path().join("datagram.tmp");
+        let datagram = UnixDatagram::bind(&datagram_path).expect("failed to create the socket");
+        // Another socket with the same name, which is not the listed one.
+        let other_dir = tempdir().expect("failed to create temp dir");
+        let other = UnixListener::bind(other_dir.path().join("socket.tmp"))
+            .expect("failed to create the socket");
+        drop(listener);
+
+        let read = || parse_sockets(&std::fs::read_to_string("/proc/net/unix").unwrap());
+        assert_eq!(
+            IpcStatus::Stale,
+            IpcStatus::socket_status(&socket_path, &read())
+        );
+        assert_eq!(
+            IpcStatus::Bound,
+            IpcStatus::socket_status(&datagram_path, &read())
+        );
+
+        let listener = UnixListener::bind(tmp_dir.path().join("listening.tmp"))
+            .expect("failed to create the socket");
+        assert_eq!(
+            IpcStatus::Listening,
+            IpcStatus::socket_status(&tmp_dir.path().join("listening.tmp"), &read())
+        );
+        drop((listener, datagram, other));
+    }
+}
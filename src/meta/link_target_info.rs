@@ -0,0 +1,121 @@
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::Flags;
+use crate::meta::{FileType, Permissions, Size};
+use ansi_term::ANSIStrings;
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LinkTargetInfo {
+    /// The file is not a symbolic link.
+    NotLink,
+    /// The type and size of the file the link points to.
+    Target { file_type: FileType, size: Size },
+    /// The link points to a file which does not exist.
+    Broken,
+}
+
+impl LinkTargetInfo {
+    /// Stat the target of a symbolic link, following every link of the chain.
+    pub fn new(path: &Path, file_type: FileType) -> Self {
+        if !matches!(file_type, FileType::SymLink { .. }) {
+            return Self::NotLink;
+        }
+
+        match path.metadata() {
+            Ok(meta) => Self::Target {
+                file_type: FileType::new(&meta, None, &Permissions::from(&meta)),
+                size: Size::from(&meta),
+            },
+            Err(_) => Self::Broken,
+        }
+    }
+
+    pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString<'static> {
+        match self {
+            Self::NotLink => colors.colorize(String::from("-"), &Elem::NonFile),
+            Self::Target { file_type, size } => {
+                let mut strings = vec![file_type.render(colors)];
+                if let FileType::File { .. } = file_type {
+                    strings.push(ColoredString::from(" "));
+                    strings.push(size.render(colors, flags, None));
+                }
+                ColoredString::from(ANSIStrings(&strings).to_string())
+            }
+            Self::Broken => colors.colorize(String::from("broken"), &Elem::BrokenSymLink),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::LinkTargetInfo;
+    use crate::color::{Colors, Theme};
+    use crate::flags::Flags;
+    use crate::meta::{FileType, Meta};
+    use std::fs::{create_dir, write};
+    use std::os::unix::fs::symlink;
+    use tempfile::tempdir;
+
+    fn info(path: &std::path::Path) -> LinkTargetInfo {
+        let meta = Meta::from_path(path, false).unwrap();
+        LinkTargetInfo::new(&meta.path, meta.file_type)
+    }
+
+    #[test]
+    fn test_link_to_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        write(tmp_dir.path().join("target"), [b'a'; 100]).expect("failed to write file");
+        symlink("target", tmp_dir.path().join("link")).expect("failed to create link");
+
+        let info = info(&tmp_dir.path().join("link"));
+        match &info {
+            LinkTargetInfo::Target {
+                file_type: FileType::File { .. },
+                size,
+            } => assert_eq!(100, size.get_bytes()),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(
+            ". 100 B",
+            info.render(&Colors::new(Theme::NoColor), &Flags::default())
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_link_to_directory() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        create_dir(tmp_dir.path().join("target")).expect("failed to create dir");
+        symlink("target", tmp_dir.path().join("link")).expect("failed to create link");
+
+        let info = info(&tmp_dir.path().join("link"));
+        assert_eq!(
+            "d",
+            info.render(&Colors::new(Theme::NoColor), &Flags::default())
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_broken_link() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        symlink("missing", tmp_dir.path().join("link")).expect("failed to create link");
+
+        assert_eq!(LinkTargetInfo::Broken, info(&tmp_dir.path().join("link")));
+    }
+
+    #[test]
+    fn test_not_link() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        write(tmp_dir.path().join("file"), "").expect("failed to write file");
+
+        let info = info(&tmp_dir.path().join("file"));
+        assert_eq!(LinkTargetInfo::NotLink, info);
+        assert_eq!(
+            "-",
+            info.render(&Colors::new(Theme::NoColor), &Flags::default())
+                .to_string()
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::Flags;
 use std::fs::Metadata;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -23,9 +24,16 @@ impl<'a> From<&'a Metadata> for Links {
 }
 
 impl Links {
-    pub fn render(&self, colors: &Colors) -> ColoredString {
+    pub fn nlink(&self) -> Option<u64> {
+        self.nlink
+    }
+
+    pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         match self.nlink {
-            Some(i) => colors.colorize(i.to_string(), &Elem::Links { valid: true }),
+            Some(i) => colors.colorize(
+                flags.thousands_separator.format(i),
+                &Elem::Links { valid: true },
+            ),
             None => colors.colorize(String::from("-"), &Elem::Links { valid: false }),
         }
     }
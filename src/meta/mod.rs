@@ -1,7 +1,15 @@
+mod capabilities;
+mod children;
 mod date;
+mod export_ignore;
+mod file_flags;
 mod filetype;
+mod filtered;
+mod fs_type;
 mod indicator;
 mod inode;
+mod ipc_status;
+mod link_target_info;
 mod links;
 pub mod name;
 mod owner;
@@ -12,10 +20,18 @@ mod symlink;
 #[cfg(windows)]
 mod windows_utils;
 
+pub use self::capabilities::Capabilities;
+pub use self::children::Children;
 pub use self::date::Date;
+pub use self::export_ignore::ExportIgnore;
+pub use self::file_flags::FileFlags;
 pub use self::filetype::FileType;
+pub use self::filtered::{Filtered, Unlisted};
+pub use self::fs_type::FsType;
 pub use self::indicator::Indicator;
 pub use self::inode::INode;
+pub use self::ipc_status::IpcStatus;
+pub use self::link_target_info::LinkTargetInfo;
 pub use self::links::Links;
 pub use self::name::Name;
 pub use self::owner::Owner;
@@ -24,10 +40,15 @@ pub use self::size::Size;
 pub use self::symlink::SymLink;
 pub use crate::icon::Icons;
 
-use crate::flags::{Display, Flags, Layout};
+use crate::exit_code::ExitCode;
+use crate::flags::{
+    Block, ColorOption, Display, Flags, IconOption, Layout, SymlinkDisplay, SymlinkSize,
+};
 use crate::print_error;
 
-use std::fs::read_link;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fs::{read_link, Metadata};
 use std::io::{Error, ErrorKind};
 use std::path::{Component, Path, PathBuf};
 
@@ -37,21 +58,97 @@ pub struct Meta {
     pub path: PathBuf,
     pub permissions: Permissions,
     pub date: Date,
+    pub accessed: Date,
+    /// The date of the last status change.
+    pub changed: Date,
     pub owner: Owner,
     pub file_type: FileType,
     pub size: Size,
+    /// The size of the target of a symbolic link, when it is not broken.
+    pub target_size: Option<Size>,
     pub symlink: SymLink,
     pub indicator: Indicator,
     pub inode: INode,
     pub links: Links,
+    pub capabilities: Option<Capabilities>,
+    /// The number of entries of a directory, only counted when the children block is shown.
+    pub children: Option<Children>,
+    /// The status of a socket or a FIFO, only detected with `--ipc-status`.
+    pub ipc_status: Option<IpcStatus>,
+    /// The type and size of the target of a symbolic link, only read when its block is shown.
+    pub link_target_info: Option<LinkTargetInfo>,
+    /// The type of the filesystem mounted on a directory, only looked up when its block is shown.
+    pub fs_type: Option<FsType>,
+    pub file_flags: FileFlags,
     pub content: Option<Vec<Meta>>,
+    /// Whether this directory could not be read when recursing into it.
+    pub unreadable: bool,
+    /// The number of entries of this directory left out of its content.
+    pub filtered: Filtered,
+    /// The number of entries of this directory left out by `--max-entries`.
+    pub truncated: usize,
 }
 
 impl Meta {
+    /// Keep the first `max` entries of the content of this directory, counting the other ones.
+    pub fn truncate_content(&mut self, max: usize) {
+        if let Some(content) = &mut self.content {
+            if content.len() > max {
+                self.truncated = content.len() - max;
+                content.truncate(max);
+            }
+        }
+    }
+
     pub fn recurse_into(
-        &self,
+        &mut self,
+        depth: usize,
+        flags: &Flags,
+    ) -> Result<Option<Vec<Meta>>, std::io::Error> {
+        self.read_entries(depth, flags, false)
+    }
+
+    /// Read the content of this directory without the one of its entries, which is read later
+    /// with [Meta::recurse_into_entries] when walking `depth` levels: until then, the
+    /// subdirectories out of the time range are kept, as their content may be listed.
+    pub fn read_content(
+        &mut self,
+        depth: usize,
+        flags: &Flags,
+    ) -> Result<Option<Vec<Meta>>, std::io::Error> {
+        self.read_entries(depth.min(1), flags, depth > 1)
+    }
+
+    /// Read the content of the entries of this directory, whose own content was read with
+    /// [Meta::read_content], then leave out the ones out of the time range.
+    pub fn recurse_into_entries(&mut self, depth: usize, flags: &Flags) {
+        if depth < 2 {
+            return;
+        }
+        let device = if flags.one_file_system.0 {
+            Self::device(&self.path)
+        } else {
+            None
+        };
+
+        if let Some(content) = &mut self.content {
+            for entry in content.iter_mut().filter(|entry| !entry.is_dot_entry()) {
+                if !Self::skips_recursion(&entry.path, device, flags) {
+                    match entry.read_content(depth - 1, flags) {
+                        Ok(content) => entry.content = content,
+                        Err(err) => entry.report_unreadable(&err),
+                    };
+                }
+            }
+            content.retain(|entry| entry.is_dot_entry() || entry.is_within_time_range(flags));
+        }
+    }
+
+    fn read_entries(
+        &mut self,
         depth: usize,
         flags: &Flags,
+        deferred: bool,
     ) -> Result<Option<Vec<Meta>>, std::io::Error> {
         if depth == 0 {
             return Ok(None);
@@ -71,24 +168,33 @@ impl Meta {
             _ => return Ok(None),
         }
 
-        let entries = match self.path.read_dir() {
-            Ok(entries) => entries,
-            Err(err) => {
-                print_error!("{}: {}.", self.path.display(), err);
-                return Ok(None);
-            }
-        };
+        let entries = Self::fs_path(&self.path).read_dir()?;
 
         let mut content: Vec<Meta> = Vec::new();
+        let mut filtered = Filtered::default();
+        let mut listed = 0;
+        // Every directory recursed into is on the file system of the listed one, so comparing
+        // with the parent is enough.
+        let device = if flags.one_file_system.0 {
+            Self::device(&self.path)
+        } else {
+            None
+        };
+        let export_ignore = if flags.respect_gitattributes.0 {
+            Some(ExportIgnore::for_dir(&self.path))
+        } else {
+            None
+        };
 
-        if Display::All == flags.display && flags.layout != Layout::Tree {
+        let with_dots = Display::All == flags.display && flags.layout != Layout::Tree;
+        if with_dots {
             let mut current_meta;
 
             current_meta = self.clone();
             current_meta.name.name = ".".to_owned();
 
             let mut parent_meta =
-                Self::from_path(&self.path.join(Component::ParentDir), flags.dereference.0)?;
+                Self::from_path_with(&self.path.join(Component::ParentDir), flags)?;
             parent_meta.name.name = "..".to_owned();
 
             content.push(current_meta);
@@ -97,26 +203,30 @@ impl Meta {
 
         for entry in entries {
             let entry = entry?;
-            let path = entry.path();
+            // Keep the path as given, rather than the one to read the file system with.
+            let path = self.path.join(entry.file_name());
 
             let name = path
                 .file_name()
                 .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid file name"))?;
 
-            if flags.ignore_globs.0.is_match(&name) {
+            let export_ignored =
+                matches!(&export_ignore, Some(export_ignore) if export_ignore.is_ignored(name));
+            let unlisted = match Self::unlisted(&path, flags) {
+                None if export_ignored => Some(Unlisted::Ignored),
+                unlisted => unlisted,
+            };
+            if let Some(unlisted) = unlisted {
+                filtered.add(unlisted);
                 continue;
             }
+            listed += 1;
 
-            if let Display::VisibleOnly = flags.display {
-                if name.to_string_lossy().starts_with('.') {
-                    continue;
-                }
-            }
-
-            let mut entry_meta = match Self::from_path(&path, flags.dereference.0) {
+            let mut entry_meta = match Self::from_path_with(&path, flags) {
                 Ok(res) => res,
                 Err(err) => {
                     print_error!("{}: {}.", path.display(), err);
+                    ExitCode::MinorIssue.set_if_greater();
                     continue;
                 }
             };
@@ -130,86 +240,298 @@ impl Meta {
                 }
             }
 
-            match entry_meta.recurse_into(depth - 1, &flags) {
-                Ok(content) => entry_meta.content = content,
-                Err(err) => {
-                    print_error!("{}: {}.", path.display(), err);
+            let skip_recursion = Self::skips_recursion(&path, device, flags);
+            if !skip_recursion {
+                match entry_meta.recurse_into(depth - 1, &flags) {
+                    Ok(content) => entry_meta.content = content,
+                    Err(err) => entry_meta.report_unreadable(&err),
+                };
+            }
+
+            let read_later = deferred && !skip_recursion && entry_meta.file_type.is_dirlike();
+            if !read_later {
+                entry_meta.probe_empty_dir(flags);
+                if !entry_meta.is_within_time_range(flags) {
                     continue;
                 }
-            };
+            }
 
             content.push(entry_meta);
         }
 
+        self.filtered = filtered;
+        self.name.empty_dir = listed == 0 && matches!(self.file_type, FileType::Directory { .. });
+        if with_dots {
+            content[0].name.empty_dir = self.name.empty_dir;
+        }
         Ok(Some(content))
     }
 
-    pub fn calculate_total_size(&mut self) {
+    /// Whether the recursion stops at an entry of a directory on the `device` file system: a
+    /// hidden one with `--no-hidden-recurse`, or one on another file system with
+    /// `--one-file-system`.
+    fn skips_recursion(path: &Path, device: Option<u64>, flags: &Flags) -> bool {
+        (flags.no_hidden_recurse.0 && Self::is_hidden(path))
+            || (device.is_some() && Self::device(path) != device)
+    }
+
+    /// Whether an entry is listed with the time range: the directories whose content is listed
+    /// are kept, as it may be within the range.
+    fn is_within_time_range(&self, flags: &Flags) -> bool {
+        self.content.is_some() || self.date.is_within(&flags.time_range)
+    }
+
+    /// Whether this is the `.` or the `..` entry listed with `--all`.
+    fn is_dot_entry(&self) -> bool {
+        self.name.name == "." || self.name.name == ".."
+    }
+
+    /// Why an entry of a directory is left out of the listing with the given flags, according to
+    /// the ignore globs and whether the hidden files are displayed, or [None] if it is listed.
+    pub fn unlisted(path: &Path, flags: &Flags) -> Option<Unlisted> {
+        let name = path.file_name()?;
+        if flags.ignore_globs.0.is_match(name) {
+            return Some(Unlisted::Ignored);
+        }
+
+        if flags.display == Display::VisibleOnly && Self::is_hidden(path) {
+            Some(Unlisted::Hidden)
+        } else {
+            None
+        }
+    }
+
+    /// Tell whether this directory has no entry listed with the given flags, for the icon of the
+    /// empty directories. It is only read when its content is not, and when the icon is shown.
+    /// An unreadable directory is not told empty.
+    pub fn probe_empty_dir(&mut self, flags: &Flags) {
+        let shown = flags.icons.when != IconOption::Never
+            && flags.blocks.0.contains(&Block::Name)
+            && !flags.icons.hide_for.hides(self.file_type);
+        if self.content.is_some() || !shown {
+            return;
+        }
+        if !matches!(self.file_type, FileType::Directory { .. }) {
+            return;
+        }
+
+        self.name.empty_dir = match Self::fs_path(&self.path).read_dir() {
+            Ok(entries) => !entries
+                .filter_map(Result::ok)
+                .any(|entry| Self::unlisted(&self.path.join(entry.file_name()), flags).is_none()),
+            Err(_) => false,
+        };
+    }
+
+    /// Check if a file is hidden, that is if its name starts with a dot or, on Windows, if it has
+    /// the hidden or the system attribute.
+    pub fn is_hidden(path: &Path) -> bool {
+        let hidden = match path.file_name() {
+            Some(name) => name.to_string_lossy().starts_with('.'),
+            None => false,
+        };
+
+        #[cfg(windows)]
+        let hidden = hidden || windows_utils::is_hidden(&Self::fs_path(path));
+
+        hidden
+    }
+
+    /// The size shown in the size column, which depends on `--symlink-size` for the symbolic
+    /// links, or [None] to show a dash.
+    pub fn displayed_size(&self, flags: &Flags) -> Option<&Size> {
+        match (self.file_type, flags.symlink_size) {
+            (FileType::SymLink { .. }, SymlinkSize::Target) => self.target_size.as_ref(),
+            (FileType::SymLink { .. }, SymlinkSize::None) => None,
+            _ => Some(&self.size),
+        }
+    }
+
+    /// Report that this directory could not be read, keeping it listed with a marker.
+    pub fn report_unreadable(&mut self, err: &Error) {
+        print_error!("{}: {}.", self.path.display(), err);
+        self.unreadable = true;
+        ExitCode::MajorIssue.set_if_greater();
+    }
+
+    /// Replace the size of a directory by the total size of its content. Like `du`, the files
+    /// with several hard links are counted once, the identifiers of the ones already counted
+    /// being kept in `counted`.
+    pub fn calculate_total_size(&mut self, counted: &mut HashSet<(u64, u64)>) {
         if let FileType::Directory { .. } = self.file_type {
             if let Some(metas) = &mut self.content {
-                let mut size_accumulated = self.size.get_bytes();
+                let mut size_accumulated = self.size.clone();
                 for x in &mut metas.iter_mut() {
-                    x.calculate_total_size();
-                    size_accumulated += x.size.get_bytes();
+                    x.calculate_total_size(counted);
+                    if !x.is_counted(counted) {
+                        size_accumulated.accumulate(&x.size);
+                    }
                 }
-                self.size = Size::new(size_accumulated);
+                self.size = size_accumulated;
             } else {
                 // possibility that 'depth' limited the recursion in 'recurse_into'
-                self.size = Size::new(Meta::calculate_total_file_size(&self.path));
+                self.size = Meta::calculate_total_file_size(&self.path, counted);
             }
         }
     }
 
-    fn calculate_total_file_size(path: &PathBuf) -> u64 {
-        let metadata = if read_link(&path).is_ok() {
+    /// Whether the entry is a file with several hard links which was already counted in the
+    /// total size, otherwise it is recorded as counted.
+    fn is_counted(&self, counted: &mut HashSet<(u64, u64)>) -> bool {
+        if !matches!(self.file_type, FileType::File { .. }) || self.links.nlink().unwrap_or(1) < 2 {
+            return false;
+        }
+        match Self::fs_path(&self.path).symlink_metadata() {
+            Ok(metadata) => Self::is_counted_file(&metadata, counted),
+            Err(_) => false,
+        }
+    }
+
+    /// Whether a file with several hard links was already counted in the total size, otherwise
+    /// it is recorded as counted by its device and inode.
+    #[cfg(unix)]
+    fn is_counted_file(metadata: &Metadata, counted: &mut HashSet<(u64, u64)>) -> bool {
+        use std::os::unix::fs::MetadataExt;
+
+        metadata.nlink() > 1 && !counted.insert((metadata.dev(), metadata.ino()))
+    }
+
+    #[cfg(not(unix))]
+    fn is_counted_file(_: &Metadata, _: &mut HashSet<(u64, u64)>) -> bool {
+        false
+    }
+
+    fn calculate_total_file_size(path: &Path, counted: &mut HashSet<(u64, u64)>) -> Size {
+        let fs_path = Self::fs_path(path);
+        let metadata = if read_link(&fs_path).is_ok() {
             // If the file is a link, retrieve the metadata without following
             // the link.
-            path.symlink_metadata()
+            fs_path.symlink_metadata()
         } else {
-            path.metadata()
+            fs_path.metadata()
         };
         let metadata = match metadata {
             Ok(meta) => meta,
             Err(err) => {
                 print_error!("{}: {}.", path.display(), err);
-                return 0;
+                ExitCode::MinorIssue.set_if_greater();
+                return Size::empty();
             }
         };
         let file_type = metadata.file_type();
-        if file_type.is_file() {
-            metadata.len()
+        if file_type.is_file() && Self::is_counted_file(&metadata, counted) {
+            Size::empty()
+        } else if file_type.is_file() {
+            Size::from(&metadata)
         } else if file_type.is_dir() {
-            let mut size = metadata.len();
+            let mut size = Size::from(&metadata);
 
-            let entries = match path.read_dir() {
+            let entries = match fs_path.read_dir() {
                 Ok(entries) => entries,
                 Err(err) => {
                     print_error!("{}: {}.", path.display(), err);
+                    ExitCode::MinorIssue.set_if_greater();
                     return size;
                 }
             };
             for entry in entries {
                 let path = match entry {
-                    Ok(entry) => entry.path(),
+                    Ok(entry) => path.join(entry.file_name()),
                     Err(err) => {
                         print_error!("{}: {}.", path.display(), err);
+                        ExitCode::MinorIssue.set_if_greater();
                         continue;
                     }
                 };
-                size += Meta::calculate_total_file_size(&path);
+                size.accumulate(&Meta::calculate_total_file_size(&path, counted));
             }
             size
         } else {
-            0
+            Size::empty()
+        }
+    }
+
+    /// The path to read the file system with, which lifts the limit of the length of the paths
+    /// on Windows.
+    fn fs_path(path: &Path) -> Cow<'_, Path> {
+        #[cfg(windows)]
+        return Cow::Owned(windows_utils::long_path(path));
+
+        #[cfg(not(windows))]
+        Cow::Borrowed(path)
+    }
+
+    /// The identifier of the device holding the file system of a path, where it is known.
+    #[cfg(unix)]
+    fn device(path: &Path) -> Option<u64> {
+        use std::os::unix::fs::MetadataExt;
+
+        path.metadata().ok().map(|metadata| metadata.dev())
+    }
+
+    #[cfg(not(unix))]
+    fn device(_: &Path) -> Option<u64> {
+        None
+    }
+
+    /// Like [Meta::from_path], also reading the details needed by the blocks shown with the given
+    /// flags, once when the entry is collected rather than each time it is rendered.
+    pub fn from_path_with(path: &Path, flags: &Flags) -> Result<Self, std::io::Error> {
+        let mut meta = Self::from_path(path, flags.dereference.0)?;
+        if flags.symlink_display == SymlinkDisplay::Name && !flags.dereference.0 {
+            meta.read_target_metadata();
+        }
+        // The capabilities are shown in their block, and told by the color of the name.
+        let with_capabilities =
+            flags.blocks.0.contains(&Block::Capabilities) || flags.color.when != ColorOption::Never;
+        if with_capabilities && matches!(meta.file_type, FileType::File { .. }) {
+            meta.capabilities = Capabilities::from_path(path);
+            meta.name.has_capabilities = meta.capabilities.is_some();
+        }
+        if flags.blocks.0.contains(&Block::Children) {
+            meta.children = Some(Children::new(path, meta.file_type, flags));
+        }
+        if flags.ipc_status.0 {
+            meta.ipc_status = Some(IpcStatus::new(path, meta.file_type));
+        }
+        if flags.blocks.0.contains(&Block::LinkTargetInfo) {
+            meta.link_target_info = Some(LinkTargetInfo::new(path, meta.file_type));
+        }
+        if flags.blocks.0.contains(&Block::FsType) {
+            meta.fs_type = Some(FsType::new(path, meta.file_type));
+        }
+        if flags.magic.0 {
+            meta.name.read_magic();
+        }
+        Ok(meta)
+    }
+
+    /// Take the metadata of the target of this link in place of its own, as only its name is
+    /// shown with `--symlink-display name`. The link is still not followed when recursing.
+    fn read_target_metadata(&mut self) {
+        if !matches!(self.file_type, FileType::SymLink { .. }) {
+            return;
+        }
+        if let Ok(target) = Self::from_path(&self.path, true) {
+            self.inode = target.inode;
+            self.links = target.links;
+            self.file_flags = target.file_flags;
+            self.size = target.size;
+            self.date = target.date;
+            self.accessed = target.accessed;
+            self.changed = target.changed;
+            self.owner = target.owner;
+            self.permissions = target.permissions;
         }
     }
 
     pub fn from_path(path: &Path, dereference: bool) -> Result<Self, std::io::Error> {
+        let fs_path = Self::fs_path(path);
         // If the file is a link then retrieve link metadata instead with target metadata (if present).
-        let (metadata, symlink_meta) = if read_link(path).is_ok() && !dereference {
-            (path.symlink_metadata()?, path.metadata().ok())
+        let (metadata, symlink_meta) = if read_link(&fs_path).is_ok() && !dereference {
+            (fs_path.symlink_metadata()?, fs_path.metadata().ok())
         } else {
-            (path.metadata()?, None)
+            (fs_path.metadata()?, None)
         };
 
         #[cfg(unix)]
@@ -218,26 +540,75 @@ impl Meta {
         let permissions = Permissions::from(&metadata);
 
         #[cfg(windows)]
-        let (owner, permissions) = windows_utils::get_file_data(&path)?;
+        let (owner, permissions) = windows_utils::get_file_data(&fs_path)?;
 
         let file_type = FileType::new(&metadata, symlink_meta.as_ref(), &permissions);
-        let name = Name::new(&path, file_type);
+        // Every file may be executed according to its permissions on Windows, so the executables
+        // are told by their extension instead.
+        #[cfg(windows)]
+        let file_type = match file_type {
+            FileType::File { uid, .. } => FileType::File {
+                uid,
+                exec: windows_utils::has_executable_extension(path),
+            },
+            file_type => file_type,
+        };
+        let mut name = Name::new(&path, file_type);
+        name.world_writable = permissions.other_write;
+        name.sticky = permissions.sticky;
+        name.broken = matches!(file_type, FileType::SymLink { .. }) && symlink_meta.is_none();
         let inode = INode::from(&metadata);
         let links = Links::from(&metadata);
 
         Ok(Self {
             inode,
             links,
+            capabilities: None,
+            children: None,
+            ipc_status: None,
+            link_target_info: None,
+            fs_type: None,
+            file_flags: FileFlags::from(&metadata),
             path: path.to_path_buf(),
-            symlink: SymLink::from(path),
+            symlink: SymLink::from(fs_path.as_ref()),
             size: Size::from(&metadata),
+            target_size: symlink_meta.as_ref().map(Size::from),
             date: Date::from(&metadata),
+            accessed: Date::accessed(&metadata),
+            changed: Date::changed(&metadata),
             indicator: Indicator::from(file_type),
             owner,
             permissions,
             name,
             file_type,
             content: None,
+            unreadable: false,
+            filtered: Filtered::default(),
+            truncated: 0,
         })
     }
 }
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::Meta;
+    use crate::flags::{Flags, OneFileSystem};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_skips_recursion_on_another_device() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path();
+        let device = Meta::device(path).unwrap();
+        let flags = Flags {
+            one_file_system: OneFileSystem(true),
+            ..Flags::default()
+        };
+
+        assert!(!Meta::skips_recursion(path, Some(device), &flags));
+        assert!(Meta::skips_recursion(path, Some(device + 1), &flags));
+        // without --one-file-system, no device is compared with
+        assert!(!Meta::skips_recursion(path, None, &flags));
+    }
+}
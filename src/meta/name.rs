@@ -1,9 +1,13 @@
 use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::{ControlChars, Flags, HyperlinkOption, QuotingStyle};
 use crate::icon::Icons;
 use crate::meta::filetype::FileType;
+use std::borrow::Cow;
 use std::cmp::{Ordering, PartialOrd};
 use std::ffi::OsStr;
 use std::path::{Component, Path, PathBuf};
+use unicode_normalization::UnicodeNormalization;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 #[derive(Debug)]
 pub enum DisplayOption<'a> {
@@ -12,11 +16,44 @@ pub enum DisplayOption<'a> {
     None,
 }
 
+/// The compound extensions known to be a single extension, like the compressed tarballs.
+const COMPOUND_EXTENSIONS: &[&str] = &[
+    "tar.gz", "tar.bz2", "tar.xz", "tar.zst", "tar.lz", "tar.lzma", "tar.z",
+];
+
+/// The first bytes of the common file types, with the extension they are usually named with.
+const MAGIC_NUMBERS: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "png"),
+    (b"\xff\xd8\xff", "jpg"),
+    (b"GIF8", "gif"),
+    (b"%PDF-", "pdf"),
+    (b"PK\x03\x04", "zip"),
+    (b"\x1f\x8b", "gz"),
+    (b"\x7fELF", "bin"),
+    (b"#!", "sh"),
+];
+
+/// The number of bytes read to tell the type of a file by its content.
+const MAGIC_LENGTH: u64 = 16;
+
 #[derive(Clone, Debug, Eq)]
 pub struct Name {
     pub name: String,
+    /// Whether the file carries capabilities, which color it specially.
+    pub has_capabilities: bool,
+    /// Whether the directory is writable by others, which colors it specially, as well as
+    /// whether it has the sticky bit.
+    pub world_writable: bool,
+    pub sticky: bool,
+    /// Whether the file is a symlink whose target does not resolve, which colors it specially.
+    pub broken: bool,
+    /// Whether the file is a directory without any listed entry, which has its own icon. It is
+    /// only told when the icons are shown.
+    pub empty_dir: bool,
     path: PathBuf,
     extension: Option<String>,
+    /// The extension told by the content of the file, only read with `--magic`.
+    magic_extension: Option<&'static str>,
     file_type: FileType,
 }
 
@@ -33,12 +70,25 @@ impl Name {
 
         Self {
             name,
+            has_capabilities: false,
+            world_writable: false,
+            sticky: false,
+            broken: false,
+            empty_dir: false,
             path: PathBuf::from(path),
             extension,
+            magic_extension: None,
             file_type,
         }
     }
 
+    /// Compose the name and the extension in the NFC form, so that the names decomposed by the
+    /// file system compare equal to the ones typed in.
+    pub fn normalize(&mut self) {
+        self.name = self.name.nfc().collect();
+        self.extension = self.extension.as_ref().map(|ext| ext.nfc().collect());
+    }
+
     pub fn file_name(&self) -> &str {
         self.path
             .file_name()
@@ -46,6 +96,50 @@ impl Name {
             .unwrap_or(&self.name)
     }
 
+    /// The file name as it is stored on the file system, which may not be valid UTF-8.
+    fn raw_file_name(&self) -> &OsStr {
+        self.path
+            .file_name()
+            .unwrap_or_else(|| OsStr::new(&self.name))
+    }
+
+    /// Decode a name, escaping its valid UTF-8 parts with `escape` and each of its invalid bytes
+    /// with `escape_byte`, rather than replacing them with U+FFFD.
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    fn decode(
+        string: &OsStr,
+        escape: impl Fn(&str) -> String,
+        escape_byte: impl Fn(u8) -> String,
+    ) -> String {
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+
+            let mut bytes = string.as_bytes();
+            let mut decoded = String::with_capacity(bytes.len());
+            loop {
+                match std::str::from_utf8(bytes) {
+                    Ok(valid) => {
+                        decoded += &escape(valid);
+                        return decoded;
+                    }
+                    Err(err) => {
+                        let (valid, invalid) = bytes.split_at(err.valid_up_to());
+                        decoded += &escape(std::str::from_utf8(valid).unwrap_or_default());
+                        let length = err.error_len().unwrap_or(invalid.len());
+                        for byte in &invalid[..length] {
+                            decoded += &escape_byte(*byte);
+                        }
+                        bytes = &invalid[length..];
+                    }
+                }
+            }
+        }
+
+        #[cfg(not(unix))]
+        escape(&string.to_string_lossy())
+    }
+
     fn relative_path<T: AsRef<Path> + Clone>(&self, base_path: T) -> PathBuf {
         let base_path = base_path.as_ref();
 
@@ -96,46 +190,245 @@ impl Name {
         }
     }
 
+    /// Replace each control character with a `?`, like GNU ls does on a tty.
+    fn hide_control_chars(&self, string: &str) -> String {
+        string
+            .chars()
+            .map(|c| {
+                if c < 0x20 as char || c == 0x7f as char {
+                    '?'
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+
+    /// Escape a name as a C string, for `--quote-name`: the double quotes and the backslashes
+    /// are preceded by a backslash, and the control characters are escaped.
+    fn escape_c(&self, string: &str) -> String {
+        let mut chars = String::with_capacity(string.len());
+        for c in string.chars() {
+            match c {
+                '"' | '\\' => {
+                    chars.push('\\');
+                    chars.push(c);
+                }
+                '\u{7}' => chars += "\\a",
+                '\u{8}' => chars += "\\b",
+                '\u{b}' => chars += "\\v",
+                '\u{c}' => chars += "\\f",
+                '\u{1b}' => chars += "\\e",
+                '\t' => chars += "\\t",
+                '\n' => chars += "\\n",
+                '\r' => chars += "\\r",
+                c if c < 0x20 as char || c == 0x7f as char => {
+                    chars += &format!("\\{:03o}", c as u32)
+                }
+                c => chars.push(c),
+            }
+        }
+        chars
+    }
+
     pub fn render(
         &self,
         colors: &Colors,
         icons: &Icons,
         display_option: &DisplayOption,
+        flags: &Flags,
     ) -> ColoredString {
-        let content = match display_option {
-            DisplayOption::FileName => {
-                format!("{}{}", icons.get(self), self.escape(self.file_name()))
+        let normalize = |string: Cow<str>| -> String {
+            if flags.normalize.0 {
+                string.nfc().collect()
+            } else {
+                string.into_owned()
             }
-            DisplayOption::Relative { base_path } => format!(
-                "{}{}",
-                icons.get(self),
-                self.escape(&self.relative_path(base_path).to_string_lossy())
-            ),
-            DisplayOption::None => format!(
-                "{}{}",
-                icons.get(self),
-                self.escape(&self.path.to_string_lossy())
+        };
+        let escape = |string: &OsStr| match flags.quoting_style {
+            QuotingStyle::Auto => {
+                let string = normalize(string.to_string_lossy());
+                if flags.quote_name.0 {
+                    return self.escape_c(&string);
+                }
+                match flags.control_chars {
+                    ControlChars::Hide => self.hide_control_chars(&string),
+                    ControlChars::Show => string,
+                    ControlChars::Auto | ControlChars::Escape => self.escape(&string),
+                }
+            }
+            QuotingStyle::Literal => normalize(string.to_string_lossy()),
+            _ => Self::decode(
+                string,
+                |valid| self.escape_c(&normalize(Cow::Borrowed(valid))),
+                |byte| format!("\\x{:02x}", byte),
             ),
         };
+        let name = match display_option {
+            DisplayOption::FileName => self.truncate(&escape(self.raw_file_name()), flags),
+            DisplayOption::Relative { base_path } => {
+                self.truncate(&escape(self.relative_path(base_path).as_os_str()), flags)
+            }
+            DisplayOption::None => escape(self.path.as_os_str()),
+        };
+        let content = match flags.quoting_style {
+            QuotingStyle::Auto if flags.quote_name.0 => format!("{}\"{}\"", icons.get(self), name),
+            QuotingStyle::C => format!("{}\"{}\"", icons.get(self), name),
+            QuotingStyle::Locale => format!("{}‘{}’", icons.get(self), name),
+            _ => format!("{}{}", icons.get(self), name),
+        };
+
+        let content = match flags.hyperlink {
+            HyperlinkOption::Always => match self.hyperlink_uri() {
+                Some(uri) => format!("\x1B]8;;{}\x1B\\{}\x1B]8;;\x1B\\", uri, content),
+                None => content,
+            },
+            _ => content,
+        };
 
         let elem = match self.file_type {
             FileType::CharDevice => Elem::CharDevice,
+            FileType::Directory { .. } if self.world_writable => Elem::WorldWritableDir {
+                sticky: self.sticky,
+            },
             FileType::Directory { uid } => Elem::Dir { uid },
+            FileType::SymLink { .. } if self.broken => Elem::BrokenSymLink,
             FileType::SymLink { .. } => Elem::SymLink,
             FileType::File { uid, exec } => Elem::File { uid, exec },
+            FileType::Pipe => Elem::Pipe,
+            FileType::Socket => Elem::Socket,
             _ => Elem::File {
                 exec: false,
                 uid: false,
             },
         };
 
+        if self.has_capabilities {
+            // The LS_COLORS of the path would color the file by its extension instead
+            return colors.colorize(content, &Elem::Capability);
+        }
+
+        if let Some(colour) = flags.name_colors.get(&self.name) {
+            return colors.recolor(content, colour);
+        }
+
         colors.colorize_using_path(content, &self.path, &elem)
     }
 
+    /// Truncate a name wider than the `--truncate` width in its middle with an ellipsis, the end
+    /// being kept wide enough to show the extension.
+    fn truncate(&self, name: &str, flags: &Flags) -> String {
+        let max_width = match flags.truncate.0 {
+            Some(max_width) if max_width > 0 && UnicodeWidthStr::width(name) > max_width => {
+                max_width
+            }
+            _ => return name.to_string(),
+        };
+
+        // One cell is taken by the ellipsis, the others are shared by the start and the end.
+        let width = max_width - 1;
+        let extension_width = self.extension.as_ref().map_or(0, |extension| {
+            UnicodeWidthStr::width(extension.as_str()) + 1
+        });
+        // The end keeps as much of the extension as possible, and at least a cell for the start.
+        let end_width = (width / 2).max(extension_width.min(width.saturating_sub(1)));
+
+        let take = |chars: &mut dyn Iterator<Item = char>, max_width: usize| {
+            let mut width = 0;
+            chars
+                .take_while(|c| {
+                    width += c.width().unwrap_or(0);
+                    width <= max_width
+                })
+                .collect::<Vec<char>>()
+        };
+        let start: String = take(&mut name.chars(), width - end_width)
+            .into_iter()
+            .collect();
+        let end: String = take(&mut name.chars().rev(), end_width)
+            .into_iter()
+            .rev()
+            .collect();
+
+        format!("{}\u{2026}{}", start, end)
+    }
+
+    /// The path the hyperlink of the file points to: the resolved target of a symlink, or the
+    /// path of the link itself when it is broken, and the path of the file for the other types.
+    fn hyperlink_target(&self) -> Option<PathBuf> {
+        match self.path.canonicalize() {
+            Ok(path) => Some(path),
+            Err(_) if matches!(self.file_type, FileType::SymLink { .. }) => {
+                let parent = match self.path.parent() {
+                    Some(parent) if parent != Path::new("") => parent.canonicalize().ok()?,
+                    _ => std::env::current_dir().ok()?,
+                };
+                Some(parent.join(self.path.file_name()?))
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// The `file://` URI of the hyperlink target, with the bytes which are not allowed in a URI
+    /// path percent-encoded.
+    fn hyperlink_uri(&self) -> Option<String> {
+        let target = self.hyperlink_target()?;
+        let mut uri = String::from("file://");
+        for byte in target.to_string_lossy().bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                    uri.push(byte as char)
+                }
+                _ => uri.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        Some(uri)
+    }
+
     pub fn extension(&self) -> Option<&str> {
         self.extension.as_deref()
     }
 
+    /// The extension usually given to the type of this file, told by its first bytes when it
+    /// was read with [Name::read_magic].
+    pub fn magic_extension(&self) -> Option<&'static str> {
+        self.magic_extension
+    }
+
+    /// Tell the type of a regular file without an extension by its first bytes, keeping the
+    /// extension usually given to this type.
+    pub fn read_magic(&mut self) {
+        use std::io::Read;
+
+        if self.extension.is_some() || !matches!(self.file_type, FileType::File { .. }) {
+            return;
+        }
+
+        let mut header = Vec::with_capacity(MAGIC_LENGTH as usize);
+        let read = std::fs::File::open(&self.path)
+            .and_then(|file| file.take(MAGIC_LENGTH).read_to_end(&mut header));
+        if read.is_ok() {
+            self.magic_extension = MAGIC_NUMBERS
+                .iter()
+                .find(|(magic, _)| header.starts_with(magic))
+                .map(|(_, extension)| *extension);
+        }
+    }
+
+    /// The extension, including the known compound extensions like `tar.gz` as a whole.
+    pub fn compound_extension(&self) -> Option<&str> {
+        let lowercase = self.name.to_lowercase();
+        let compound = COMPOUND_EXTENSIONS.iter().find(|compound| {
+            lowercase.len() > compound.len() + 1
+                && lowercase.ends_with(*compound)
+                && lowercase[..lowercase.len() - compound.len()].ends_with('.')
+        });
+        match compound {
+            Some(compound) => Some(&self.name[self.name.len() - compound.len()..]),
+            None => self.extension(),
+        }
+    }
+
     pub fn file_type(&self) -> FileType {
         self.file_type
     }
@@ -166,6 +459,11 @@ mod test {
     use super::DisplayOption;
     use super::Name;
     use crate::color::{self, Colors};
+    use crate::config_file::Config;
+    use crate::flags::{
+        Configurable, ControlChars, Flags, HyperlinkOption, NameColors, Normalize, QuoteName,
+        QuotingStyle, Truncate,
+    };
     use crate::icon::{self, Icons};
     use crate::meta::FileType;
     use crate::meta::Meta;
@@ -198,7 +496,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(184).paint(" file.txt"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -210,13 +508,49 @@ mod test {
         // Chreate the directory
         let dir_path = tmp_dir.path().join("directory");
         fs::create_dir(&dir_path).expect("failed to create the dir");
+        File::create(dir_path.join("file")).expect("failed to create file");
         let meta = Meta::from_path(&dir_path, false).unwrap();
 
         let colors = Colors::new(color::Theme::NoLscolors);
 
         assert_eq!(
             Colour::Fixed(33).paint(" directory"),
-            meta.name.render(&colors, &icons, &DisplayOption::FileName)
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_print_world_writable_dir_name() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let colors = Colors::new(color::Theme::NoLscolors);
+
+        let other_writable = tmp_dir.path().join("other-writable");
+        fs::create_dir(&other_writable).expect("failed to create the dir");
+        fs::set_permissions(&other_writable, fs::Permissions::from_mode(0o777))
+            .expect("failed to set the permissions");
+        let meta = Meta::from_path(&other_writable, false).unwrap();
+        assert_eq!(
+            Colour::Fixed(33)
+                .on(Colour::Fixed(28))
+                .paint("other-writable"),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
+        );
+
+        let sticky = tmp_dir.path().join("sticky");
+        fs::create_dir(&sticky).expect("failed to create the dir");
+        fs::set_permissions(&sticky, fs::Permissions::from_mode(0o1777))
+            .expect("failed to set the permissions");
+        let meta = Meta::from_path(&sticky, false).unwrap();
+        assert_eq!(
+            Colour::Fixed(16).on(Colour::Fixed(28)).paint("sticky"),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -244,7 +578,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(44).paint(" target.tmp"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -272,7 +606,36 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(44).paint(" target.d"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
+        );
+    }
+
+    #[test]
+    #[cfg(unix)] // Symlinks are hard on Windows
+    fn test_print_symlink_name_valid_and_broken() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let colors = Colors::new(color::Theme::NoLscolors);
+
+        let file_path = tmp_dir.path().join("file.tmp");
+        File::create(&file_path).expect("failed to create file");
+        let valid_path = tmp_dir.path().join("valid.tmp");
+        symlink(&file_path, &valid_path).expect("failed to create symlink");
+        let broken_path = tmp_dir.path().join("broken.tmp");
+        symlink(tmp_dir.path().join("missing"), &broken_path).expect("failed to create symlink");
+
+        let meta = Meta::from_path(&valid_path, false).unwrap();
+        assert_eq!(
+            Colour::Fixed(44).paint("valid.tmp"),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
+        );
+
+        let meta = Meta::from_path(&broken_path, false).unwrap();
+        assert_eq!(
+            Colour::Fixed(124).paint("broken.tmp"),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -297,8 +660,8 @@ mod test {
         let name = Name::new(&pipe_path, file_type);
 
         assert_eq!(
-            Colour::Fixed(184).paint(" pipe.tmp"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            Colour::Fixed(178).paint("\u{f0232} pipe.tmp"),
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -317,12 +680,40 @@ mod test {
         assert_eq!(
             "file.txt",
             meta.name
-                .render(&colors, &icons, &DisplayOption::FileName)
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
                 .to_string()
                 .as_str()
         );
     }
 
+    #[test]
+    fn test_normalize() {
+        let file = FileType::File {
+            uid: false,
+            exec: false,
+        };
+        let mut decomposed = Name::new(Path::new("cafe\u{301}.te\u{301}"), file);
+        let composed = Name::new(Path::new("caf\u{e9}.t\u{e9}"), file);
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let flags = Flags {
+            normalize: Normalize(true),
+            ..Flags::default()
+        };
+
+        assert_ne!(decomposed, composed);
+        assert_eq!(
+            "caf\u{e9}.t\u{e9}",
+            decomposed
+                .render(&colors, &icons, &DisplayOption::FileName, &flags)
+                .to_string()
+        );
+
+        decomposed.normalize();
+        assert_eq!(decomposed, composed);
+        assert_eq!(Some("t\u{e9}"), decomposed.extension());
+    }
+
     #[test]
     fn test_extensions_with_valid_file() {
         let path = Path::new("some-file.txt");
@@ -338,6 +729,27 @@ mod test {
         assert_eq!(Some("txt"), name.extension());
     }
 
+    #[test]
+    fn test_compound_extension() {
+        for (name, extension, compound) in &[
+            ("archive.tar.gz", Some("gz"), Some("tar.gz")),
+            ("Archive.TAR.BZ2", Some("BZ2"), Some("TAR.BZ2")),
+            ("notatar.gz", Some("gz"), Some("gz")),
+            (".tar.gz", Some("gz"), Some("gz")),
+            ("file.txt", Some("txt"), Some("txt")),
+        ] {
+            let name = Name::new(
+                Path::new(name),
+                FileType::File {
+                    uid: false,
+                    exec: false,
+                },
+            );
+            assert_eq!(*extension, name.extension());
+            assert_eq!(*compound, name.compound_extension());
+        }
+    }
+
     #[test]
     fn test_extensions_with_file_without_extension() {
         let path = Path::new(".gitignore");
@@ -533,7 +945,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(184).paint(" file\\ttab.txt"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
 
         let file_path = tmp_dir.path().join("file\nnewline.txt");
@@ -546,7 +958,253 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(184).paint(" file\\nnewline.txt"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
+        );
+    }
+
+    #[test]
+    fn test_render_name_colors() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let colors = Colors::new(color::Theme::NoLscolors);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let config: Config = serde_yaml::from_str("name-colors:\n  '*.bak': 244").unwrap();
+        let flags = Flags {
+            name_colors: NameColors::from_config(&config).unwrap(),
+            ..Flags::default()
+        };
+
+        let file_path = tmp_dir.path().join("foo.bak");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+        assert_eq!(
+            Colour::Fixed(244).paint("foo.bak"),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &flags)
+        );
+
+        let file_path = tmp_dir.path().join("foo.txt");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+        assert_ne!(
+            Colour::Fixed(244).paint("foo.txt"),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &flags)
+        );
+    }
+
+    #[test]
+    fn test_render_quoted() {
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let flags = Flags {
+            quote_name: QuoteName(true),
+            ..Flags::default()
+        };
+
+        for (name, expected) in &[
+            ("file.txt", "\"file.txt\""),
+            ("say \"hi\"", "\"say \\\"hi\\\"\""),
+            ("back\\slash", "\"back\\\\slash\""),
+            ("new\nline\ttab", "\"new\\nline\\ttab\""),
+            ("bell\u{7}del\u{7f}", "\"bell\\adel\\177\""),
+        ] {
+            let name = Name::new(
+                Path::new(name),
+                FileType::File {
+                    uid: false,
+                    exec: false,
+                },
+            );
+            assert_eq!(
+                *expected,
+                name.render(&colors, &icons, &DisplayOption::FileName, &flags)
+                    .to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_control_chars() {
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let name = Name::new(
+            Path::new("new\nline\u{7f}"),
+            FileType::File {
+                uid: false,
+                exec: false,
+            },
+        );
+
+        for (control_chars, expected) in &[
+            (ControlChars::Hide, "new?line?"),
+            (ControlChars::Escape, "new\\nline\\u{7f}"),
+            (ControlChars::Show, "new\nline\u{7f}"),
+        ] {
+            let flags = Flags {
+                control_chars: *control_chars,
+                ..Flags::default()
+            };
+            assert_eq!(
+                *expected,
+                name.render(&colors, &icons, &DisplayOption::FileName, &flags)
+                    .to_string()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_render_invalid_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let name = Name::new(
+            Path::new(OsStr::from_bytes(b"caf\xe9\n\xff.txt")),
+            FileType::File {
+                uid: false,
+                exec: false,
+            },
+        );
+
+        for (quoting_style, expected) in &[
+            (QuotingStyle::Auto, "caf\u{fffd}\\n\u{fffd}.txt"),
+            (QuotingStyle::Literal, "caf\u{fffd}\n\u{fffd}.txt"),
+            (QuotingStyle::Escape, "caf\\xe9\\n\\xff.txt"),
+            (QuotingStyle::C, "\"caf\\xe9\\n\\xff.txt\""),
+            (QuotingStyle::Locale, "‘caf\\xe9\\n\\xff.txt’"),
+        ] {
+            let flags = Flags {
+                quoting_style: *quoting_style,
+                ..Flags::default()
+            };
+            assert_eq!(
+                *expected,
+                name.render(&colors, &icons, &DisplayOption::FileName, &flags)
+                    .to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_truncated() {
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let flags = Flags {
+            truncate: Truncate(Some(10)),
+            ..Flags::default()
+        };
+        let file_type = FileType::File {
+            uid: false,
+            exec: false,
+        };
+
+        for (name, truncated) in &[
+            ("short.txt", "short.txt"),
+            ("exactly10c", "exactly10c"),
+            ("a_very_long_file_name.txt", "a_ver\u{2026}.txt"),
+            ("a_very_long_file_name", "a_ver\u{2026}name"),
+            ("long_extension.markdown", "l\u{2026}markdown"),
+            ("日本語のファイル.txt", "日本\u{2026}.txt"),
+        ] {
+            let name = Name::new(Path::new(name), file_type);
+            assert_eq!(
+                *truncated,
+                name.render(&colors, &icons, &DisplayOption::FileName, &flags)
+                    .to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_hyperlink_dir() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir_path = tmp_dir.path().join("dir");
+        fs::create_dir(&dir_path).expect("failed to create dir");
+        let dir_path = dir_path.canonicalize().unwrap();
+
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let meta = Meta::from_path(&dir_path, false).unwrap();
+
+        assert_eq!(
+            format!(
+                "\x1B]8;;file://{}\x1B\\dir\x1B]8;;\x1B\\",
+                dir_path.display()
+            ),
+            meta.name
+                .render(
+                    &colors,
+                    &icons,
+                    &DisplayOption::FileName,
+                    &Flags {
+                        hyperlink: HyperlinkOption::Always,
+                        ..Flags::default()
+                    }
+                )
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)] // Symlinks are hard on Windows
+    fn test_render_hyperlink_symlink_to_target() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let root = tmp_dir.path().canonicalize().unwrap();
+        File::create(root.join("target file")).expect("failed to create file");
+        symlink("target file", root.join("link")).expect("failed to create symlink");
+
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let meta = Meta::from_path(&root.join("link"), false).unwrap();
+
+        assert_eq!(
+            format!(
+                "\x1B]8;;file://{}/target%20file\x1B\\link\x1B]8;;\x1B\\",
+                root.display()
+            ),
+            meta.name
+                .render(
+                    &colors,
+                    &icons,
+                    &DisplayOption::FileName,
+                    &Flags {
+                        hyperlink: HyperlinkOption::Always,
+                        ..Flags::default()
+                    }
+                )
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)] // Symlinks are hard on Windows
+    fn test_render_hyperlink_broken_symlink() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let root = tmp_dir.path().canonicalize().unwrap();
+        symlink("missing", root.join("link")).expect("failed to create symlink");
+
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let meta = Meta::from_path(&root.join("link"), false).unwrap();
+
+        assert_eq!(
+            format!(
+                "\x1B]8;;file://{}/link\x1B\\link\x1B]8;;\x1B\\",
+                root.display()
+            ),
+            meta.name
+                .render(
+                    &colors,
+                    &icons,
+                    &DisplayOption::FileName,
+                    &Flags {
+                        hyperlink: HyperlinkOption::Always,
+                        ..Flags::default()
+                    }
+                )
+                .to_string()
         );
     }
 }
@@ -36,6 +36,14 @@ impl<'a> From<&'a Metadata> for Owner {
 }
 
 impl Owner {
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
     pub fn render_user(&self, colors: &Colors) -> ColoredString {
         colors.colorize(self.user.clone(), &Elem::User)
     }
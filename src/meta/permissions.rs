@@ -98,6 +98,18 @@ impl Permissions {
         ColoredString::from(res)
     }
 
+    /// The permissions in octal, with the special bits, eg: `0755`.
+    pub fn octal(&self) -> String {
+        let digit = |a: bool, b: bool, c: bool| (a as u8) << 2 | (b as u8) << 1 | c as u8;
+        format!(
+            "{}{}{}{}",
+            digit(self.setuid, self.setgid, self.sticky),
+            digit(self.user_read, self.user_write, self.user_execute),
+            digit(self.group_read, self.group_write, self.group_execute),
+            digit(self.other_read, self.other_write, self.other_execute),
+        )
+    }
+
     pub fn is_executable(&self) -> bool {
         self.user_execute || self.group_execute || self.other_execute
     }
@@ -17,24 +17,67 @@ pub enum Unit {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Size {
     bytes: u64,
+    allocated: Option<u64>,
 }
 
 impl<'a> From<&'a Metadata> for Size {
+    #[cfg(unix)]
+    fn from(meta: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        let len = meta.len();
+        // `blocks` is always expressed in 512-byte units, whatever the fs block size is.
+        Self {
+            bytes: len,
+            allocated: Some(meta.blocks() * 512),
+        }
+    }
+
+    #[cfg(windows)]
     fn from(meta: &Metadata) -> Self {
         let len = meta.len();
-        Self { bytes: len }
+        Self {
+            bytes: len,
+            allocated: None,
+        }
     }
 }
 
 impl Size {
     pub fn new(bytes: u64) -> Self {
-        Self { bytes }
+        Self {
+            bytes,
+            allocated: None,
+        }
+    }
+
+    /// An empty size, which keeps the allocated size available where the platform provides it.
+    pub fn empty() -> Self {
+        Self {
+            bytes: 0,
+            allocated: if cfg!(unix) { Some(0) } else { None },
+        }
+    }
+
+    /// The number of 512-byte blocks allocated, where the platform provides it.
+    pub fn blocks(&self) -> Option<u64> {
+        self.allocated.map(|allocated| allocated / 512)
     }
 
     pub fn get_bytes(&self) -> u64 {
         self.bytes
     }
 
+    /// Adds the apparent and allocated sizes of `other` to `self`. The allocated size is only
+    /// kept when both sides know it.
+    pub fn accumulate(&mut self, other: &Size) {
+        self.bytes += other.bytes;
+        self.allocated = match (self.allocated, other.allocated) {
+            (Some(own), Some(other)) => Some(own + other),
+            _ => None,
+        };
+    }
+
     fn format_size(&self, number: f64) -> String {
         format!("{0:.1$}", number, if number < 10.0 { 1 } else { 0 })
     }
@@ -85,6 +128,8 @@ impl Size {
 
         if unit == Unit::None {
             colors.colorize(content, &Elem::NonFile)
+        } else if self.bytes == 0 {
+            colors.colorize(content, &Elem::FileEmpty)
         } else if unit == Unit::Byte || unit == Unit::Kilo {
             colors.colorize(content, &Elem::FileSmall)
         } else if unit == Unit::Mega {
@@ -105,7 +150,7 @@ impl Size {
 
         match unit {
             Unit::None => "".to_string(),
-            Unit::Byte => self.bytes.to_string(),
+            Unit::Byte => flags.thousands_separator.format(self.bytes),
             Unit::Kilo => self.format_size(((self.bytes as f64) / 1024.0 * 10.0).round() / 10.0),
             Unit::Mega => {
                 self.format_size(((self.bytes as f64) / (1024.0 * 1024.0) * 10.0).round() / 10.0)
@@ -119,6 +164,34 @@ impl Size {
         }
     }
 
+    /// Renders the apparent and the allocated size side by side, e.g. `10K / 12K`.
+    pub fn render_detail(&self, colors: &Colors, flags: &Flags) -> ColoredString<'static> {
+        let allocated = match self.allocated {
+            Some(allocated) => Size::new(allocated).render_compact(colors, flags),
+            None => colors
+                .colorize(String::from("-"), &Elem::NonFile)
+                .to_string(),
+        };
+
+        ColoredString::from(format!(
+            "{} / {}",
+            self.render_compact(colors, flags),
+            allocated
+        ))
+    }
+
+    /// Renders the size without alignment, with a space before the unit only in the default
+    /// format, e.g. `10 KB` or `10K`.
+    pub fn render_compact(&self, colors: &Colors, flags: &Flags) -> String {
+        let mut strings: Vec<ColoredString> = vec![self.render_value(colors, flags)];
+        if flags.size == SizeFlag::Default {
+            strings.push(ColoredString::from(" "));
+        }
+        strings.push(self.render_unit(colors, flags));
+
+        ANSIStrings(&strings).to_string()
+    }
+
     pub fn render_unit(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         let content = self.unit_string(flags);
 
@@ -330,4 +403,56 @@ mod test {
         assert_eq!(size.render(&colors, &flags, Some(2)).to_string(), "42K");
         assert_eq!(size.render(&colors, &flags, Some(3)).to_string(), " 42K");
     }
+
+    #[test]
+    fn render_empty_file_color() {
+        use ansi_term::Colour;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let empty_path = tmp_dir.path().join("empty");
+        std::fs::File::create(&empty_path).expect("failed to create file");
+        let full_path = tmp_dir.path().join("full");
+        std::fs::write(&full_path, b"hello").expect("failed to write file");
+
+        let flags = Flags::default();
+        let colors = Colors::new(Theme::NoLscolors);
+
+        let empty = Size::from(&empty_path.metadata().expect("failed to get metas"));
+        assert_eq!(
+            Colour::Fixed(167).paint("0"),
+            empty.render_value(&colors, &flags)
+        );
+
+        let full = Size::from(&full_path.metadata().expect("failed to get metas"));
+        assert_eq!(
+            Colour::Fixed(229).paint("5"),
+            full.render_value(&colors, &flags)
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn render_detail_apparent_and_allocated() {
+        use std::io::Write;
+        use std::os::unix::fs::MetadataExt;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("small");
+        let mut file = std::fs::File::create(&file_path).expect("failed to create file");
+        file.write_all(b"hello").expect("failed to write file");
+        file.sync_all().expect("failed to sync file");
+        let meta = file_path.metadata().expect("failed to get metas");
+
+        let size = Size::from(&meta);
+        let flags = Flags {
+            size: SizeFlag::Bytes,
+            ..Flags::default()
+        };
+        let colors = Colors::new(Theme::NoColor);
+
+        assert_eq!(
+            size.render_detail(&colors, &flags).to_string(),
+            format!("5 / {}", meta.blocks() * 512)
+        );
+    }
 }
@@ -1,20 +1,26 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::Flags;
+use crate::flags::{Flags, SymlinkTarget};
 use ansi_term::{ANSIString, ANSIStrings};
-use std::fs::read_link;
+use std::fs::{canonicalize, read_link};
 use std::path::Path;
 
 #[derive(Clone, Debug)]
 pub struct SymLink {
     target: Option<String>,
+    /// The canonical absolute path of the target, unless the link is broken.
+    absolute: Option<String>,
     valid: bool,
 }
 
 impl<'a> From<&'a Path> for SymLink {
     fn from(path: &'a Path) -> Self {
         if let Ok(target) = read_link(path) {
+            let absolute = canonicalize(path)
+                .ok()
+                .map(|absolute| absolute.to_string_lossy().to_string());
             if target.is_absolute() || path.parent() == None {
                 return Self {
+                    absolute,
                     valid: target.exists(),
                     target: Some(
                         target
@@ -32,12 +38,14 @@ impl<'a> From<&'a Path> for SymLink {
                         .expect("failed to convert symlink to str")
                         .to_string(),
                 ),
+                absolute,
                 valid: path.parent().unwrap().join(target).exists(),
             };
         }
 
         Self {
             target: None,
+            absolute: None,
             valid: false,
         }
     }
@@ -52,17 +60,34 @@ impl SymLink {
         }
     }
 
-    pub fn render(&self, colors: &Colors, flag: &Flags) -> ColoredString {
-        if let Some(target_string) = self.symlink_string() {
-            let elem = if self.valid {
-                &Elem::SymLink
-            } else {
-                &Elem::BrokenSymLink
-            };
+    /// The target to display, as stored in the link or as its absolute path, which falls back to
+    /// the stored one for broken links.
+    fn display_string(&self, flags: &Flags) -> Option<String> {
+        match (flags.symlink_target, &self.absolute) {
+            (SymlinkTarget::Absolute, Some(absolute)) if self.valid => Some(absolute.clone()),
+            _ => self.symlink_string(),
+        }
+    }
 
+    fn elem(&self) -> &Elem {
+        if self.valid {
+            &Elem::SymLink
+        } else {
+            &Elem::BrokenSymLink
+        }
+    }
+
+    /// Renders the target of the link alone, without any arrow, if there is one.
+    pub fn render_target(&self, colors: &Colors, flags: &Flags) -> Option<ColoredString<'_>> {
+        self.display_string(flags)
+            .map(|target_string| colors.colorize(target_string, self.elem()))
+    }
+
+    pub fn render(&self, colors: &Colors, flag: &Flags) -> ColoredString {
+        if let Some(target_string) = self.display_string(flag) {
             let strings: &[ColoredString] = &[
                 ColoredString::from(format!(" {} ", flag.symlink_arrow)), // ⇒ \u{21d2}
-                colors.colorize(target_string, elem),
+                colors.colorize(target_string, self.elem()),
             ];
 
             let res = ANSIStrings(strings).to_string();
@@ -85,6 +110,7 @@ mod tests {
     fn test_symlink_render_default_valid_target_nocolor() {
         let link = SymLink {
             target: Some("/target".to_string()),
+            absolute: None,
             valid: true,
         };
         let argv = vec!["lsd"];
@@ -103,6 +129,7 @@ mod tests {
     fn test_symlink_render_default_invalid_target_nocolor() {
         let link = SymLink {
             target: Some("/target".to_string()),
+            absolute: None,
             valid: false,
         };
         let argv = vec!["lsd"];
@@ -116,4 +143,34 @@ mod tests {
             .to_string()
         );
     }
+
+    #[test]
+    fn test_symlink_render_absolute_target_nocolor() {
+        let argv = vec!["lsd", "--symlink-target", "absolute"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let link = SymLink {
+            target: Some("../target".to_string()),
+            absolute: Some("/tmp/target".to_string()),
+            valid: true,
+        };
+        assert_eq!(
+            " ⇒ /tmp/target",
+            link.render(&Colors::new(Theme::NoColor), &flags)
+                .to_string()
+        );
+
+        let broken = SymLink {
+            target: Some("../target".to_string()),
+            absolute: None,
+            valid: false,
+        };
+        assert_eq!(
+            " ⇒ ../target",
+            broken
+                .render(&Colors::new(Theme::NoColor), &flags)
+                .to_string()
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use std::ffi::{OsStr, OsString};
 use std::io;
 use std::os::windows::ffi::{OsStrExt, OsStringExt};
-use std::path::Path;
+use std::path::{Component, Path, PathBuf, Prefix};
 use std::ptr::null_mut;
 
 use winapi::ctypes::c_void;
@@ -13,6 +13,78 @@ use super::{Owner, Permissions};
 
 const BUF_SIZE: u32 = 256;
 
+/// The maximum length of a path, unless it is prefixed with `\\?\`.
+const MAX_PATH: usize = 260;
+
+/// Get a path to read the metadata of a file whose path may be longer than `MAX_PATH`.
+///
+/// The long paths are made absolute and prefixed with `\\?\`, which lifts the limit. As Windows
+/// does not normalize the prefixed paths, their `.` and `..` components are resolved here. The
+/// other paths are kept as they are.
+pub fn long_path(path: &Path) -> PathBuf {
+    if path.as_os_str().len() < MAX_PATH {
+        return path.to_path_buf();
+    }
+    let absolute = match std::env::current_dir() {
+        _ if path.is_absolute() => path.to_path_buf(),
+        Ok(current_dir) => current_dir.join(path),
+        Err(_) => return path.to_path_buf(),
+    };
+
+    let mut long = OsString::new();
+    let mut parts: Vec<&OsStr> = vec![];
+    for component in absolute.components() {
+        match component {
+            Component::Prefix(prefix) => match prefix.kind() {
+                Prefix::Disk(disk) => long.push(format!(r"\\?\{}:", disk as char)),
+                Prefix::UNC(server, share) => {
+                    long.push(r"\\?\UNC\");
+                    long.push(server);
+                    long.push(r"\");
+                    long.push(share);
+                }
+                // The path is already prefixed, or is a device.
+                _ => return path.to_path_buf(),
+            },
+            Component::RootDir | Component::CurDir => {}
+            Component::ParentDir => {
+                parts.pop();
+            }
+            Component::Normal(part) => parts.push(part),
+        }
+    }
+    for part in parts {
+        long.push(r"\");
+        long.push(part);
+    }
+    PathBuf::from(long)
+}
+
+/// The extensions of the files Windows runs, as it has no executable permission bit.
+const EXECUTABLE_EXTENSIONS: &[&str] = &["exe", "bat", "cmd", "ps1", "com"];
+
+/// Check if a file is executable, according to its extension.
+pub fn has_executable_extension(path: &Path) -> bool {
+    match path.extension() {
+        Some(extension) => {
+            let extension = extension.to_string_lossy().to_lowercase();
+            EXECUTABLE_EXTENSIONS.contains(&extension.as_str())
+        }
+        None => false,
+    }
+}
+
+/// Check if a file has the hidden or the system attribute, with which Windows hides it.
+pub fn is_hidden(path: &Path) -> bool {
+    use std::os::windows::fs::MetadataExt;
+
+    let hidden = winnt::FILE_ATTRIBUTE_HIDDEN | winnt::FILE_ATTRIBUTE_SYSTEM;
+    match path.symlink_metadata() {
+        Ok(metadata) => metadata.file_attributes() & hidden != 0,
+        Err(_) => false,
+    }
+}
+
 pub fn get_file_data(path: &Path) -> Result<(Owner, Permissions), io::Error> {
     // Overall design:
     // This function allocates some data with GetNamedSecurityInfoW,
@@ -290,6 +362,44 @@ fn buf_from_os(os: &OsStr) -> Vec<u16> {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_has_executable_extension() {
+        for name in &[
+            "setup.exe",
+            "BUILD.CMD",
+            "run.bat",
+            "script.ps1",
+            "tool.com",
+        ] {
+            assert!(has_executable_extension(Path::new(name)), "{}", name);
+        }
+        for name in &["notes.txt", "exe", "archive.exe.zip"] {
+            assert!(!has_executable_extension(Path::new(name)), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_long_path() {
+        let short = Path::new(r"C:\Users\lsd");
+        assert_eq!(short, long_path(short));
+
+        let name = "a".repeat(100);
+        let long = format!(r"C:\{}\.\{}\..\{}\{}", name, name, name, name);
+        assert_eq!(
+            PathBuf::from(format!(r"\\?\C:\{}\{}\{}", name, name, name)),
+            long_path(Path::new(&long))
+        );
+
+        let unc = format!(r"\\server\share\{}\{}\{}", name, name, name);
+        assert_eq!(
+            PathBuf::from(format!(r"\\?\UNC\server\share\{}\{}\{}", name, name, name)),
+            long_path(Path::new(&unc))
+        );
+
+        let verbatim = format!(r"\\?\C:\{}\{}\{}", name, name, name);
+        assert_eq!(Path::new(&verbatim), long_path(Path::new(&verbatim)));
+    }
+
     #[test]
     fn basic_wtf16_behavior() {
         let basic_os = OsString::from("TeSt");
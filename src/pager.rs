@@ -0,0 +1,146 @@
+use crate::flags::Paging;
+use crate::{print_error, print_output};
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// Whether the output should be written through a pager. `height` is the height of the terminal,
+/// it is [None] when stdout is not a terminal, so the output is only paged when forced.
+pub fn is_needed(paging: Paging, output: &str, height: Option<usize>) -> bool {
+    match paging {
+        Paging::Always => true,
+        Paging::Never => false,
+        Paging::Auto => matches!(height, Some(height) if output.lines().count() >= height),
+    }
+}
+
+/// The command of the pager, with the environment variables set for it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PagerCommand {
+    program: String,
+    args: Vec<String>,
+    envs: Vec<(&'static str, &'static str)>,
+}
+
+/// Build the command of the pager: `$PAGER` with its arguments, or `less -R` if it is not set.
+///
+/// The colors are kept by the pager because `LESS` is set to `R` when the user did not set it,
+/// as `$PAGER` is often a plain `less`.
+pub fn command<F>(env: F) -> PagerCommand
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let pager = env("PAGER")
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| String::from("less -R"));
+
+    let mut words = pager.split_whitespace().map(String::from);
+    let program = words.next().unwrap_or_else(|| String::from("less"));
+    let envs = if env("LESS").is_none() {
+        vec![("LESS", "R")]
+    } else {
+        vec![]
+    };
+    PagerCommand {
+        program,
+        args: words.collect(),
+        envs,
+    }
+}
+
+/// Run the pager and write the output to its standard input, then wait for the user to quit it.
+pub fn spawn(command: PagerCommand, output: &str) -> io::Result<()> {
+    let mut child = Command::new(command.program)
+        .args(command.args)
+        .envs(command.envs)
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may be quit before reading the whole output, this is not an error.
+        let _ = stdin.write_all(output.as_bytes());
+    }
+    child.wait()?;
+    Ok(())
+}
+
+/// Write the output through the pager spawned by `spawn` when it is needed, or to stdout.
+///
+/// When the pager can not be run, the output is written to stdout instead.
+pub fn write<S>(output: &str, paging: Paging, height: Option<usize>, spawn: S)
+where
+    S: FnOnce(&str) -> io::Result<()>,
+{
+    if is_needed(paging, output, height) {
+        match spawn(output) {
+            Ok(()) => return,
+            Err(err) => print_error!("failed to run the pager: {}", err),
+        }
+    }
+
+    print_output!("{}", output);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{command, is_needed, write, PagerCommand};
+    use crate::flags::Paging;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_is_needed() {
+        let output = "a\nb\nc\n";
+
+        assert!(is_needed(Paging::Always, output, None));
+        assert!(!is_needed(Paging::Never, output, Some(2)));
+        assert!(is_needed(Paging::Auto, output, Some(2)));
+        assert!(!is_needed(Paging::Auto, output, Some(10)));
+        assert!(!is_needed(Paging::Auto, output, None));
+    }
+
+    #[test]
+    fn test_write_always_spawns_the_pager() {
+        let paged = RefCell::new(None);
+        write("a\n", Paging::Always, None, |output| {
+            paged.replace(Some(output.to_owned()));
+            Ok(())
+        });
+
+        assert_eq!(Some(String::from("a\n")), paged.into_inner());
+    }
+
+    #[test]
+    fn test_write_never_does_not_spawn_the_pager() {
+        write("", Paging::Never, Some(1), |_| {
+            panic!("the pager should not be spawned");
+        });
+    }
+
+    #[test]
+    fn test_command_default() {
+        assert_eq!(
+            PagerCommand {
+                program: String::from("less"),
+                args: vec![String::from("-R")],
+                envs: vec![("LESS", "R")],
+            },
+            command(|_| None)
+        );
+    }
+
+    #[test]
+    fn test_command_from_env() {
+        let command = command(|name| match name {
+            "PAGER" => Some(String::from("most -s")),
+            "LESS" => Some(String::from("FRX")),
+            _ => None,
+        });
+
+        assert_eq!(
+            PagerCommand {
+                program: String::from("most"),
+                args: vec![String::from("-s")],
+                envs: vec![],
+            },
+            command
+        );
+    }
+}
@@ -1,33 +1,145 @@
-use crate::flags::{DirGrouping, Flags, SortColumn, SortOrder};
+use crate::flags::sorting::DotFiles;
+use crate::flags::{DirGrouping, Flags, SortColumn, SortOrder, SortPriority};
 use crate::meta::Meta;
-use human_sort::compare;
+#[cfg(windows)]
+use crate::print_error;
+#[cfg(feature = "natural-locale")]
+use feruca::Collator;
+#[cfg(feature = "natural-locale")]
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::Chars;
 
 pub type SortFn = fn(&Meta, &Meta) -> Ordering;
 
-pub fn assemble_sorters(flags: &Flags) -> Vec<(SortOrder, SortFn)> {
-    let mut sorters: Vec<(SortOrder, SortFn)> = vec![];
+/// A comparator of the sort chain, which may capture the group it applies to.
+pub type Comparator = Box<dyn Fn(&Meta, &Meta) -> Ordering>;
+
+/// A comparator of the sort chain, with the order it is applied in.
+pub type Sorter = (SortOrder, Comparator);
+
+pub fn assemble_sorters(flags: &Flags) -> Vec<Sorter> {
+    let mut sorters: Vec<Sorter> = vec![];
+    // The prioritized entries come first whatever the other keys and the order.
+    if !flags.sort_priority.is_empty() {
+        let priority = flags.sort_priority.clone();
+        sorters.push((
+            SortOrder::Default,
+            Box::new(move |a, b| by_priority(&priority, a, b)),
+        ));
+    }
     match flags.sorting.dir_grouping {
         DirGrouping::First => {
-            sorters.push((SortOrder::Default, with_dirs_first));
+            sorters.push((SortOrder::Default, Box::new(with_dirs_first)));
         }
         DirGrouping::Last => {
-            sorters.push((SortOrder::Reverse, with_dirs_first));
+            sorters.push((SortOrder::Reverse, Box::new(with_dirs_first)));
         }
         DirGrouping::None => {}
     };
-    let other_sort = match flags.sorting.column {
+    match flags.sorting.dotfiles {
+        DotFiles::First => sorters.push((SortOrder::Default, Box::new(with_dotfiles_first))),
+        DotFiles::Last => sorters.push((SortOrder::Reverse, Box::new(with_dotfiles_first))),
+        DotFiles::Mixed => {}
+    };
+
+    // With the directories grouped, they may be sorted by their own column, the other keys then
+    // only sorting the files.
+    let dir_column = match flags.sorting.dir_column.0 {
+        Some(column) if flags.sorting.dir_grouping != DirGrouping::None => Some(column),
+        _ => None,
+    };
+    let scoped = |sorter: SortFn| -> Comparator {
+        match dir_column {
+            Some(_) => Box::new(within_group(sorter, false)),
+            None => Box::new(sorter),
+        }
+    };
+    if let Some(column) = dir_column {
+        sorters.push((
+            flags.sorting.order,
+            Box::new(within_group(sort_fn(column, flags), true)),
+        ));
+    }
+
+    if flags.sorting.keys.0.is_empty() {
+        sorters.push((
+            flags.sorting.order,
+            scoped(sort_fn(flags.sorting.column, flags)),
+        ));
+    }
+    for (column, order) in &flags.sorting.keys.0 {
+        // Reversing the sort reverses every key of the chain.
+        let order = match (flags.sorting.order, order) {
+            (SortOrder::Default, order) => *order,
+            (SortOrder::Reverse, SortOrder::Default) => SortOrder::Reverse,
+            (SortOrder::Reverse, SortOrder::Reverse) => SortOrder::Default,
+        };
+        sorters.push((order, scoped(sort_fn(*column, flags))));
+    }
+    sorters
+}
+
+/// Restrict a comparator to the pairs of directories, or to the pairs of other entries, the
+/// entries of the other group being left equal.
+fn within_group(sorter: SortFn, dirs: bool) -> impl Fn(&Meta, &Meta) -> Ordering {
+    move |a, b| {
+        if a.file_type.is_dirlike() == dirs && b.file_type.is_dirlike() == dirs {
+            sorter(a, b)
+        } else {
+            Ordering::Equal
+        }
+    }
+}
+
+fn sort_fn(column: SortColumn, flags: &Flags) -> SortFn {
+    match column {
+        #[cfg(feature = "natural-locale")]
+        SortColumn::Name if flags.sorting.natural_locale.0 => by_name_locale,
         SortColumn::Name => by_name,
         SortColumn::Size => by_size,
         SortColumn::Time => by_date,
         SortColumn::Version => by_version,
-        SortColumn::Extension => by_extension,
-    };
-    sorters.push((flags.sorting.order, other_sort));
-    sorters
+        SortColumn::Extension => match (
+            flags.compound_extensions.0,
+            flags.sorting.case_sensitive_extensions.0,
+        ) {
+            (true, true) => by_compound_extension_case_sensitive,
+            (true, false) => by_compound_extension,
+            (false, true) => by_extension_case_sensitive,
+            (false, false) => by_extension,
+        },
+        SortColumn::Owner => by_owner,
+        SortColumn::Group => by_group,
+        SortColumn::Inode => {
+            // The inodes are not read on Windows, the entries are then sorted by name.
+            #[cfg(windows)]
+            print_error!("Sorting by inode is not supported on Windows, sorting by name instead.");
+            by_inode
+        }
+        // The entries are shuffled before sorting, which keeps their order for this column.
+        SortColumn::Random => by_random,
+        SortColumn::Recent => by_recent,
+        SortColumn::None => by_none,
+    }
 }
 
-pub fn by_meta(sorters: &[(SortOrder, SortFn)], a: &Meta, b: &Meta) -> Ordering {
+/// Whether two consecutive entries belong to different groups, according to the sort keys
+/// which gather entries together: the directory grouping, the dotfiles placement and the
+/// extension column.
+pub fn is_group_boundary(flags: &Flags, a: &Meta, b: &Meta) -> bool {
+    if flags.sorting.dir_grouping != DirGrouping::None && with_dirs_first(a, b) != Ordering::Equal {
+        return true;
+    }
+    if flags.sorting.dotfiles != DotFiles::Mixed && with_dotfiles_first(a, b) != Ordering::Equal {
+        return true;
+    }
+    flags.sorting.column == SortColumn::Extension
+        && sort_fn(SortColumn::Extension, flags)(a, b) != Ordering::Equal
+}
+
+pub fn by_meta(sorters: &[Sorter], a: &Meta, b: &Meta) -> Ordering {
     for (direction, sorter) in sorters.iter() {
         match (sorter)(a, b) {
             Ordering::Equal => continue,
@@ -42,34 +154,169 @@ pub fn by_meta(sorters: &[(SortOrder, SortFn)], a: &Meta, b: &Meta) -> Ordering
     Ordering::Equal
 }
 
+/// The entries matching a pattern of the sort priority come first, in the order of the patterns.
+fn by_priority(priority: &SortPriority, a: &Meta, b: &Meta) -> Ordering {
+    match (priority.rank(&a.name.name), priority.rank(&b.name.name)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
 fn with_dirs_first(a: &Meta, b: &Meta) -> Ordering {
     b.file_type.is_dirlike().cmp(&a.file_type.is_dirlike())
 }
 
+fn with_dotfiles_first(a: &Meta, b: &Meta) -> Ordering {
+    let is_dotfile = |meta: &Meta| meta.name.name.starts_with('.');
+    is_dotfile(b).cmp(&is_dotfile(a))
+}
+
+/// With `--total-size`, the sizes of the directories are accumulated before sorting, so the
+/// directories are sorted by the size of their content rather than by their own size.
 fn by_size(a: &Meta, b: &Meta) -> Ordering {
-    b.size.get_bytes().cmp(&a.size.get_bytes())
+    b.size
+        .get_bytes()
+        .cmp(&a.size.get_bytes())
+        .then(a.name.cmp(&b.name))
+}
+
+fn by_random(_: &Meta, _: &Meta) -> Ordering {
+    Ordering::Equal
+}
+
+fn by_none(_: &Meta, _: &Meta) -> Ordering {
+    Ordering::Equal
 }
 
 fn by_name(a: &Meta, b: &Meta) -> Ordering {
     a.name.cmp(&b.name)
 }
 
+#[cfg(feature = "natural-locale")]
+thread_local! {
+    // The collator keeps internal buffers around, so reuse it across comparisons.
+    static COLLATOR: RefCell<Collator> = RefCell::new(Collator::default());
+}
+
+#[cfg(feature = "natural-locale")]
+fn by_name_locale(a: &Meta, b: &Meta) -> Ordering {
+    COLLATOR.with(|collator| collator.borrow_mut().collate(&a.name.name, &b.name.name))
+}
+
 fn by_date(a: &Meta, b: &Meta) -> Ordering {
     b.date.cmp(&a.date).then(a.name.cmp(&b.name))
 }
 
+/// Compare the latest of the times accessed, modified and changed, whichever changed last.
+fn by_recent(a: &Meta, b: &Meta) -> Ordering {
+    let recent = |meta: &Meta| {
+        meta.date
+            .clone()
+            .max(meta.accessed.clone())
+            .max(meta.changed.clone())
+    };
+    recent(b).cmp(&recent(a)).then(a.name.cmp(&b.name))
+}
+
 fn by_version(a: &Meta, b: &Meta) -> Ordering {
-    compare(&a.name.name, &b.name.name)
+    compare_natural(&a.name.name, &b.name.name)
+}
+
+/// Compare two strings with their runs of digits compared by numeric value, whatever their
+/// leading zeros. The strings differing only by the leading zeros are compared as they are, so
+/// that the order stays deterministic.
+fn compare_natural(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek(), b_chars.peek()) {
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let ordering =
+                    compare_digits(&take_digits(&mut a_chars), &take_digits(&mut b_chars));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(y);
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (None, None) => return a.cmp(b),
+        }
+    }
+}
+
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(digit) = chars.peek().filter(|c| c.is_ascii_digit()) {
+        digits.push(*digit);
+        chars.next();
+    }
+    digits
+}
+
+/// Compare two runs of digits by value, without any limit on their length.
+fn compare_digits(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// Compare the extensions regardless of their case, so that `.JPG` and `.jpg` are grouped.
+fn cmp_extensions(a: Option<&str>, b: Option<&str>) -> Ordering {
+    a.map(str::to_lowercase).cmp(&b.map(str::to_lowercase))
 }
 
 fn by_extension(a: &Meta, b: &Meta) -> Ordering {
+    cmp_extensions(a.name.extension(), b.name.extension())
+}
+
+fn by_extension_case_sensitive(a: &Meta, b: &Meta) -> Ordering {
     a.name.extension().cmp(&b.name.extension())
 }
 
+fn by_compound_extension(a: &Meta, b: &Meta) -> Ordering {
+    cmp_extensions(a.name.compound_extension(), b.name.compound_extension())
+}
+
+fn by_compound_extension_case_sensitive(a: &Meta, b: &Meta) -> Ordering {
+    a.name
+        .compound_extension()
+        .cmp(&b.name.compound_extension())
+}
+
+fn by_owner(a: &Meta, b: &Meta) -> Ordering {
+    a.owner.user().cmp(b.owner.user()).then(by_group(a, b))
+}
+
+fn by_group(a: &Meta, b: &Meta) -> Ordering {
+    a.owner
+        .group()
+        .cmp(b.owner.group())
+        .then(a.name.cmp(&b.name))
+}
+
+fn by_inode(a: &Meta, b: &Meta) -> Ordering {
+    a.inode
+        .index()
+        .cmp(&b.inode.index())
+        .then(a.name.cmp(&b.name))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::flags::Flags;
+    use crate::config_file::Config;
+    use crate::flags::sorting::DirColumn;
+    use crate::flags::{Configurable, Flags};
     use std::fs::{create_dir, File};
     use std::process::Command;
     use tempfile::tempdir;
@@ -102,6 +349,39 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
     }
 
+    #[test]
+    fn test_sort_assemble_sorters_with_dotfiles() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        // The dotfiles sort before the other names anyway, so sort by size
+        let path_a = tmp_dir.path().join("Makefile");
+        std::fs::write(&path_a, "all:").expect("failed to create file");
+        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+
+        let path_z = tmp_dir.path().join(".zshrc");
+        File::create(&path_z).expect("failed to create file");
+        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Size;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
+
+        flags.sorting.dotfiles = DotFiles::First;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
+
+        // The dotfiles stay first when reversing the sort
+        flags.sorting.order = SortOrder::Reverse;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
+
+        flags.sorting.dotfiles = DotFiles::Last;
+        flags.sorting.order = SortOrder::Default;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
+    }
+
     #[test]
     fn test_sort_assemble_sorters_by_name_with_files_first() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -232,6 +512,161 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_sort_assemble_sorters_by_recent() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        // The file modified long ago, but accessed the latest.
+        let path_z = tmp_dir.path().join("zzz");
+        File::create(&path_z).expect("failed to create file");
+        for (time, arg) in &[("-m", "198511160000"), ("-a", "203511160000")] {
+            let success = Command::new("touch")
+                .arg(time)
+                .arg("-t")
+                .arg(arg)
+                .arg(&path_z)
+                .status()
+                .unwrap()
+                .success();
+            assert!(success, "failed to change file timestamp");
+        }
+        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+
+        // The file created after, so modified and changed the latest.
+        let path_a = tmp_dir.path().join("aaa");
+        File::create(&path_a).expect("failed to create file");
+        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Time;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_z, &meta_a), Ordering::Greater);
+
+        flags.sorting.column = SortColumn::Recent;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_z, &meta_a), Ordering::Less);
+
+        flags.sorting.order = SortOrder::Reverse;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_z, &meta_a), Ordering::Greater);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_sort_assemble_sorters_with_dir_column() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        // The first directory and the first file by name are the oldest.
+        for (name, is_dir) in &[
+            ("a.d", true),
+            ("b.d", true),
+            ("a_old", false),
+            ("z_new", false),
+        ] {
+            let path = tmp_dir.path().join(name);
+            if *is_dir {
+                create_dir(&path).expect("failed to create dir");
+            } else {
+                File::create(&path).expect("failed to create file");
+            }
+        }
+        for (name, time) in &[
+            ("a.d", "198511160000"),
+            ("a_old", "198611160000"),
+            ("b.d", "200011160000"),
+            ("z_new", "200111160000"),
+        ] {
+            let success = Command::new("touch")
+                .arg("-t")
+                .arg(time)
+                .arg(tmp_dir.path().join(name))
+                .status()
+                .unwrap()
+                .success();
+            assert!(success, "failed to change file timestamp");
+        }
+        let mut metas: Vec<Meta> = ["z_new", "b.d", "a_old", "a.d"]
+            .iter()
+            .map(|name| Meta::from_path(&tmp_dir.path().join(name), false).unwrap())
+            .collect();
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Time;
+        flags.sorting.dir_grouping = DirGrouping::First;
+        flags.sorting.dir_column = DirColumn(Some(SortColumn::Name));
+
+        let sorters = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorters, a, b));
+        let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["a.d", "b.d", "z_new", "a_old"], names);
+
+        // Without grouping, the directories are sorted like the files.
+        flags.sorting.dir_grouping = DirGrouping::None;
+        let sorters = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorters, a, b));
+        let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["z_new", "b.d", "a_old", "a.d"], names);
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_with_priority() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        for name in &["Cargo.toml", "README.md", "build.rs", "src", "LICENSE"] {
+            File::create(tmp_dir.path().join(name)).expect("failed to create file");
+        }
+        let mut metas: Vec<Meta> = ["src", "README.md", "LICENSE", "build.rs", "Cargo.toml"]
+            .iter()
+            .map(|name| Meta::from_path(&tmp_dir.path().join(name), false).unwrap())
+            .collect();
+
+        let config: Config =
+            serde_yaml::from_str("sort-priority:\n  - README*\n  - src\n  - Cargo.*").unwrap();
+        let mut flags = Flags {
+            sort_priority: SortPriority::from_config(&config).unwrap(),
+            ..Flags::default()
+        };
+        flags.sorting.order = SortOrder::Reverse;
+
+        let sorters = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorters, a, b));
+        let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(
+            vec!["README.md", "src", "Cargo.toml", "LICENSE", "build.rs"],
+            names
+        );
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_by_size_with_equal_sizes() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas: Vec<Meta> = ["ccc", "aaa", "zzz", "bbb"]
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                let content = if *name == "zzz" { "bigger" } else { "same" };
+                std::fs::write(&path, content).expect("failed to write file");
+                Meta::from_path(&path, false).expect("failed to get meta")
+            })
+            .collect();
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Size;
+
+        // The files of the same size are sorted by name, whatever the order they are read in
+        let sorter = assemble_sorters(&flags);
+        metas.sort_unstable_by(|a, b| by_meta(&sorter, a, b));
+        let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["zzz", "aaa", "bbb", "ccc"], names);
+
+        flags.sorting.order = SortOrder::Reverse;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_unstable_by(|a, b| by_meta(&sorter, a, b));
+        let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["ccc", "bbb", "aaa", "zzz"], names);
+    }
+
     #[test]
     fn test_sort_assemble_sorters_by_extension() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -295,4 +730,147 @@ mod tests {
         let sorter = assemble_sorters(&flags);
         assert_eq!(by_meta(&sorter, &meta_b, &meta_c), Ordering::Less);
     }
+
+    #[test]
+    fn test_compare_natural_zero_padded() {
+        let mut names = vec![
+            "img100", "img010", "img1a", "img2", "img001", "img01a", "img011",
+        ];
+        names.sort_by(|a, b| compare_natural(a, b));
+        assert_eq!(
+            names,
+            vec!["img001", "img01a", "img1a", "img2", "img010", "img011", "img100"]
+        );
+
+        assert_eq!(
+            compare_natural("v99999999999999999999", "v100000000000000000000"),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_sort_assemble_sorters_by_inode() {
+        use std::os::unix::fs::MetadataExt;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        for name in &["c", "a", "e", "d"] {
+            File::create(tmp_dir.path().join(name)).expect("failed to create file");
+        }
+        // A hard link shares the inode of its target, so the name breaks the tie.
+        std::fs::hard_link(tmp_dir.path().join("d"), tmp_dir.path().join("b"))
+            .expect("failed to create hard link");
+
+        let mut metas: Vec<Meta> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| {
+                Meta::from_path(&tmp_dir.path().join(name), false).expect("failed to get meta")
+            })
+            .collect();
+        let mut expected: Vec<(u64, String)> = metas
+            .iter()
+            .map(|m| (m.path.metadata().unwrap().ino(), m.name.name.clone()))
+            .collect();
+        expected.sort();
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Inode;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        let names: Vec<String> = metas.iter().map(|m| m.name.name.clone()).collect();
+        assert_eq!(
+            names,
+            expected
+                .into_iter()
+                .map(|(_, name)| name)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_sort_assemble_sorters_by_owner_and_group() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        // (name, uid, gid), the users and groups 0, 1 and 2 are root, daemon and bin.
+        let files = [
+            ("a", 1, 0),
+            ("b", 0, 2),
+            ("c", 0, 1),
+            ("d", 2, 0),
+            ("e", 0, 1),
+        ];
+        for (name, uid, gid) in &files {
+            let path = tmp_dir.path().join(name);
+            File::create(&path).expect("failed to create file");
+            // Changing the owner requires to be root, skip the test otherwise.
+            if std::os::unix::fs::chown(&path, Some(*uid), Some(*gid)).is_err() {
+                return;
+            }
+        }
+        let mut metas: Vec<Meta> = files
+            .iter()
+            .map(|(name, _, _)| {
+                Meta::from_path(&tmp_dir.path().join(name), false).expect("failed to get meta")
+            })
+            .collect();
+        let names =
+            |metas: &[Meta]| -> Vec<String> { metas.iter().map(|m| m.name.name.clone()).collect() };
+        // The names of the users and groups may differ on some systems, skip the test then.
+        let owners: Vec<(&str, &str)> = metas
+            .iter()
+            .map(|m| (m.owner.user(), m.owner.group()))
+            .collect();
+        let expected = vec![
+            ("daemon", "root"),
+            ("root", "bin"),
+            ("root", "daemon"),
+            ("bin", "root"),
+            ("root", "daemon"),
+        ];
+        if owners != expected {
+            return;
+        }
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Owner;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["d", "a", "b", "c", "e"]);
+
+        flags.sorting.column = SortColumn::Group;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["b", "c", "e", "a", "d"]);
+    }
+
+    #[test]
+    #[cfg(feature = "natural-locale")]
+    fn test_sort_assemble_sorters_by_name_locale() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas: Vec<Meta> = ["banana", "ápex", "apple"]
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                Meta::from_path(&path, false).expect("failed to get meta")
+            })
+            .collect();
+
+        let mut flags = Flags::default();
+
+        // The default sort puts accented letters after the plain ones.
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        let names: Vec<&str> = metas.iter().map(|m| m.name.name.as_str()).collect();
+        assert_eq!(names, vec!["apple", "banana", "ápex"]);
+
+        // The locale sort puts accented letters near their base letters.
+        flags.sorting.natural_locale = crate::flags::sorting::NaturalLocale(true);
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        let names: Vec<&str> = metas.iter().map(|m| m.name.name.as_str()).collect();
+        assert_eq!(names, vec!["ápex", "apple", "banana"]);
+    }
 }
@@ -0,0 +1,107 @@
+//! This module provides the dimensions of the terminal the output is written to.
+
+use std::path::PathBuf;
+use terminal_size::{Height, Width};
+
+/// The dimensions of a terminal, in columns and rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dimensions {
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Query the dimensions of the terminal stdout is written to, or [None] if it is not a terminal.
+///
+/// They are not cached: every listing queries them again, so that a resized terminal is taken
+/// into account, eg: when lsd is run in a `watch` loop.
+pub fn size() -> Option<Dimensions> {
+    terminal_size::terminal_size().map(|(Width(width), Height(height))| Dimensions {
+        width: width as usize,
+        height: height as usize,
+    })
+}
+
+/// The dimensions assumed when the output is laid out as on a terminal while it is not one, eg:
+/// with `--tty=force` when piped: `$COLUMNS` and `$LINES`, or 80 columns and 24 rows.
+pub fn fallback_size<F>(env: F) -> Dimensions
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let read = |name, default| {
+        env(name)
+            .and_then(|value| value.trim().parse().ok())
+            .filter(|&value| value > 0)
+            .unwrap_or(default)
+    };
+
+    Dimensions {
+        width: read("COLUMNS", 80),
+        height: read("LINES", 24),
+    }
+}
+
+/// The escape sequence setting the title of the terminal to the listed paths, separated by
+/// commas. The paths are made absolute when they can be, and their control characters, which
+/// would end the sequence early, are dropped.
+pub fn title_sequence(paths: &[PathBuf]) -> String {
+    let title = paths
+        .iter()
+        .map(|path| {
+            path.canonicalize()
+                .unwrap_or_else(|_| path.clone())
+                .to_string_lossy()
+                .chars()
+                .filter(|c| !c.is_control())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("\x1B]2;{}\x07", title)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fallback_size, title_sequence, Dimensions};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_fallback_size_default() {
+        assert_eq!(
+            Dimensions {
+                width: 80,
+                height: 24
+            },
+            fallback_size(|_| None)
+        );
+    }
+
+    #[test]
+    fn test_fallback_size_from_env() {
+        let size = fallback_size(|name| match name {
+            "COLUMNS" => Some(String::from("120")),
+            "LINES" => Some(String::from("not a number")),
+            _ => None,
+        });
+
+        assert_eq!(
+            Dimensions {
+                width: 120,
+                height: 24
+            },
+            size
+        );
+    }
+
+    #[test]
+    fn test_title_sequence() {
+        let paths = vec![
+            PathBuf::from("/not/a/dir"),
+            PathBuf::from("/not/a/\x1B]0;file\x07"),
+        ];
+        assert_eq!(
+            "\x1B]2;/not/a/dir, /not/a/]0;file\x07",
+            title_sequence(&paths)
+        );
+    }
+}
@@ -23,6 +23,74 @@ fn test_list_empty_directory() {
         .stdout(predicate::eq(""));
 }
 
+#[test]
+fn test_recursive_deep_tree() {
+    let dir = tempdir();
+    let mut path = std::path::PathBuf::new();
+    let mut expected = String::from("d1\nfile0\n");
+    dir.child("file0").touch().unwrap();
+    for level in 1..=20 {
+        path.push(format!("d{}", level));
+        dir.child(&path).create_dir_all().unwrap();
+        dir.child(path.join(format!("file{}", level)))
+            .touch()
+            .unwrap();
+
+        expected += &format!("\n{}:\n", dir.path().join(&path).display());
+        if level < 20 {
+            expected += &format!("d{}\n", level + 1);
+        }
+        expected += &format!("file{}\n", level);
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--recursive")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(expected.as_str()));
+
+    // the subdirectories are listed, but not walked into, beyond the depth
+    cmd()
+        .arg("--ignore-config")
+        .arg("--recursive")
+        .arg("--depth")
+        .arg("2")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            format!(
+                "d1\nfile0\n\n{}:\nd2\nfile1\n",
+                dir.path().join("d1").display()
+            )
+            .as_str(),
+        ));
+}
+
+#[test]
+fn test_list_all_dot_entries_are_directories() {
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .arg("--all")
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("permission,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match("^d\\S+ \\.\nd\\S+ \\.\\.\n\\.\\S+ file\n$").unwrap());
+
+    cmd()
+        .arg("--almost-all")
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("permission,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match("^\\.\\S+ file\n$").unwrap());
+}
+
 #[test]
 fn test_list_almost_all_empty_directory() {
     let matched = "";
@@ -112,6 +180,33 @@ fn test_almost_sort_with_folder() {
         .stdout(predicate::str::is_match("\\.\n\\.\\.\na\n$").unwrap());
 }
 
+#[cfg(windows)]
+#[test]
+fn test_list_attribute_hidden_file() {
+    let dir = tempdir();
+    dir.child("hidden").touch().unwrap();
+    dir.child("visible").touch().unwrap();
+    let status = Command::new("attrib")
+        .arg("+h")
+        .arg(dir.path().join("hidden"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("visible\n"));
+
+    cmd()
+        .arg("--almost-all")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("hidden\nvisible\n"));
+}
+
 #[test]
 fn test_list_inode_populated_directory() {
     let dir = tempdir();
@@ -178,6 +273,31 @@ fn test_list_block_inode_populated_directory_with_long() {
         .stdout(predicate::str::is_match(matched).unwrap());
 }
 
+#[test]
+fn test_list_block_children() {
+    let dir = tempdir();
+    for name in &["one", "two", "three", "four", "five", ".hidden"] {
+        dir.child("d").child(name).touch().unwrap();
+    }
+
+    cmd()
+        .arg("--blocks")
+        .arg("children,name")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match("^5 +.*d\n$").unwrap());
+
+    cmd()
+        .arg("--blocks")
+        .arg("children,name")
+        .arg("--ignore-config")
+        .arg("--almost-all")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match("^6 +.*d\n$").unwrap());
+}
+
 #[test]
 fn test_list_inode_with_long_ok() {
     let dir = tempdir();
@@ -263,6 +383,211 @@ fn test_dereference_link_right_type_and_no_link() {
         .stdout(predicate::str::contains(link_icon).not());
 }
 
+#[cfg(unix)]
+#[test]
+fn test_wide_symlink_arrow_keeps_columns_aligned() {
+    let dir = tempdir();
+    dir.child("config.yaml")
+        .write_str("symlink-arrow: \"⟶ 矢印 ⟶\"\n")
+        .unwrap();
+    dir.child("folder").create_dir_all().unwrap();
+    dir.child("folder/target").touch().unwrap();
+    fs::symlink("target", dir.path().join("folder/link")).unwrap();
+
+    // the arrow is 8 columns wide while it is 14 bytes long
+    cmd()
+        .arg("--config-file")
+        .arg(dir.path().join("config.yaml"))
+        .arg("-l")
+        .arg("--blocks")
+        .arg("name,links")
+        .arg(dir.child("folder").path())
+        .assert()
+        .stdout(predicate::eq(
+            "link ⟶ 矢印 ⟶ target 1\ntarget               1\n",
+        ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dereference_lists_symlinked_directory() {
+    let dir = tempdir();
+    dir.child("target").create_dir_all().unwrap();
+    dir.child("target/inside").touch().unwrap();
+    let link = dir.path().join("link");
+    fs::symlink("target", &link).unwrap();
+
+    cmd()
+        .arg("--oneline")
+        .arg("--ignore-config")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::contains("⇒ target"));
+
+    cmd()
+        .arg("--oneline")
+        .arg("--dereference")
+        .arg("--ignore-config")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::eq("inside\n"));
+
+    cmd()
+        .arg("-l")
+        .arg("--dereference")
+        .arg("--ignore-config")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::starts_with(".rw"))
+        .stdout(predicate::str::ends_with(" inside\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_no_symlink_hides_target() {
+    let dir = tempdir();
+    dir.child("target").touch().unwrap();
+    let link = dir.path().join("link");
+    fs::symlink("target", &link).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::contains("⇒ target"));
+
+    cmd()
+        .arg("-l")
+        .arg("--no-symlink")
+        .arg("--ignore-config")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::starts_with("lrwx"))
+        .stdout(predicate::str::ends_with("link\n"));
+
+    // the metadata is the one of the target, but the target is still not displayed
+    cmd()
+        .arg("-l")
+        .arg("--no-symlink")
+        .arg("--dereference")
+        .arg("--ignore-config")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::starts_with(".rw"))
+        .stdout(predicate::str::ends_with("link\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_display_full() {
+    let dir = tempdir();
+    dir.child("target").touch().unwrap();
+    let link = dir.path().join("link");
+    fs::symlink("target", &link).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--symlink-display")
+        .arg("full")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::starts_with("lrw"))
+        .stdout(predicate::str::ends_with("link ⇒ target\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_display_target() {
+    let dir = tempdir();
+    dir.child("target").touch().unwrap();
+    let link = dir.path().join("link");
+    fs::symlink("target", &link).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--symlink-display")
+        .arg("target")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::starts_with("lrw"))
+        .stdout(predicate::str::ends_with(" target\n"))
+        .stdout(predicate::str::contains("link").not())
+        .stdout(predicate::str::contains("⇒").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_target_absolute() {
+    let dir = tempdir();
+    dir.child("target").touch().unwrap();
+    dir.child("folder").create_dir_all().unwrap();
+    let link = dir.path().join("folder/link");
+    fs::symlink("../target", &link).unwrap();
+    let target = dir.path().canonicalize().unwrap().join("target");
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--symlink-target")
+        .arg("absolute")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::ends_with(format!(
+            "link ⇒ {}\n",
+            target.display()
+        )));
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::ends_with("link ⇒ ../target\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_display_name() {
+    let dir = tempdir();
+    dir.child("target").write_str(&"a".repeat(100)).unwrap();
+    let link = dir.path().join("link");
+    fs::symlink("target", &link).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--symlink-display")
+        .arg("name")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::starts_with("lrw"))
+        .stdout(predicate::str::contains(" 100 B "))
+        .stdout(predicate::str::ends_with("link\n"))
+        .stdout(predicate::str::contains("⇒").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_display_name_does_not_follow_links() {
+    let dir = tempdir();
+    dir.child("target/inside").touch().unwrap();
+    fs::symlink("target", dir.path().join("link")).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--recursive")
+        .arg("--symlink-display")
+        .arg("name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("target:\ninside\n"))
+        .stdout(predicate::str::contains("link:").not());
+}
+
 #[cfg(unix)]
 #[test]
 fn test_show_folder_content_of_symlink() {
@@ -480,91 +805,2029 @@ fn test_tree_d() {
         .stdout(predicate::str::is_match("├── one.d\n│   └── one.d\n└── two.d\n$").unwrap());
 }
 
-fn cmd() -> Command {
-    Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap()
-}
+#[test]
+fn test_depth_zero() {
+    let tmp = tempdir();
+    tmp.child("one.d/one").touch().unwrap();
 
-fn tempdir() -> assert_fs::TempDir {
-    assert_fs::TempDir::new().unwrap()
+    for layout in &["--oneline", "--tree"] {
+        cmd()
+            .arg("--ignore-config")
+            .arg(layout)
+            .arg("--depth")
+            .arg("0")
+            .arg(tmp.path().join("one.d"))
+            .assert()
+            .stdout(predicate::str::is_match("one.d\n$").unwrap())
+            .stdout(predicate::str::contains("one\n").not());
+    }
 }
 
-#[cfg(unix)]
 #[test]
-fn test_lower_case_name_icon_match() {
-    let dir = tempdir();
-    dir.child(".trash").touch().unwrap();
-    let test_file = dir.path().join(".trash");
+fn test_no_hidden_recurse() {
+    let tmp = tempdir();
+    tmp.child(".git/objects").create_dir_all().unwrap();
+    tmp.child(".gitignore").touch().unwrap();
+    tmp.child("src/main.rs").touch().unwrap();
 
     cmd()
-        .arg("--icon")
-        .arg("always")
         .arg("--ignore-config")
-        .arg(test_file)
+        .arg("--tree")
+        .arg("--all")
+        .arg("--no-hidden-recurse")
+        .arg(tmp.path())
         .assert()
-        .stdout(predicate::str::contains("\u{f1f8}"));
+        .stdout(predicate::str::contains("├── .git\n"))
+        .stdout(predicate::str::contains("├── .gitignore\n"))
+        .stdout(predicate::str::contains("objects").not())
+        .stdout(predicate::str::contains("    └── main.rs\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--recursive")
+        .arg("--all")
+        .arg("--no-hidden-recurse")
+        .arg(tmp.path())
+        .assert()
+        .stdout(predicate::str::contains(".git"))
+        .stdout(predicate::str::contains("objects").not())
+        .stdout(predicate::str::contains("main.rs"));
 }
 
 #[cfg(unix)]
 #[test]
-fn test_upper_case_name_icon_match() {
+fn test_one_file_system() {
     let dir = tempdir();
-    dir.child(".TRASH").touch().unwrap();
-    let test_file = dir.path().join(".TRASH");
+    dir.child("sub/nested/file").touch().unwrap();
 
+    // The directories on the same file system are still recursed into.
     cmd()
-        .arg("--icon")
-        .arg("always")
         .arg("--ignore-config")
-        .arg(test_file)
+        .arg("--oneline")
+        .arg("--recursive")
+        .arg("--one-file-system")
+        .arg(dir.path())
         .assert()
-        .stdout(predicate::str::contains("\u{f1f8}"));
+        .stdout(predicate::str::contains("nested:\nfile\n"));
 }
 
 #[cfg(unix)]
 #[test]
-fn test_lower_case_ext_icon_match() {
+fn test_dereference_symlink_loop() {
     let dir = tempdir();
-    dir.child("test.7z").touch().unwrap();
-    let test_file = dir.path().join("test.7z");
+    dir.child("file").touch().unwrap();
+    fs::symlink("b", dir.path().join("a")).unwrap();
+    fs::symlink("a", dir.path().join("b")).unwrap();
 
     cmd()
-        .arg("--icon")
-        .arg("always")
         .arg("--ignore-config")
-        .arg(test_file)
+        .arg("--dereference")
+        .arg(dir.path())
         .assert()
-        .stdout(predicate::str::contains("\u{f410}"));
+        .stdout(predicate::str::contains("file"))
+        .stderr(predicate::str::contains(
+            "a: Too many levels of symbolic links",
+        ));
 }
 
-#[cfg(unix)]
 #[test]
-fn test_upper_case_ext_icon_match() {
+fn test_sort_size_with_total_size() {
     let dir = tempdir();
-    dir.child("test.7Z").touch().unwrap();
-    let test_file = dir.path().join("test.7Z");
+    dir.child("small/file").write_str("small").unwrap();
+    dir.child("big/file")
+        .write_str(&"big".repeat(100_000))
+        .unwrap();
 
     cmd()
-        .arg("--icon")
-        .arg("always")
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--total-size")
+        .arg("--sort")
+        .arg("size")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("big\nsmall\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--total-size")
+        .arg("--sort")
+        .arg("size")
+        .arg("--reverse")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("small\nbig\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_total_size_counts_hard_links_once() {
+    let dir = tempdir();
+    dir.child("copies/a")
+        .write_str(&"a".repeat(200_000))
+        .unwrap();
+    dir.child("copies/b")
+        .write_str(&"b".repeat(200_000))
+        .unwrap();
+    dir.child("links/file")
+        .write_str(&"l".repeat(300_000))
+        .unwrap();
+    std::fs::hard_link(dir.path().join("links/file"), dir.path().join("links/link")).unwrap();
+
+    // The content of the directories is either read to list it or only summed up.
+    for recursive in &[false, true] {
+        let mut command = cmd();
+        command
+            .arg("--ignore-config")
+            .arg("--oneline")
+            .arg("--total-size")
+            .arg("--sort")
+            .arg("size");
+        if *recursive {
+            command.arg("--recursive");
+        }
+        command
+            .arg(dir.path())
+            .assert()
+            .stdout(predicate::str::starts_with("copies\nlinks\n"));
+    }
+}
+
+#[test]
+fn test_group_directories_last() {
+    let dir = tempdir();
+    dir.child("a").create_dir_all().unwrap();
+    dir.child("d").create_dir_all().unwrap();
+    dir.child("b").touch().unwrap();
+    dir.child("c").touch().unwrap();
+    dir.child("config.yaml")
+        .write_str("sorting:\n  dir-grouping: first\n")
+        .unwrap();
+
+    cmd()
+        .arg("--config-file")
+        .arg(dir.path().join("config.yaml"))
+        .arg("--group-directories-last")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("b\nc\nconfig.yaml\na\nd\n"));
+
+    cmd()
+        .arg("--config-file")
+        .arg(dir.path().join("config.yaml"))
+        .arg("--group-directories-last")
+        .arg("--reverse")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("config.yaml\nc\nb\nd\na\n"));
+}
+
+#[test]
+fn test_sort_multiple_keys() {
+    let dir = tempdir();
+    dir.child("b").write_str(&"b".repeat(10)).unwrap();
+    dir.child("a").write_str(&"a".repeat(10)).unwrap();
+    dir.child("c").write_str(&"c".repeat(100)).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--sort")
+        .arg("-size,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("c\na\nb\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--sort")
+        .arg("+size,-name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("b\na\nc\n"));
+}
+
+#[test]
+fn test_sort_invalid_key() {
+    cmd()
+        .arg("--sort")
+        .arg("size,colour")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid sort key 'colour'"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_ipc_status_fifo() {
+    let dir = tempdir();
+    let fifo = dir.path().join("pipe");
+    let success = std::process::Command::new("mkfifo")
+        .arg(&fifo)
+        .status()
+        .expect("failed to exec mkfifo")
+        .success();
+    assert!(success, "failed to exec mkfifo");
+
+    // Without LS_COLORS, the name is colored with the pipe color (Gold3) and followed by the pipe
+    // indicator. The FIFO is never opened, so its status is unknown (muted nord3).
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .arg("--color")
+        .arg("always")
+        .arg("--color-theme")
+        .arg("nord")
+        .arg("--icon")
+        .arg("never")
+        .arg("--classify")
+        .arg("--ipc-status")
+        .arg("pipe")
+        .assert()
+        .stdout(predicate::str::contains(
+            "\u{1b}[38;5;178mpipe\u{1b}[0m|\u{1b}[38;2;76;86;106m [?]",
+        ));
+}
+
+#[test]
+fn test_classify_auto_when_piped() {
+    let dir = tempdir();
+    dir.child("folder").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--classify=auto")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("folder\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--classify")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("folder/\n"));
+}
+
+#[test]
+fn test_since_until() {
+    let dir = tempdir();
+    let now = std::time::SystemTime::now();
+    let hour = std::time::Duration::from_secs(3600);
+    for (name, age) in &[("old", 48 * hour), ("recent", 3 * hour), ("new", hour / 2)] {
+        dir.child(name).touch().unwrap();
+        filetime::set_file_mtime(
+            dir.child(name).path(),
+            filetime::FileTime::from_system_time(now - *age),
+        )
+        .unwrap();
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--since")
+        .arg("2 hours ago")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("new\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--until")
+        .arg("2h")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("old\nrecent\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--since")
+        .arg("1day")
+        .arg("--until")
+        .arg("1 hour ago")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("recent\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--since")
+        .arg("yesterday-ish")
+        .arg(dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid time 'yesterday-ish'"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_list_block_link_target_info() {
+    let dir = tempdir();
+    dir.child("target").write_str(&"a".repeat(100)).unwrap();
+    fs::symlink("target", dir.path().join("link")).unwrap();
+    fs::symlink("missing", dir.path().join("missing-link")).unwrap();
+
+    cmd()
+        .arg("--blocks")
+        .arg("link_target_info,name")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match("^\\. 100 B +link ").unwrap())
+        .stdout(predicate::str::is_match("\nbroken +missing-link ").unwrap())
+        .stdout(predicate::str::is_match("\n- +target\n$").unwrap());
+}
+
+#[test]
+fn test_sort_dotfiles_first() {
+    let dir = tempdir();
+    dir.child("config.yaml")
+        .write_str("sorting:\n  dotfiles: first\n")
+        .unwrap();
+    for name in &["Cargo.toml", "README.md", ".gitignore", "build.rs", ".env"] {
+        dir.child("folder").child(name).touch().unwrap();
+    }
+
+    cmd()
+        .arg("--config-file")
+        .arg(dir.path().join("config.yaml"))
+        .arg("--almost-all")
+        .arg(dir.child("folder").path())
+        .assert()
+        .stdout(predicate::eq(
+            ".env\n.gitignore\nbuild.rs\nCargo.toml\nREADME.md\n",
+        ));
+
+    cmd()
+        .arg("--config-file")
+        .arg(dir.path().join("config.yaml"))
+        .arg("--almost-all")
+        .arg("--dotfiles")
+        .arg("last")
+        .arg(dir.child("folder").path())
+        .assert()
+        .stdout(predicate::eq(
+            "build.rs\nCargo.toml\nREADME.md\n.env\n.gitignore\n",
+        ));
+}
+
+#[test]
+fn test_stream() {
+    let dir = tempdir();
+    for i in 0..100 {
+        dir.child(format!("file-{:03}", i)).touch().unwrap();
+    }
+    dir.child(".hidden").touch().unwrap();
+
+    // The entries are listed in the order of the directory, so compare them sorted
+    let output = cmd()
+        .arg("--ignore-config")
+        .arg("--stream")
+        .arg(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.stderr.is_empty());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines: Vec<&str> = stdout.lines().collect();
+    lines.sort_unstable();
+    let expected: Vec<String> = (0..100).map(|i| format!("file-{:03}", i)).collect();
+    assert_eq!(expected, lines);
+}
+
+#[test]
+fn test_blocks_duplicate_warning() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("name,name")
+        .arg(dir.path())
+        .assert()
+        .stderr(predicate::str::contains(
+            "block 'name' is listed more than once",
+        ))
+        .stdout(predicate::eq("one one\n"));
+}
+
+#[test]
+fn test_date_invalid_format_in_config() {
+    let config = tempdir();
+    config
+        .child("config.yaml")
+        .write_str("date: +%J\n")
+        .unwrap();
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("two").touch().unwrap();
+
+    let output = cmd()
+        .arg("--config-file")
+        .arg(config.path().join("config.yaml"))
+        .arg("--blocks")
+        .arg("date,accessed,name")
+        .arg(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr,
+        "lsd: Not a valid date format: +%J, invalid date-time format: %J.\n\n"
+    );
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 2);
+}
+
+#[test]
+fn test_invalid_colors_in_config() {
+    let config = tempdir();
+    config
+        .child("config.yaml")
+        .write_str("block-colors:\n  size: notacolor\nname-colors:\n  '*.rs': '#12345'\n")
+        .unwrap();
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+
+    let output = cmd()
+        .arg("--config-file")
+        .arg(config.path().join("config.yaml"))
+        .arg(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr
+        .contains("lsd: Not a valid color: notacolor, for the block 'size' in block-colors.\n"));
+    assert!(stderr.contains(
+        "lsd: Not a valid hexadecimal color: #12345, expected #rrggbb, \
+         for the pattern '*.rs' in name-colors.\n"
+    ));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "one\n");
+}
+
+#[test]
+fn test_stream_disabled_when_sorting() {
+    let dir = tempdir();
+    dir.child("small").write_str("a").unwrap();
+    dir.child("big").write_str("abc").unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--stream")
+        .arg("--sizesort")
+        .arg(dir.path())
+        .assert()
+        .stderr(predicate::str::contains(
+            "--stream is disabled as sorting is requested",
+        ))
+        .stdout(predicate::eq("big\nsmall\n"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_list_block_caps() {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempdir();
+    dir.child("ping").touch().unwrap();
+    dir.child("plain").touch().unwrap();
+
+    // A third revision vfs_cap_data with cap_net_raw permitted and effective
+    let value: Vec<u8> = [0x0300_0001_u32, 1 << 13, 0, 0, 0, 0]
+        .iter()
+        .flat_map(|word| word.to_le_bytes().to_vec())
+        .collect();
+    let path = CString::new(dir.child("ping").path().as_os_str().as_bytes()).unwrap();
+    let res = unsafe {
+        libc::setxattr(
+            path.as_ptr(),
+            b"security.capability\0".as_ptr() as *const libc::c_char,
+            value.as_ptr() as *const libc::c_void,
+            value.len(),
+            0,
+        )
+    };
+    if res != 0 {
+        // Setting capabilities needs CAP_SETFCAP and a filesystem supporting them
+        return;
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("caps,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("cap_net_raw=ep ping\n-              plain\n"));
+
+    // The name is colored with the `ca` color of LS_COLORS
+    cmd()
+        .env("LS_COLORS", "ca=30;41")
+        .arg("--ignore-config")
+        .arg("--color")
+        .arg("always")
+        .arg("--icon")
+        .arg("never")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\u{1b}[41;30mping\u{1b}[0m"));
+}
+
+#[test]
+fn test_group_separators() {
+    let dir = tempdir();
+    dir.child("a.rs").touch().unwrap();
+    dir.child("b.rs").touch().unwrap();
+    dir.child("a.txt").touch().unwrap();
+    dir.child("c").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--extensionsort")
+        .arg("--group-separators")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("c\n\na.rs\nb.rs\n\na.txt\n"));
+}
+
+#[test]
+fn test_group_separators_extensions_differing_in_case() {
+    let dir = tempdir();
+    dir.child("a.JPG").touch().unwrap();
+    dir.child("b.jpg").touch().unwrap();
+    dir.child("c.png").touch().unwrap();
+    dir.child("d.Jpg").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--sort")
+        .arg("extension,name")
+        .arg("--group-separators")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("a.JPG\nb.jpg\nd.Jpg\n\nc.png\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--sort")
+        .arg("extension,name")
+        .arg("--group-separators")
+        .arg("--case-sensitive-extensions")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("a.JPG\n\nd.Jpg\n\nb.jpg\n\nc.png\n"));
+}
+
+#[test]
+fn test_respect_gitattributes() {
+    let repo = tempdir();
+    repo.child(".git").create_dir_all().unwrap();
+    repo.child(".gitattributes")
+        .write_str("/ci export-ignore\n*.bak export-ignore\n")
+        .unwrap();
+    repo.child("ci").create_dir_all().unwrap();
+    repo.child("notes.bak").touch().unwrap();
+    repo.child("src/lib.rs").touch().unwrap();
+    repo.child("src/lib.rs.bak").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--respect-gitattributes")
+        .arg("--recursive")
+        .arg(repo.path())
+        .assert()
+        .stdout(predicate::str::is_match("^src\n\n.*src:\nlib.rs\n$").unwrap());
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(repo.path())
+        .assert()
+        .stdout(predicate::eq("ci\nnotes.bak\nsrc\n"));
+}
+
+#[test]
+fn test_set_title() {
+    let dir = tempdir();
+    dir.child("one").create_dir_all().unwrap();
+    dir.child("two").create_dir_all().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--set-title")
+        .arg("--tty")
+        .arg("force")
+        .arg("--color")
+        .arg("never")
+        .arg("--icon")
+        .arg("never")
+        .arg(dir.path().join("one"))
+        .arg(dir.path().join("two"))
+        .assert()
+        .stdout(predicate::str::starts_with(format!(
+            "\x1B]2;{}, {}\x07",
+            root.join("one").display(),
+            root.join("two").display()
+        )));
+
+    // The title is only set on a terminal.
+    cmd()
+        .arg("--ignore-config")
+        .arg("--set-title")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("one\ntwo\n"));
+}
+
+#[test]
+fn test_date_groups() {
+    let dir = tempdir();
+    let now = std::time::SystemTime::now();
+    let day = std::time::Duration::from_secs(86_400);
+    for (name, age) in &[
+        ("new", std::time::Duration::from_secs(0)),
+        ("days", day * 3),
+        ("weeks", day * 20),
+        ("months", day * 90),
+        ("year", day * 400),
+    ] {
+        dir.child(name).touch().unwrap();
+        filetime::set_file_mtime(
+            dir.child(name).path(),
+            filetime::FileTime::from_system_time(now - *age),
+        )
+        .unwrap();
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--timesort")
+        .arg("--date-groups")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "Today\nnew\nThis Week\ndays\nThis Month\nweeks\nOlder\nmonths\nyear\n",
+        ));
+
+    // The groups are only listed when sorting by time.
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--date-groups")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("days\nmonths\nnew\nweeks\nyear\n"));
+}
+
+#[test]
+fn test_color_theme_preset() {
+    let dir = tempdir();
+    dir.child("folder").create_dir_all().unwrap();
+
+    // nord9, the directory color of the nord theme
+    cmd()
+        .arg("--ignore-config")
+        .arg("--color")
+        .arg("always")
+        .arg("--icon")
+        .arg("never")
+        .arg("--theme")
+        .arg("nord")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\u{1b}[38;2;129;161;193mfolder"));
+}
+
+#[test]
+fn test_color_theme_file() {
+    let dir = tempdir();
+    dir.child("folder").create_dir_all().unwrap();
+    let theme = tempdir();
+    theme
+        .child("theme.yaml")
+        .write_str("dir: '#102030'\n")
+        .unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--color")
+        .arg("always")
+        .arg("--icon")
+        .arg("never")
+        .arg("--color-theme")
+        .arg(theme.path().join("theme.yaml"))
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\u{1b}[38;2;16;32;48mfolder"));
+
+    theme
+        .child("theme.yaml")
+        .write_str("dir: '#1020'\n")
+        .unwrap();
+    cmd()
+        .arg("--ignore-config")
+        .arg("--color-theme")
+        .arg(theme.path().join("theme.yaml"))
+        .arg(dir.path())
+        .assert()
+        .stderr(predicate::str::contains(
+            "Not a valid hexadecimal color: #1020, expected #rrggbb, for the key 'dir' in the theme file",
+        ));
+}
+
+#[test]
+fn test_output_csv() {
+    let dir = tempdir();
+    dir.child("a,b.txt").touch().unwrap();
+    dir.child("quote\"d").touch().unwrap();
+    dir.child("plain").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--output")
+        .arg("csv")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg("--size")
+        .arg("bytes")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "size,name\n0,\"a,b.txt\"\n0,plain\n0,\"quote\"\"d\"\n",
+        ));
+}
+
+#[test]
+fn test_output_json() {
+    let dir = tempdir();
+    dir.child("file").write_str("abc").unwrap();
+
+    let output = cmd()
+        .arg("--ignore-config")
+        .arg("--json")
+        .arg(dir.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(json["version"], 1);
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["name"], "file");
+    assert_eq!(entries[0]["type"], "file");
+    assert_eq!(entries[0]["size"], 3);
+    assert!(entries[0]["target"].is_null());
+    for field in &[
+        "path",
+        "permissions",
+        "user",
+        "group",
+        "modified",
+        "accessed",
+        "inode",
+        "links",
+    ] {
+        assert!(entries[0].get(field).is_some(), "missing field {}", field);
+    }
+}
+
+#[test]
+fn test_output_json_truncated_at_depth() {
+    let dir = tempdir();
+    dir.child("one/two/three").create_dir_all().unwrap();
+    dir.child("one/file").touch().unwrap();
+
+    let output = cmd()
+        .arg("--ignore-config")
+        .arg("--depth")
+        .arg("1")
+        .arg("--tree")
+        .arg("--output")
+        .arg("json")
+        .arg(dir.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["name"], "one");
+    assert_eq!(entries[0]["truncated"], true);
+
+    let output = cmd()
+        .arg("--ignore-config")
+        .arg("--depth")
+        .arg("2")
+        .arg("--tree")
+        .arg("--output")
+        .arg("json")
+        .arg(dir.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let truncated: Vec<(&str, bool)> = json["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            (
+                entry["name"].as_str().unwrap(),
+                entry["truncated"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        truncated,
+        vec![("one", false), ("one/file", false), ("one/two", true)]
+    );
+}
+
+#[test]
+fn test_list_blocks() {
+    let mut assert = cmd().arg("--list-blocks").assert().success();
+    for block in &[
+        "permission",
+        "user",
+        "group",
+        "size",
+        "size_value",
+        "size_detail",
+        "date",
+        "name",
+        "inode",
+        "links",
+        "link_target_info",
+        "caps",
+        "fstype",
+        "flags",
+    ] {
+        assert = assert.stdout(predicate::str::contains(*block));
+    }
+}
+
+#[test]
+fn test_list_block_fstype_not_mount_point() {
+    let dir = tempdir();
+
+    cmd()
+        .arg("--directory-only")
+        .arg("--blocks")
+        .arg("fstype")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("-\n"));
+}
+
+#[test]
+fn test_truncate_long_names_keeps_extension_and_indicator() {
+    let dir = tempdir();
+    dir.child("a_very_long_directory").create_dir_all().unwrap();
+    dir.child("a_very_long_file_name.txt").touch().unwrap();
+    dir.child("short.txt").touch().unwrap();
+
+    cmd()
+        .arg("--truncate")
+        .arg("10")
+        .arg("--classify")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "a_ver\u{2026}tory/\na_ver\u{2026}.txt\nshort.txt\n",
+        ));
+}
+
+#[test]
+fn test_sort_none_in_directory_order() {
+    let dir = tempdir();
+    for name in &["b", "d", "a", "c"] {
+        dir.child(name).touch().unwrap();
+    }
+    let mut expected: String = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap() + "\n")
+        .collect();
+
+    // without the metadata of the entries
+    cmd()
+        .arg("--ignore-config")
+        .arg("--sort")
+        .arg("none")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(expected.as_str()));
+
+    // with the metadata of the entries
+    expected = expected.replace('\n', " 0 B\n");
+    cmd()
+        .arg("--ignore-config")
+        .arg("--sort")
+        .arg("none")
+        .arg("--blocks")
+        .arg("name,size")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(expected.as_str()));
+}
+
+#[cfg(unix)]
+#[test]
+#[ignore = "the permissions are not enforced for root, which the tests may be run as"]
+fn test_sort_none_does_not_stat_the_entries() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir();
+    dir.child("locked").create_dir_all().unwrap();
+    dir.child("locked/file").touch().unwrap();
+    let locked = dir.path().join("locked");
+    // the names can be read, but not the metadata of the entries
+    std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o444)).unwrap();
+
+    let unsorted = cmd()
+        .arg("--ignore-config")
+        .arg("--sort")
+        .arg("none")
+        .arg(&locked)
+        .assert();
+    let sorted = cmd().arg("--ignore-config").arg(&locked).assert();
+    std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    unsorted
+        .success()
+        .stdout(predicate::eq("file\n"))
+        .stderr(predicate::str::is_empty());
+    sorted.stderr(predicate::str::contains("Permission denied"));
+}
+
+#[test]
+fn test_normalize() {
+    let dir = tempdir();
+    // the first name is decomposed, like on macOS, the second one composed
+    dir.child("e\u{301}a").touch().unwrap();
+    dir.child("\u{e9}b").touch().unwrap();
+    dir.child("f").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("e\u{301}a\nf\n\u{e9}b\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--normalize")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("f\n\u{e9}a\n\u{e9}b\n"));
+}
+
+#[test]
+fn test_list_sorts() {
+    let mut assert = cmd().arg("--list-sorts").assert().success();
+    for sort in &[
+        "name",
+        "size",
+        "time",
+        "version",
+        "extension",
+        "owner",
+        "group",
+        "inode",
+        "random",
+        "recent",
+        "none",
+    ] {
+        assert = assert.stdout(predicate::str::contains(*sort));
+    }
+}
+
+fn three_levels_tempdir() -> assert_fs::TempDir {
+    let tmp = tempdir();
+    tmp.child("one.d/two.d/three.d").create_dir_all().unwrap();
+    tmp.child("one.d/two.d/three.d/four").touch().unwrap();
+    tmp
+}
+
+#[test]
+fn test_tree_reverse_keeps_hierarchy() {
+    let tmp = tempdir();
+    for name in &["a/1", "a/2", "b/3", "b/4", "c"] {
+        tmp.child(name).touch().unwrap();
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg("--reverse")
+        .arg(tmp.path())
+        .assert()
+        .stdout(
+            predicate::str::is_match(
+                "\n├── c\n├── b\n│   ├── 4\n│   └── 3\n└── a\n    ├── 2\n    └── 1\n$",
+            )
+            .unwrap(),
+        );
+}
+
+#[test]
+fn test_tree_group_directories_first_at_every_level() {
+    let tmp = tempdir();
+    for name in &[
+        "0file",
+        "a/0file",
+        "a/b_dir/0file",
+        "a/b_dir/z_file",
+        "a/b_dir/z_dir/x",
+    ] {
+        tmp.child(name).touch().unwrap();
+    }
+    tmp.child("z_dir").create_dir_all().unwrap();
+    let expected =
+        "\n├── a\n│   ├── b_dir\n│   │   ├── z_dir\n│   │   │   └── x\n│   │   ├── 0file\n\
+         │   │   └── z_file\n│   └── 0file\n├── z_dir\n└── 0file\n";
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg("--group-directories-first")
+        .arg(tmp.path())
+        .assert()
+        .stdout(predicate::str::ends_with(expected));
+
+    let config = tempdir();
+    config
+        .child("config.yaml")
+        .write_str("sorting:\n  dir-grouping: first\n")
+        .unwrap();
+    cmd()
+        .arg("--config-file")
+        .arg(config.path().join("config.yaml"))
+        .arg("--tree")
+        .arg(tmp.path())
+        .assert()
+        .stdout(predicate::str::ends_with(expected));
+}
+
+#[test]
+fn test_tree_depth() {
+    let tmp = three_levels_tempdir();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg("--depth")
+        .arg("1")
+        .arg(tmp.path())
+        .assert()
+        .stdout(predicate::str::is_match("\n└── one.d\n$").unwrap());
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg("--max-depth")
+        .arg("2")
+        .arg(tmp.path())
+        .assert()
+        .stdout(predicate::str::is_match("\n└── one.d\n    └── two.d\n$").unwrap());
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg(tmp.path())
+        .assert()
+        .stdout(
+            predicate::str::is_match(
+                "\n└── one.d\n    └── two.d\n        └── three.d\n            └── four\n$",
+            )
+            .unwrap(),
+        );
+}
+
+#[test]
+fn test_recursive_depth() {
+    let tmp = three_levels_tempdir();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--recursive")
+        .arg("--depth")
+        .arg("1")
+        .arg(tmp.path())
+        .assert()
+        .stdout(predicate::eq("one.d\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--recursive")
+        .arg("--max-depth")
+        .arg("2")
+        .arg(tmp.path())
+        .assert()
+        .stdout(predicate::str::is_match("^one.d\n\n.*one.d:\ntwo.d\n$").unwrap());
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--recursive")
+        .arg(tmp.path())
+        .assert()
+        .stdout(predicate::str::ends_with("three.d:\nfour\n"));
+}
+
+#[test]
+fn test_recurse_order() {
+    let tmp = three_levels_tempdir();
+    let pre = format!(
+        "one.d\n\n{0}/one.d:\ntwo.d\n\n{0}/one.d/two.d:\nthree.d\n\n{0}/one.d/two.d/three.d:\nfour\n",
+        tmp.path().display()
+    );
+    let post = format!(
+        "{0}/one.d/two.d/three.d:\nfour\n\n{0}/one.d/two.d:\nthree.d\n\n{0}/one.d:\ntwo.d\n\n{0}:\none.d\n",
+        tmp.path().display()
+    );
+
+    // with the total size, the whole tree is read before it is walked
+    for args in &[vec![], vec!["--total-size", "--blocks", "name"]] {
+        cmd()
+            .arg("--ignore-config")
+            .arg("--recursive")
+            .arg("--recurse-order")
+            .arg("pre")
+            .args(args)
+            .arg(tmp.path())
+            .assert()
+            .stdout(predicate::eq(pre.as_str()));
+
+        cmd()
+            .arg("--ignore-config")
+            .arg("--recursive")
+            .arg("--recurse-order")
+            .arg("post")
+            .args(args)
+            .arg(tmp.path())
+            .assert()
+            .stdout(predicate::eq(post.as_str()));
+    }
+}
+
+#[test]
+fn test_max_entries() {
+    let dir = tempdir();
+    for i in 0..100 {
+        dir.child(format!("file{:03}", i)).touch().unwrap();
+    }
+    dir.child("sub").create_dir_all().unwrap();
+    dir.child("sub/inner").touch().unwrap();
+
+    let output = cmd()
+        .arg("--ignore-config")
+        .arg("--max-entries")
+        .arg("10")
+        .arg(dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(11, lines.len());
+    assert_eq!("file000", lines[0]);
+    assert_eq!("file009", lines[9]);
+    assert_eq!("... and 91 more", lines[10]);
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--max-entries")
+        .arg("2")
+        .arg("--tree")
+        .arg("--ignore-glob")
+        .arg("file0[1-9]*")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with(
+            "├── file000\n├── file001\n└── ... and 9 more\n",
+        ));
+}
+
+#[test]
+fn test_nonexistent_path_exit_code() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(dir.path().join("nonexistent"))
+        .arg(dir.path().join("one"))
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("one"))
+        .stderr(predicate::str::contains("nonexistent"));
+}
+
+#[test]
+fn test_success_exit_code() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .code(0);
+}
+
+#[cfg(unix)]
+#[test]
+#[ignore = "the permissions are not enforced for root, which the tests may be run as"]
+fn test_permission_denied_directory() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir();
+    dir.child("locked").create_dir_all().unwrap();
+    dir.child("locked/file").touch().unwrap();
+    let locked = dir.path().join("locked");
+    std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+
+    let assert = cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg(dir.path())
+        .assert();
+    std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    assert
+        .code(2)
+        .stdout(predicate::str::contains("locked [unreadable]"))
+        .stderr(predicate::str::diff(format!(
+            "lsd: {}: Permission denied (os error 13).\n\n",
+            locked.display()
+        )));
+}
+
+fn cmd() -> Command {
+    Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap()
+}
+
+fn tempdir() -> assert_fs::TempDir {
+    assert_fs::TempDir::new().unwrap()
+}
+
+#[cfg(unix)]
+#[test]
+fn test_lower_case_name_icon_match() {
+    let dir = tempdir();
+    dir.child(".trash").touch().unwrap();
+    let test_file = dir.path().join(".trash");
+
+    cmd()
+        .arg("--icon")
+        .arg("always")
+        .arg("--ignore-config")
+        .arg(test_file)
+        .assert()
+        .stdout(predicate::str::contains("\u{f1f8}"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_upper_case_name_icon_match() {
+    let dir = tempdir();
+    dir.child(".TRASH").touch().unwrap();
+    let test_file = dir.path().join(".TRASH");
+
+    cmd()
+        .arg("--icon")
+        .arg("always")
+        .arg("--ignore-config")
+        .arg(test_file)
+        .assert()
+        .stdout(predicate::str::contains("\u{f1f8}"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_magic_icon_match() {
+    let dir = tempdir();
+    dir.child("image")
+        .write_binary(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")
+        .unwrap();
+    let test_file = dir.path().join("image");
+
+    cmd()
+        .arg("--icon")
+        .arg("always")
+        .arg("--ignore-config")
+        .arg("--magic")
+        .arg(&test_file)
+        .assert()
+        .stdout(predicate::str::contains("\u{f1c5}"));
+
+    cmd()
+        .arg("--icon")
+        .arg("always")
+        .arg("--ignore-config")
+        .arg(test_file)
+        .assert()
+        .stdout(predicate::str::contains("\u{f1c5}").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_lower_case_ext_icon_match() {
+    let dir = tempdir();
+    dir.child("test.7z").touch().unwrap();
+    let test_file = dir.path().join("test.7z");
+
+    cmd()
+        .arg("--icon")
+        .arg("always")
+        .arg("--ignore-config")
+        .arg(test_file)
+        .assert()
+        .stdout(predicate::str::contains("\u{f410}"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_upper_case_ext_icon_match() {
+    let dir = tempdir();
+    dir.child("test.7Z").touch().unwrap();
+    let test_file = dir.path().join("test.7Z");
+
+    cmd()
+        .arg("--icon")
+        .arg("always")
         .arg("--ignore-config")
         .arg(test_file)
         .assert()
-        .stdout(predicate::str::contains("\u{f410}"));
+        .stdout(predicate::str::contains("\u{f410}"));
+}
+
+#[test]
+fn test_date_formats_per_block() {
+    let dir = tempdir();
+    dir.child("config.yaml")
+        .write_str("date:\n  modified: +modified-%Y\n  accessed: +accessed-%Y\n")
+        .unwrap();
+    dir.child("folder").create_dir_all().unwrap();
+    dir.child("folder/file").touch().unwrap();
+
+    cmd()
+        .arg("--config-file")
+        .arg(dir.path().join("config.yaml"))
+        .arg("--blocks")
+        .arg("date,accessed,name")
+        .arg(dir.child("folder").path())
+        .assert()
+        .stdout(predicate::str::is_match("^modified-\\d{4} +accessed-\\d{4} +file\n$").unwrap());
+}
+
+#[test]
+fn test_block_align() {
+    let dir = tempdir();
+    dir.child("left.yaml")
+        .write_str("block-align:\n  size: left\n")
+        .unwrap();
+    dir.child("right.yaml")
+        .write_str("block-align:\n  size: right\n")
+        .unwrap();
+    dir.child("folder/big")
+        .write_str(&"a".repeat(1234))
+        .unwrap();
+    dir.child("folder/small").write_str("small").unwrap();
+
+    cmd()
+        .arg("--config-file")
+        .arg(dir.path().join("left.yaml"))
+        .arg("--blocks")
+        .arg("size,name")
+        .arg(dir.child("folder").path())
+        .assert()
+        .stdout(predicate::eq("1.2 KB big\n5 B    small\n"));
+
+    cmd()
+        .arg("--config-file")
+        .arg(dir.path().join("right.yaml"))
+        .arg("--blocks")
+        .arg("size,name")
+        .arg(dir.child("folder").path())
+        .assert()
+        .stdout(predicate::eq("1.2 KB big\n   5 B small\n"));
+}
+
+#[test]
+fn test_tty_force_keeps_grid_when_piped() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("two").touch().unwrap();
+    dir.child("three").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("one\nthree\ntwo\n"));
+
+    cmd()
+        .env("COLUMNS", "80")
+        .arg("--ignore-config")
+        .arg("--tty=force")
+        .arg("--icon=never")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("one  three  two\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_custom_config_file_parsing() {
+    let dir = tempdir();
+    dir.child("config.yaml").write_str("layout: tree").unwrap();
+    dir.child("folder").create_dir_all().unwrap();
+    dir.child("folder/file").touch().unwrap();
+    let custom_config = dir.path().join("config.yaml");
+
+    cmd()
+        .arg("--config-file")
+        .arg(custom_config)
+        .arg(dir.child("folder").path())
+        .assert()
+        .stdout(predicate::str::is_match("folder\n└── file").unwrap());
 }
 
 #[cfg(unix)]
 #[test]
-fn test_custom_config_file_parsing() {
+fn test_custom_config_file_profile() {
     let dir = tempdir();
-    dir.child("config.yaml").write_str("layout: tree").unwrap();
+    dir.child("config.yaml")
+        .write_str("layout: oneline\nprofiles:\n  work:\n    layout: tree\n")
+        .unwrap();
     dir.child("folder").create_dir_all().unwrap();
     dir.child("folder/file").touch().unwrap();
     let custom_config = dir.path().join("config.yaml");
 
     cmd()
         .arg("--config-file")
-        .arg(custom_config)
+        .arg(&custom_config)
+        .arg("--profile")
+        .arg("work")
         .arg(dir.child("folder").path())
         .assert()
         .stdout(predicate::str::is_match("folder\n└── file").unwrap());
+
+    cmd()
+        .arg("--config-file")
+        .arg(&custom_config)
+        .arg("--profile")
+        .arg("home")
+        .arg(dir.child("folder").path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Profile home is not defined"));
+}
+
+#[test]
+fn test_classic_verbose_notes_overridden_options() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--classic")
+        .arg("--verbose")
+        .arg("--group-dirs")
+        .arg("first")
+        .arg("--icon")
+        .arg("always")
+        .arg("--size")
+        .arg("bytes")
+        .arg(dir.path())
+        .assert()
+        .stderr(predicate::str::contains(
+            "note: the classic mode overrides --icon always with never.",
+        ))
+        .stderr(predicate::str::contains(
+            "note: the classic mode overrides --group-dirs first with none.",
+        ))
+        .stderr(predicate::str::contains("--size").not());
+
+    // The notes are only printed with --verbose.
+    cmd()
+        .arg("--ignore-config")
+        .arg("--classic")
+        .arg("--group-dirs")
+        .arg("first")
+        .arg(dir.path())
+        .assert()
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_verbose_notes_resolved_settings() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--verbose")
+        .arg("--long")
+        .arg("--timesort")
+        .arg("--reverse")
+        .arg("--color")
+        .arg("auto")
+        .arg(dir.path())
+        .assert()
+        .stderr(predicate::str::contains("note: tty: no."))
+        .stderr(predicate::str::contains("note: layout: oneline."))
+        .stderr(predicate::str::contains(
+            "note: sort: time reversed (oldest first), directories grouped none.",
+        ))
+        .stderr(predicate::str::contains("note: color: never."))
+        .stderr(predicate::str::contains("note: icons: never."));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--verbose")
+        .arg("--tty")
+        .arg("force")
+        .arg("--icon")
+        .arg("always")
+        .arg("--icon-theme")
+        .arg("unicode")
+        .arg(dir.path())
+        .assert()
+        .stderr(predicate::str::contains("note: tty: yes."))
+        .stderr(predicate::str::contains("note: layout: grid."))
+        .stderr(predicate::str::contains(
+            "note: icons: always, with the unicode theme.",
+        ));
+
+    // The default direction depends on the column, and a sort key may reverse it by itself.
+    for (args, direction) in &[
+        (vec!["--sizesort"], "size default (largest first)"),
+        (vec!["--sort", "+size"], "size reversed (smallest first)"),
+        (vec!["--sort", "-name"], "name reversed (descending)"),
+        (
+            vec!["--sort", "-name", "--reverse"],
+            "name default (ascending)",
+        ),
+    ] {
+        cmd()
+            .arg("--ignore-config")
+            .arg("--verbose")
+            .args(args)
+            .arg(dir.path())
+            .assert()
+            .stderr(predicate::str::contains(format!(
+                "note: sort: {},",
+                direction
+            )));
+    }
+}
+
+#[test]
+fn test_min_columns() {
+    let dir = tempdir();
+    for name in &["aaa", "bbb", "ccc", "ddd"] {
+        dir.child(name).touch().unwrap();
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tty")
+        .arg("force")
+        .arg("--color")
+        .arg("never")
+        .arg("--icon")
+        .arg("never")
+        .arg("--min-columns")
+        .arg("4")
+        .env("COLUMNS", "300")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("aaa  bbb  ccc  ddd\n"));
+
+    // Without a terminal the grid is printed in the minimum columns.
+    cmd()
+        .arg("--ignore-config")
+        .arg("--min-columns")
+        .arg("2")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("aaa  ccc\nbbb  ddd\n"));
+
+    // The names too wide for the minimum columns fall back to the natural packing.
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tty")
+        .arg("force")
+        .arg("--color")
+        .arg("never")
+        .arg("--icon")
+        .arg("never")
+        .arg("--min-columns")
+        .arg("4")
+        .env("COLUMNS", "10")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("aaa  ccc\nbbb  ddd\n"));
+}
+
+#[test]
+fn test_sort_random_seed() {
+    let dir = tempdir();
+    for index in 0..20 {
+        dir.child(format!("file{:02}", index)).touch().unwrap();
+    }
+    dir.child("dir").create_dir_all().unwrap();
+
+    let list = |seed: &str| {
+        let output = cmd()
+            .arg("--ignore-config")
+            .arg("--sort")
+            .arg("random")
+            .arg("--seed")
+            .arg(seed)
+            .arg("--group-dirs")
+            .arg("first")
+            .arg(dir.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let first = list("1");
+    assert_eq!(first, list("1"));
+    assert_ne!(first, list("2"));
+    // The directories are still grouped first.
+    assert!(first.starts_with("dir\n"));
+    assert_eq!(21, first.lines().count());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_stat_prints_every_attribute() {
+    let dir = tempdir();
+    dir.child("file.txt").write_str("hello").unwrap();
+
+    let mut assert = cmd()
+        .arg("--ignore-config")
+        .arg("--stat")
+        .arg(dir.path().join("file.txt"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("       Name: "))
+        .stdout(predicate::str::contains("file.txt\n"))
+        .stdout(predicate::str::contains("       Type: file\n"))
+        .stdout(predicate::str::contains("       Size: 5 B (5 bytes)\n"))
+        .stdout(predicate::str::is_match("\n +Blocks: \\d+\n").unwrap())
+        .stdout(predicate::str::contains("Permissions: 0"))
+        .stdout(predicate::str::is_match("\n +Inode: \\d+\n").unwrap())
+        .stdout(predicate::str::contains("      Links: 1\n"));
+    for label in &["Modified", "Accessed", "Changed", "User", "Group"] {
+        assert = assert.stdout(predicate::str::is_match(format!("\n +{}: \\S", label)).unwrap());
+    }
+
+    // A directory is described itself, not its content.
+    cmd()
+        .arg("--ignore-config")
+        .arg("--stat")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("       Type: directory\n"))
+        .stdout(predicate::str::contains("file.txt").not());
+}
+
+#[test]
+fn test_compound_extensions_sort() {
+    let dir = tempdir();
+    for name in &["a.tar.gz", "b.gz", "c.tar.gz", "d.tar"] {
+        dir.child(name).touch().unwrap();
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--sort")
+        .arg("extension,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("a.tar.gz\nb.gz\nc.tar.gz\nd.tar\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--sort")
+        .arg("extension,name")
+        .arg("--compound-extensions")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("b.gz\nd.tar\na.tar.gz\nc.tar.gz\n"));
+}
+
+#[cfg(windows)]
+#[test]
+fn test_list_path_longer_than_max_path() {
+    let dir = tempdir();
+    let mut path = dir.path().to_path_buf();
+    for level in 0..6 {
+        path.push(format!("{}{}", "d".repeat(50), level));
+    }
+    assert!(path.as_os_str().len() > 260);
+    std::fs::create_dir_all(&path).unwrap();
+    std::fs::write(path.join("deep.txt"), "deep").unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::eq("deep.txt\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("deep.txt"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_quote_name() {
+    let dir = tempdir();
+    dir.child("plain").touch().unwrap();
+    dir.child("say \"hi\"").touch().unwrap();
+    dir.child("new\nline").touch().unwrap();
+
+    let expected = "\"new\\nline\"\n\"plain\"\n\"say \\\"hi\\\"\"\n";
+    cmd()
+        .arg("--ignore-config")
+        .arg("-Q")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(expected));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--quote-name")
+        .arg("--tree")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("└── \"say \\\"hi\\\"\""));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_control_chars() {
+    let dir = tempdir();
+    dir.child("new\nline").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tty")
+        .arg("force")
+        .arg("--color")
+        .arg("never")
+        .arg("--icon")
+        .arg("never")
+        .arg("--oneline")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("new?line\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("new\\nline\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--show-control-chars")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("new\nline\n"));
+}
+
+#[test]
+fn test_summary() {
+    let dir = tempdir();
+    for name in &[
+        ".a",
+        ".b",
+        "one.tmp",
+        "file",
+        "sub/.c",
+        "sub/two.tmp",
+        "sub/file",
+    ] {
+        dir.child(name).touch().unwrap();
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--ignore-glob")
+        .arg("*.tmp")
+        .arg("--summary")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("file\nsub\n(2 hidden, 1 ignored)\n"));
+
+    for layout in &["--recursive", "--tree"] {
+        cmd()
+            .arg("--ignore-config")
+            .arg("--ignore-glob")
+            .arg("*.tmp")
+            .arg("--summary")
+            .arg(layout)
+            .arg(dir.path())
+            .assert()
+            .stdout(predicate::str::ends_with("(3 hidden, 2 ignored)\n"));
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--summary")
+        .arg("--all")
+        .arg(dir.path().join("sub"))
+        .assert()
+        .stdout(predicate::str::contains("hidden").not());
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--ignore-glob")
+        .arg("*.tmp")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("ignored").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_size() {
+    let dir = tempdir();
+    dir.child("target").write_str(&"a".repeat(500)).unwrap();
+    fs::symlink("target", dir.path().join("link")).unwrap();
+    fs::symlink("nowhere", dir.path().join("broken")).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg("--symlink-size")
+        .arg("target")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "  -   broken ⇒ nowhere\n500 B link ⇒ target\n500 B target\n",
+        ));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg("--symlink-size")
+        .arg("none")
+        .arg(dir.path().join("link"))
+        .assert()
+        .stdout(predicate::str::is_match("^- .*link ⇒ target\n$").unwrap());
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg(dir.path().join("link"))
+        .assert()
+        .stdout(predicate::str::is_match("^6 B .*link ⇒ target\n$").unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_quoting_style_invalid_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempdir();
+    std::fs::File::create(dir.path().join(OsStr::from_bytes(b"caf\xe9"))).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--quoting-style")
+        .arg("escape")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("caf\\xe9\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--quoting-style")
+        .arg("locale")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("‘caf\\xe9’\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--quoting-style")
+        .arg("c")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("\"caf\\xe9\"\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_external_formatter_cat() {
+    let dir = tempdir();
+    dir.child("file").write_str("abc").unwrap();
+
+    let json = cmd()
+        .arg("--ignore-config")
+        .arg("--json")
+        .arg(dir.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--external-formatter")
+        .arg("cat")
+        .arg(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::eq(json.as_slice()));
+
+    // A recursive listing is formatted as a whole too, the access times aside.
+    dir.child("sub/nested").touch().unwrap();
+    cmd()
+        .arg("--ignore-config")
+        .arg("--external-formatter")
+        .arg("cat")
+        .arg("--recursive")
+        .arg(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("{\n  \"version\": 1,"))
+        .stdout(predicate::str::contains("\"name\": \"sub/nested\","));
+}
+
+#[test]
+fn test_layout_non_tty_when_piped() {
+    let dir = tempdir();
+    dir.child("a").touch().unwrap();
+    dir.child("config.yaml")
+        .write_str("layout: grid\nlayout-non-tty: tree\n")
+        .unwrap();
+
+    cmd()
+        .arg("--config-file")
+        .arg(dir.path().join("config.yaml"))
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with("├── a\n└── config.yaml\n"));
+
+    // a layout given as argument is used whatever the output
+    cmd()
+        .arg("--config-file")
+        .arg(dir.path().join("config.yaml"))
+        .arg("--oneline")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("a\nconfig.yaml\n"));
+}
+
+#[test]
+fn test_external_formatter_missing() {
+    let dir = tempdir();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--external-formatter")
+        .arg("lsd-missing-formatter")
+        .arg(dir.path())
+        .assert()
+        .code(2)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "failed to run the external formatter",
+        ));
 }